@@ -0,0 +1,168 @@
+use crate::data_paths::{app_data_root, read_store, write_store};
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// ── App lock ───────────────────────────────────────────────────────────────
+// An optional password, stored as an Argon2 hash in the profile's data
+// folder. While it is set, sensitive commands refuse to run until
+// `unlock_session` succeeds; the unlocked state lasts until the app exits.
+// The password also keys cookie encryption (see `vault`).
+
+const APP_LOCK_FILE: &str = "app_lock.json";
+
+static UNLOCKED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Serialize, Deserialize)]
+struct AppLockFile {
+    /// PHC-format Argon2 hash.
+    hash: String,
+    /// Salt for deriving the cookie encryption key. Missing in lock files
+    /// written before cookie encryption existed.
+    #[serde(default)]
+    kdf_salt: String,
+}
+
+#[derive(Serialize)]
+pub struct AppLockStatus {
+    pub enabled: bool,
+    pub unlocked: bool,
+}
+
+fn app_lock_path() -> PathBuf {
+    app_data_root().join(APP_LOCK_FILE)
+}
+
+fn read_lock_file() -> Option<AppLockFile> {
+    let raw = std::fs::read_to_string(app_lock_path()).ok()?;
+    read_store(&raw)
+}
+
+fn write_lock_file(file: &AppLockFile) -> Result<(), String> {
+    let path = app_lock_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(path, write_store(file, true)?).map_err(|e| e.to_string())
+}
+
+fn password_matches(hash: &str, password: &str) -> bool {
+    PasswordHash::new(hash)
+        .map(|parsed| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+fn derive_key(password: &str, salt: &str) -> Result<[u8; 32], String> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt.as_bytes(), &mut key)
+        .map_err(|e| e.to_string())?;
+    Ok(key)
+}
+
+pub fn is_enabled() -> bool {
+    app_lock_path().exists()
+}
+
+/// Errors while a lock is set and this session has not been unlocked.
+pub fn ensure_unlocked() -> Result<(), String> {
+    if UNLOCKED.load(Ordering::Relaxed) || !is_enabled() {
+        return Ok(());
+    }
+    Err("LIBMALY is locked. Unlock it first.".to_string())
+}
+
+#[tauri::command]
+pub fn get_app_lock_status() -> AppLockStatus {
+    let enabled = is_enabled();
+    AppLockStatus {
+        enabled,
+        unlocked: !enabled || UNLOCKED.load(Ordering::Relaxed),
+    }
+}
+
+/// Sets or changes the password. Changing it needs an unlocked session.
+#[tauri::command]
+pub fn set_app_lock(password: String) -> Result<(), String> {
+    ensure_unlocked()?;
+    if password.is_empty() {
+        return Err("Password cannot be empty".to_string());
+    }
+    let hash = Argon2::default()
+        .hash_password(password.as_bytes(), &SaltString::generate(&mut OsRng))
+        .map_err(|e| e.to_string())?
+        .to_string();
+    let kdf_salt = SaltString::generate(&mut OsRng).as_str().to_string();
+    let key = derive_key(&password, &kdf_salt)?;
+
+    // Read the cookies with the old key before it is replaced.
+    crate::metadata::preload_sessions();
+    write_lock_file(&AppLockFile { hash, kdf_salt })?;
+    crate::vault::set_password_key(Some(key));
+    UNLOCKED.store(true, Ordering::Relaxed);
+    crate::metadata::resave_sessions();
+    Ok(())
+}
+
+/// Checks a password without unlocking anything.
+#[tauri::command]
+pub fn verify_app_lock(password: String) -> bool {
+    match read_lock_file() {
+        Some(file) => password_matches(&file.hash, &password),
+        None => true,
+    }
+}
+
+#[tauri::command]
+pub fn unlock_session(password: String) -> Result<bool, String> {
+    let mut file = match read_lock_file() {
+        Some(f) => f,
+        None => return Ok(true),
+    };
+    if !password_matches(&file.hash, &password) {
+        crate::push_rust_log(None, "warn", "Failed unlock attempt");
+        return Ok(false);
+    }
+    let upgrade = file.kdf_salt.is_empty();
+    if upgrade {
+        file.kdf_salt = SaltString::generate(&mut OsRng).as_str().to_string();
+        write_lock_file(&file)?;
+    }
+    crate::vault::set_password_key(Some(derive_key(&password, &file.kdf_salt)?));
+    UNLOCKED.store(true, Ordering::Relaxed);
+    // Cookie files may have been unreadable until now.
+    crate::metadata::reload_sessions();
+    if upgrade {
+        crate::metadata::preload_sessions();
+        crate::metadata::resave_sessions();
+    }
+    Ok(true)
+}
+
+#[tauri::command]
+pub fn clear_app_lock(current_password: String) -> Result<(), String> {
+    let file = match read_lock_file() {
+        Some(f) => f,
+        None => return Ok(()),
+    };
+    if !password_matches(&file.hash, &current_password) {
+        return Err("Incorrect password".to_string());
+    }
+    if !file.kdf_salt.is_empty() {
+        crate::vault::set_password_key(Some(derive_key(&current_password, &file.kdf_salt)?));
+        crate::metadata::reload_sessions();
+        crate::metadata::preload_sessions();
+    }
+    std::fs::remove_file(app_lock_path()).map_err(|e| e.to_string())?;
+    crate::vault::set_password_key(None);
+    // Re-encrypt with the keyring key (or write plaintext if encryption is off).
+    crate::metadata::resave_sessions();
+    Ok(())
+}
@@ -0,0 +1,464 @@
+use crate::updater::{extract_zip_with_progress, unwrap_single_dir};
+use crate::{find_best_exe_in_install_dir, is_blocked, is_generic_name, Game, ScanOptions};
+use serde::Serialize;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use tauri::Emitter;
+use walkdir::WalkDir;
+
+// ── Archived games ─────────────────────────────────────────────────────────
+// Games often sit in the zip they were downloaded as. These helpers list an
+// archive's entries without extracting it, so such games can be offered for
+// extraction. Zips are read natively; 7z goes through the `7z` binary.
+
+const ARCHIVE_EXTS: &[&str] = &["zip", "7z"];
+
+#[derive(Serialize, Clone)]
+pub struct ArchivedGame {
+    pub name: String,
+    pub archive_path: String,
+    /// Path of the launcher inside the archive, `/`-separated.
+    pub inner_exe: String,
+    pub archive_size: u64,
+}
+
+/// One file entry of an archive listing.
+pub struct ArchiveEntry {
+    /// `/`-separated path inside the archive.
+    pub path: String,
+    pub size: u64,
+}
+
+/// The `7z` executable, from PATH or the default 7-Zip install folder.
+pub fn seven_zip_binary() -> Option<PathBuf> {
+    #[cfg(windows)]
+    {
+        for dir in ["ProgramFiles", "ProgramFiles(x86)"] {
+            if let Ok(base) = std::env::var(dir) {
+                let exe = Path::new(&base).join("7-Zip").join("7z.exe");
+                if exe.is_file() {
+                    return Some(exe);
+                }
+            }
+        }
+        std::env::var_os("PATH").and_then(|paths| {
+            std::env::split_paths(&paths)
+                .map(|dir| dir.join("7z.exe"))
+                .find(|p| p.is_file())
+        })
+    }
+    #[cfg(not(windows))]
+    {
+        ["7z", "7zz", "7za"]
+            .iter()
+            .find(|name| crate::command_in_path(name))
+            .map(PathBuf::from)
+    }
+}
+
+fn seven_zip_command(bin: &Path) -> Command {
+    #[allow(unused_mut)]
+    let mut cmd = Command::new(bin);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+    cmd
+}
+
+fn list_zip(path: &Path) -> Result<Vec<ArchiveEntry>, String> {
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    // Only the central directory is read; nothing is decompressed.
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let mut entries = Vec::new();
+    for i in 0..archive.len() {
+        let entry = archive.by_index_raw(i).map_err(|e| e.to_string())?;
+        if entry.is_dir() {
+            continue;
+        }
+        if let Some(name) = entry.enclosed_name() {
+            entries.push(ArchiveEntry {
+                path: name.to_string_lossy().replace('\\', "/"),
+                size: entry.size(),
+            });
+        }
+    }
+    Ok(entries)
+}
+
+/// Parses `7z l -slt` output: blank-line separated `Key = Value` blocks.
+fn list_7z(path: &Path) -> Result<Vec<ArchiveEntry>, String> {
+    let bin = seven_zip_binary().ok_or("7-Zip is not installed; cannot read .7z archives")?;
+    let output = seven_zip_command(&bin)
+        .args(["l", "-slt", "-ba"])
+        .arg(path)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(format!(
+            "7z could not list {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let mut entries = Vec::new();
+    let text = String::from_utf8_lossy(&output.stdout);
+    for block in text.split("\n\n") {
+        let mut name: Option<String> = None;
+        let mut size = 0u64;
+        let mut is_dir = false;
+        for line in block.lines() {
+            let Some((key, value)) = line.split_once(" = ") else {
+                continue;
+            };
+            match key.trim() {
+                "Path" => name = Some(value.trim().replace('\\', "/")),
+                "Size" => size = value.trim().parse().unwrap_or(0),
+                "Folder" => is_dir = value.trim() == "+",
+                "Attributes" => is_dir |= value.trim().starts_with('D'),
+                _ => {}
+            }
+        }
+        if let Some(path) = name.filter(|_| !is_dir) {
+            entries.push(ArchiveEntry { path, size });
+        }
+    }
+    Ok(entries)
+}
+
+fn archive_ext(path: &Path) -> String {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Lists the files of a `.zip` or `.7z` without extracting it.
+pub fn list_archive(path: &Path) -> Result<Vec<ArchiveEntry>, String> {
+    match archive_ext(path).as_str() {
+        "zip" => list_zip(path),
+        "7z" => list_7z(path),
+        _ => Err(format!("Unsupported archive: {}", path.display())),
+    }
+}
+
+/// The single directory every entry sits in, if there is one
+/// (`game-v2.0/game.exe` style packaging).
+pub fn single_top_dir(entries: &[ArchiveEntry]) -> Option<String> {
+    let mut top: Option<&str> = None;
+    for entry in entries {
+        let (first, _) = entry.path.split_once('/')?;
+        match top {
+            Some(t) if t != first => return None,
+            _ => top = Some(first),
+        }
+    }
+    top.map(|t| t.to_string())
+}
+
+/// Picks the archive's launcher: a top-level exe (below a single wrapper
+/// directory, if any) that the scanner would not block. Descriptive names
+/// beat generic ones, then the larger file wins.
+fn archived_game(archive: &Path, options: &ScanOptions) -> Option<ArchivedGame> {
+    let entries = list_archive(archive).ok()?;
+    let top = single_top_dir(&entries);
+    let prefix = top.as_ref().map(|t| format!("{t}/")).unwrap_or_default();
+
+    let best = entries
+        .iter()
+        .filter_map(|entry| {
+            let rel = entry.path.strip_prefix(&prefix)?;
+            if rel.contains('/') || !rel.to_lowercase().ends_with(".exe") {
+                return None;
+            }
+            let stem = &rel[..rel.len() - 4];
+            if is_blocked(stem, &entry.path, options) {
+                return None;
+            }
+            Some((!is_generic_name(stem), entry.size, entry, stem))
+        })
+        .max_by_key(|(descriptive, size, _, _)| (*descriptive, *size))?;
+    let (descriptive, _, entry, stem) = best;
+
+    let archive_stem = archive
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name = if descriptive {
+        stem.to_string()
+    } else {
+        top.unwrap_or(archive_stem)
+    };
+    Some(ArchivedGame {
+        name,
+        archive_path: archive.to_string_lossy().into_owned(),
+        inner_exe: entry.path.clone(),
+        archive_size: archive.metadata().map(|m| m.len()).unwrap_or(0),
+    })
+}
+
+/// Finds `.zip`/`.7z` archives under `path` that hold a game launcher, reading
+/// only their listings. `.7z` archives are skipped when 7-Zip is missing.
+#[tauri::command]
+pub fn scan_archives(
+    path: String,
+    options: Option<ScanOptions>,
+) -> Result<Vec<ArchivedGame>, String> {
+    let root = Path::new(&path);
+    if !root.is_dir() {
+        return Err(format!("Not a folder: {path}"));
+    }
+    let options = options.unwrap_or_default();
+    let games = WalkDir::new(root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| ARCHIVE_EXTS.contains(&archive_ext(e.path()).as_str()))
+        .filter_map(|e| archived_game(e.path(), &options))
+        .collect();
+    Ok(games)
+}
+
+// ── Extraction ─────────────────────────────────────────────────────────────
+
+/// Sent as `extract-progress` while `extract_game` runs.
+#[derive(Serialize, Clone)]
+struct ExtractProgress {
+    archive_path: String,
+    percent: u8,
+}
+
+/// Extracts a `.7z` with the 7z binary, reporting the percentages it prints
+/// with `-bsp1`. Progress updates are separated by `\r` or backspaces.
+pub(crate) fn extract_7z(
+    archive: &Path,
+    dest: &Path,
+    mut on_percent: impl FnMut(u8),
+) -> Result<(), String> {
+    let bin = seven_zip_binary().ok_or("7-Zip is not installed; cannot extract .7z archives")?;
+    let mut child = seven_zip_command(&bin)
+        .args(["x", "-y", "-bso0", "-bsp1"])
+        .arg(format!("-o{}", dest.display()))
+        .arg(archive)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    // Drained on its own thread so a chatty 7z cannot fill the pipe and
+    // block while we are still reading progress from stdout.
+    let stderr = child.stderr.take().map(|mut pipe| {
+        std::thread::spawn(move || {
+            let mut buf = String::new();
+            let _ = pipe.read_to_string(&mut buf);
+            buf
+        })
+    });
+    if let Some(stdout) = child.stdout.take() {
+        let mut chunk = Vec::new();
+        for byte in std::io::BufReader::new(stdout).bytes() {
+            let byte = byte.map_err(|e| e.to_string())?;
+            if !matches!(byte, b'\r' | b'\n' | 0x08) {
+                chunk.push(byte);
+                continue;
+            }
+            let text = String::from_utf8_lossy(&chunk);
+            if let Some((pct, _)) = text.trim().split_once('%') {
+                if let Ok(pct) = pct.trim().parse::<u8>() {
+                    on_percent(pct);
+                }
+            }
+            chunk.clear();
+        }
+    }
+    let status = child.wait().map_err(|e| e.to_string())?;
+    let stderr = stderr
+        .and_then(|handle| handle.join().ok())
+        .unwrap_or_default();
+    if !status.success() {
+        return Err(format!(
+            "7z could not extract {} ({status}): {}",
+            archive.display(),
+            stderr.trim()
+        ));
+    }
+    Ok(())
+}
+
+/// `dest/name`, or `dest/name (2)`, `dest/name (3)`… if that is taken.
+fn unique_dir(dest: &Path, name: &str) -> PathBuf {
+    let mut candidate = dest.join(name);
+    let mut n = 2;
+    while candidate.exists() {
+        candidate = dest.join(format!("{name} ({n})"));
+        n += 1;
+    }
+    candidate
+}
+
+fn extract_into(
+    app: &tauri::AppHandle,
+    archive: &Path,
+    dest: &Path,
+    options: &ScanOptions,
+) -> Result<Game, String> {
+    let archive_path = archive.to_string_lossy().into_owned();
+    let mut last_percent: Option<u8> = None;
+    let mut report = |percent: u8| {
+        if last_percent != Some(percent) {
+            last_percent = Some(percent);
+            let _ = app.emit(
+                "extract-progress",
+                ExtractProgress {
+                    archive_path: archive_path.clone(),
+                    percent,
+                },
+            );
+        }
+    };
+
+    // Extract next to the final folder so moving it into place is a rename.
+    let staging = dest.join(format!(".libmaly_extract_{}", crate::now_ms()));
+    let extracted = match archive_ext(archive).as_str() {
+        "zip" => extract_zip_with_progress(archive, &staging, |done, total| {
+            report((done * 100 / total.max(1)) as u8)
+        }),
+        "7z" => {
+            std::fs::create_dir_all(&staging).map_err(|e| e.to_string())?;
+            extract_7z(archive, &staging, &mut report)
+        }
+        _ => Err(format!("Unsupported archive: {}", archive.display())),
+    };
+    if let Err(e) = extracted {
+        let _ = std::fs::remove_dir_all(&staging);
+        return Err(format!("Extraction failed: {e}"));
+    }
+    report(100);
+
+    let root = unwrap_single_dir(&staging);
+    let name = if root != staging {
+        root.file_name()
+    } else {
+        archive.file_stem()
+    }
+    .map(|n| n.to_string_lossy().into_owned())
+    .unwrap_or_else(|| "Game".to_string());
+    let target = unique_dir(dest, &name);
+    let moved = std::fs::rename(&root, &target);
+    let _ = std::fs::remove_dir_all(&staging);
+    moved.map_err(|e| format!("Failed to move extracted files into place: {e}"))?;
+
+    let exe = find_best_exe_in_install_dir(&target.to_string_lossy())
+        .ok_or_else(|| format!("No launcher found in {}", target.display()))?;
+    let exe_path = Path::new(&exe);
+    let exe_dir = exe_path.parent().unwrap_or(&target);
+    // Prefer the scanner's view of the exe so naming and engine detection
+    // match a regular library scan.
+    if let Some(game) = crate::scan_dir_shallow(exe_dir, options)
+        .into_iter()
+        .find(|g| g.path == exe)
+    {
+        return Ok(game);
+    }
+    let meta = exe_path.metadata().ok();
+    Ok(Game {
+        name: target
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or(name),
+        path: exe.clone(),
+        is_demo: false,
+        engine: crate::detect_engine(exe_dir),
+        size_bytes: meta.as_ref().map(|m| m.len()).unwrap_or(0),
+        modified: meta
+            .and_then(|m| m.modified().ok())
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        source_dir: None,
+    })
+}
+
+/// Lowercase hex SHA-256 of the file at `path`, read in chunks so large
+/// archives are never held in memory.
+pub(crate) fn sha256_file(path: &Path) -> Result<String, String> {
+    use sha2::{Digest, Sha256};
+    let mut file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).map_err(|e| e.to_string())?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
+}
+
+/// Trims and lowercases a user-supplied SHA-256, rejecting anything that is
+/// not 64 hex digits.
+fn normalize_sha256(raw: &str) -> Result<String, String> {
+    let hash = raw.trim().to_lowercase();
+    if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Not a SHA-256 hash: {}", raw.trim()));
+    }
+    Ok(hash)
+}
+
+/// Errors unless the file at `path` hashes to `expected`.
+pub(crate) fn ensure_sha256(path: &Path, expected: &str) -> Result<(), String> {
+    let expected = normalize_sha256(expected)?;
+    let actual = sha256_file(path)?;
+    if actual != expected {
+        return Err(format!(
+            "Checksum mismatch for {}: expected {expected}, got {actual}. The file may be corrupt or incomplete",
+            path.display()
+        ));
+    }
+    Ok(())
+}
+
+/// Whether the file at `path` has the SHA-256 `sha256`.
+#[tauri::command]
+pub async fn verify_file_hash(path: String, sha256: String) -> Result<bool, String> {
+    let expected = normalize_sha256(&sha256)?;
+    let path = PathBuf::from(path);
+    if !path.is_file() {
+        return Err(format!("File not found: {}", path.display()));
+    }
+    tauri::async_runtime::spawn_blocking(move || Ok(sha256_file(&path)? == expected))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Extracts a `.zip`/`.7z` into a new folder under `dest_dir` (named after
+/// the archive's wrapper directory or file, with ` (2)` etc. on collisions)
+/// and returns the game entry for its best launcher, ready to add to the
+/// library. Emits `extract-progress` while extracting; a failed extraction
+/// leaves nothing behind. With `expected_sha256` the archive is hashed first
+/// and nothing is extracted on a mismatch.
+#[tauri::command]
+pub async fn extract_game(
+    app: tauri::AppHandle,
+    archive_path: String,
+    dest_dir: String,
+    options: Option<ScanOptions>,
+    expected_sha256: Option<String>,
+) -> Result<Game, String> {
+    let archive = PathBuf::from(&archive_path);
+    if !archive.is_file() {
+        return Err(format!("Archive not found: {archive_path}"));
+    }
+    if dest_dir.trim().is_empty() {
+        return Err("Destination path is empty".to_string());
+    }
+    let dest = PathBuf::from(&dest_dir);
+    std::fs::create_dir_all(&dest).map_err(|e| e.to_string())?;
+    let options = options.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Some(expected) = expected_sha256.as_deref() {
+            ensure_sha256(&archive, expected)?;
+        }
+        extract_into(&app, &archive, &dest, &options)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,85 @@
+use crate::settings;
+use auto_launch::{AutoLaunch, AutoLaunchBuilder};
+use serde::Serialize;
+use tauri::AppHandle;
+#[cfg(target_os = "linux")]
+use tauri::Manager;
+
+// ── Launch on login ────────────────────────────────────────────────────────
+// The autostart plugin fixes its arguments at init, so changing whether the
+// entry passes `--minimized` means registering it again ourselves. The entry
+// uses the same name and path as the plugin's, so both manage one entry.
+
+pub const MINIMIZED_ARG: &str = "--minimized";
+
+#[derive(Serialize)]
+pub struct AutostartState {
+    pub enabled: bool,
+    pub start_minimized: bool,
+}
+
+fn build_autolaunch(app: &AppHandle, start_minimized: bool) -> Result<AutoLaunch, String> {
+    let mut builder = AutoLaunchBuilder::new();
+    builder.set_app_name(&app.package_info().name);
+    if start_minimized {
+        builder.set_args(&[MINIMIZED_ARG]);
+    }
+
+    let current_exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    #[cfg(target_os = "linux")]
+    let app_path = app
+        .env()
+        .appimage
+        .and_then(|p| p.to_str().map(|s| s.to_string()))
+        .unwrap_or_else(|| current_exe.display().to_string());
+    #[cfg(target_os = "macos")]
+    let app_path = {
+        builder.set_use_launch_agent(true);
+        current_exe
+            .canonicalize()
+            .map_err(|e| e.to_string())?
+            .display()
+            .to_string()
+    };
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    let app_path = current_exe.display().to_string();
+    builder.set_app_path(&app_path);
+
+    builder.build().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_autostart(app: AppHandle) -> Result<AutostartState, String> {
+    let start_minimized = settings::current().autostart_minimized;
+    let enabled = build_autolaunch(&app, start_minimized)?
+        .is_enabled()
+        .map_err(|e| e.to_string())?;
+    Ok(AutostartState {
+        enabled,
+        start_minimized,
+    })
+}
+
+/// Enables or disables launch on login. `start_minimized` defaults to the
+/// last saved choice.
+#[tauri::command]
+pub fn set_autostart(
+    app: AppHandle,
+    enabled: bool,
+    start_minimized: Option<bool>,
+) -> Result<AutostartState, String> {
+    let start_minimized = start_minimized.unwrap_or(settings::current().autostart_minimized);
+    let launcher = build_autolaunch(&app, start_minimized)?;
+    // Drop the old entry first; its arguments may differ from the new ones.
+    if launcher.is_enabled().map_err(|e| e.to_string())? {
+        launcher.disable().map_err(|e| e.to_string())?;
+    }
+    if enabled {
+        launcher.enable().map_err(|e| e.to_string())?;
+    }
+    settings::update(|s| s.autostart_minimized = start_minimized)?;
+    Ok(AutostartState {
+        enabled,
+        start_minimized,
+    })
+}
@@ -0,0 +1,196 @@
+use crate::data_paths::app_data_root;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+
+// ── Local cover cache ──────────────────────────────────────────────────────
+// Custom covers are stored by the frontend as local file paths (or the asset
+// URLs made from them). `normalize_covers` writes downscaled JPEG copies of
+// them into `<data>/covers` and reports the new paths, leaving the user's
+// original images alone.
+
+const COVER_EXTS: &[&str] = &["jpg", "jpeg", "png", "webp", "gif"];
+
+/// JPEG quality used when re-encoding covers.
+const COVER_JPEG_QUALITY: u8 = 85;
+
+pub fn covers_dir() -> PathBuf {
+    app_data_root().join("covers")
+}
+
+#[derive(Serialize, Clone)]
+struct CoverNormalizeProgress {
+    done: usize,
+    total: usize,
+    current: String,
+}
+
+#[derive(Serialize, Clone)]
+pub struct CoverNormalizeResult {
+    pub processed: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    /// Negative when re-encoding grew the covers.
+    pub bytes_saved: i64,
+    /// Cover as passed in -> path of its normalized copy, for every cover
+    /// that was re-encoded. The frontend swaps these into its stored covers.
+    pub paths: HashMap<String, String>,
+}
+
+fn is_cover_file(path: &Path) -> bool {
+    path.extension()
+        .map(|e| COVER_EXTS.contains(&e.to_string_lossy().to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// The local file behind a cover: a plain path, or an asset URL made by
+/// `convertFileSrc` (`asset://localhost/…` or `http://asset.localhost/…`).
+/// Remote URLs have no local file and give `None`.
+fn cover_file_path(cover: &str) -> Option<PathBuf> {
+    let encoded = [
+        "asset://localhost/",
+        "http://asset.localhost/",
+        "https://asset.localhost/",
+    ]
+    .iter()
+    .find_map(|prefix| cover.strip_prefix(prefix));
+    match encoded {
+        Some(rest) => urlencoding::decode(rest)
+            .ok()
+            .map(|p| PathBuf::from(p.into_owned())),
+        None if cover.contains("://") => None,
+        None => Some(PathBuf::from(cover)),
+    }
+}
+
+/// Where the normalized copy of `path` goes. Covers already in the cache are
+/// rewritten under their own stem; others get a stem derived from their path
+/// so the same source always maps to the same copy.
+fn normalized_path(path: &Path) -> PathBuf {
+    let dir = covers_dir();
+    let stem = match path.parent() {
+        Some(parent) if parent == dir => path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        _ => Sha256::digest(path.to_string_lossy().as_bytes())
+            .iter()
+            .take(8)
+            .map(|b| format!("{b:02x}"))
+            .collect(),
+    };
+    dir.join(format!("{stem}.jpg"))
+}
+
+/// Re-encodes one cover as a JPEG no wider than `max_width` at
+/// `normalized_path`. Returns the new path with the `(old, new)` byte sizes,
+/// or `None` when the file was already compliant.
+fn normalize_cover(path: &Path, max_width: u32) -> Result<Option<(PathBuf, u64, u64)>, String> {
+    let old_size = path.metadata().map_err(|e| e.to_string())?.len();
+    let is_jpeg = path
+        .extension()
+        .map(|e| matches!(e.to_string_lossy().to_lowercase().as_str(), "jpg" | "jpeg"))
+        .unwrap_or(false);
+    let (width, _) = image::image_dimensions(path).map_err(|e| e.to_string())?;
+    if is_jpeg && width <= max_width {
+        return Ok(None);
+    }
+
+    let mut img = image::open(path).map_err(|e| e.to_string())?;
+    if img.width() > max_width {
+        img = img.resize(max_width, u32::MAX, image::imageops::FilterType::Triangle);
+    }
+    let rgb = img.to_rgb8();
+    let out_path = normalized_path(path);
+    std::fs::create_dir_all(covers_dir()).map_err(|e| e.to_string())?;
+    let tmp_path = out_path.with_extension("jpg.tmp");
+    {
+        let file = std::fs::File::create(&tmp_path).map_err(|e| e.to_string())?;
+        let mut writer = std::io::BufWriter::new(file);
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut writer, COVER_JPEG_QUALITY)
+            .encode_image(&rgb)
+            .map_err(|e| e.to_string())?;
+    }
+    std::fs::rename(&tmp_path, &out_path).map_err(|e| e.to_string())?;
+    // A cached PNG/WebP that became a JPEG is replaced; user files are kept.
+    if out_path != path && path.parent() == out_path.parent() {
+        std::fs::remove_file(path).map_err(|e| e.to_string())?;
+    }
+    let new_size = out_path.metadata().map_err(|e| e.to_string())?.len();
+    Ok(Some((out_path, old_size, new_size)))
+}
+
+/// Downscales `covers` (local paths or asset URLs) to `max_width` as JPEG
+/// copies in the cover cache, emitting `cover-normalize-progress` as it goes.
+/// Remote covers and files that are already compliant are skipped. The
+/// returned `paths` map each re-encoded cover to its new file.
+#[tauri::command]
+pub async fn normalize_covers(
+    app: AppHandle,
+    max_width: u32,
+    covers: Vec<String>,
+) -> Result<CoverNormalizeResult, String> {
+    if max_width == 0 {
+        return Err("max_width must be greater than 0".to_string());
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut covers = covers;
+        covers.sort();
+        covers.dedup();
+
+        let total = covers.len();
+        let mut result = CoverNormalizeResult {
+            processed: 0,
+            skipped: 0,
+            failed: 0,
+            bytes_saved: 0,
+            paths: HashMap::new(),
+        };
+        for (i, cover) in covers.iter().enumerate() {
+            let _ = app.emit(
+                "cover-normalize-progress",
+                CoverNormalizeProgress {
+                    done: i,
+                    total,
+                    current: cover.clone(),
+                },
+            );
+            let Some(path) = cover_file_path(cover).filter(|p| p.is_file() && is_cover_file(p))
+            else {
+                result.skipped += 1;
+                continue;
+            };
+            match normalize_cover(&path, max_width) {
+                Ok(Some((new_path, old, new))) => {
+                    result.processed += 1;
+                    result.bytes_saved += old as i64 - new as i64;
+                    result
+                        .paths
+                        .insert(cover.clone(), new_path.to_string_lossy().into_owned());
+                }
+                Ok(None) => result.skipped += 1,
+                Err(e) => {
+                    result.failed += 1;
+                    crate::push_rust_log(
+                        Some(&app),
+                        "warn",
+                        format!("Failed to normalize cover {}: {e}", path.display()),
+                    );
+                }
+            }
+        }
+        let _ = app.emit(
+            "cover-normalize-progress",
+            CoverNormalizeProgress {
+                done: total,
+                total,
+                current: String::new(),
+            },
+        );
+        result
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,58 @@
+use crate::metadata::SITE_SESSIONS;
+use crate::vault::KEYRING_SERVICE;
+use serde::{Deserialize, Serialize};
+
+// ── Saved site credentials ─────────────────────────────────────────────────
+// Opt-in: the frontend only calls `save_credentials` after the user agrees.
+// Credentials go to the OS keyring, never to a file, and let an expired
+// session be signed in again in the background (see `metadata`).
+
+#[derive(Serialize, Deserialize)]
+pub struct SiteCredentials {
+    pub user: String,
+    pub pass: String,
+}
+
+fn entry(site: &str) -> Result<keyring::Entry, String> {
+    if !SITE_SESSIONS.iter().any(|s| s.id == site) {
+        return Err(format!("Unknown site: {site}"));
+    }
+    keyring::Entry::new(KEYRING_SERVICE, &format!("credentials-{site}")).map_err(|e| e.to_string())
+}
+
+/// Saved credentials for `site`, if any.
+pub fn load(site: &str) -> Option<SiteCredentials> {
+    let secret = entry(site).ok()?.get_password().ok()?;
+    serde_json::from_str(&secret).ok()
+}
+
+/// Removes saved credentials; a missing entry is not an error.
+pub fn remove(site: &str) -> Result<(), String> {
+    match entry(site)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[tauri::command]
+pub fn save_credentials(site: String, user: String, pass: String) -> Result<(), String> {
+    crate::applock::ensure_unlocked()?;
+    if user.trim().is_empty() || pass.is_empty() {
+        return Err("Username and password are required".to_string());
+    }
+    let secret =
+        serde_json::to_string(&SiteCredentials { user, pass }).map_err(|e| e.to_string())?;
+    entry(&site)?
+        .set_password(&secret)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn clear_credentials(site: String) -> Result<(), String> {
+    remove(&site)
+}
+
+#[tauri::command]
+pub fn has_credentials(site: String) -> bool {
+    load(&site).is_some()
+}
@@ -1,10 +1,26 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
 
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
 use tauri::{AppHandle, Manager};
 
 const PORTABLE_MARKERS: [&str; 2] = ["portable.mode", ".portable"];
 const PORTABLE_ENV: &str = "LIBMALY_PORTABLE";
 
+/// Library profiles live in `<base>/profiles/<name>`; the default profile is
+/// the base data folder itself, so existing installs keep their data.
+pub const PROFILES_DIR: &str = "profiles";
+/// Name of the active profile, stored in the base folder. Absent = default.
+pub const ACTIVE_PROFILE_FILE: &str = "active_profile";
+
+static ACTIVE_PROFILE: OnceLock<Mutex<Option<String>>> = OnceLock::new();
+
+/// Format version written into every JSON store the backend persists. Bump it
+/// together with a new step in `migrate_store` whenever a store's shape changes.
+pub const STORE_SCHEMA_VERSION: u64 = 1;
+
 fn executable_dir() -> Option<PathBuf> {
     std::env::current_exe()
         .ok()
@@ -29,7 +45,8 @@ pub fn is_portable_mode() -> bool {
     false
 }
 
-pub fn app_data_root() -> PathBuf {
+/// Data folder of the default profile; also holds the other profiles.
+pub fn base_data_root() -> PathBuf {
     if is_portable_mode() {
         executable_dir()
             .unwrap_or_else(|| PathBuf::from("."))
@@ -61,6 +78,63 @@ pub fn app_data_root() -> PathBuf {
     }
 }
 
+pub fn profiles_root() -> PathBuf {
+    base_data_root().join(PROFILES_DIR)
+}
+
+fn active_profile_state() -> &'static Mutex<Option<String>> {
+    ACTIVE_PROFILE.get_or_init(|| {
+        // A profile deleted behind our back falls back to the default.
+        let name = std::fs::read_to_string(base_data_root().join(ACTIVE_PROFILE_FILE))
+            .ok()
+            .map(|n| n.trim().to_string())
+            .filter(|n| is_valid_profile_name(n) && profiles_root().join(n).is_dir());
+        Mutex::new(name)
+    })
+}
+
+/// Profile names double as folder names: letters, digits, spaces, `-` and `_`.
+pub fn is_valid_profile_name(name: &str) -> bool {
+    let name = name.trim();
+    !name.is_empty()
+        && name.len() <= 64
+        && !name.eq_ignore_ascii_case("default")
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == ' ')
+}
+
+/// Active profile, or `None` for the default one.
+pub fn active_profile() -> Option<String> {
+    active_profile_state().lock().unwrap().clone()
+}
+
+/// Persists the active profile. Stores already loaded in memory keep the
+/// old profile's data, so callers restart the app afterwards.
+pub fn set_active_profile(name: Option<String>) -> Result<(), String> {
+    let base = base_data_root();
+    std::fs::create_dir_all(&base).map_err(|e| e.to_string())?;
+    let marker = base.join(ACTIVE_PROFILE_FILE);
+    match &name {
+        Some(n) => std::fs::write(&marker, n).map_err(|e| e.to_string())?,
+        None => {
+            if marker.exists() {
+                std::fs::remove_file(&marker).map_err(|e| e.to_string())?;
+            }
+        }
+    }
+    *active_profile_state().lock().unwrap() = name;
+    Ok(())
+}
+
+/// Data folder of the active profile. Every store resolves its path from here.
+pub fn app_data_root() -> PathBuf {
+    match active_profile() {
+        Some(name) => profiles_root().join(name),
+        None => base_data_root(),
+    }
+}
+
 pub fn crash_report_path(app: &AppHandle, filename: &str) -> PathBuf {
     if is_portable_mode() {
         return app_data_root().join(filename);
@@ -71,3 +145,77 @@ pub fn crash_report_path(app: &AppHandle, filename: &str) -> PathBuf {
         .unwrap_or_else(|_| app_data_root())
         .join(filename)
 }
+
+// ── Versioned JSON stores ──────────────────────────────────────────────────
+// Stores are written as `{ "schema_version": N, "data": ... }`. Files from
+// before versioning hold the bare payload and are read as version 0.
+
+#[derive(Serialize)]
+struct StoreEnvelope<'a, T> {
+    schema_version: u64,
+    data: &'a T,
+}
+
+/// Splits a parsed store into its version and payload.
+fn unwrap_store(value: Value) -> (u64, Value) {
+    match value {
+        Value::Object(mut map) if map.contains_key("data") => {
+            match map.get("schema_version").and_then(Value::as_u64) {
+                Some(version) => (version, map.remove("data").unwrap_or(Value::Null)),
+                None => (0, Value::Object(map)),
+            }
+        }
+        other => (0, other),
+    }
+}
+
+/// Upgrades a store payload from `version` to `STORE_SCHEMA_VERSION`.
+fn migrate_store(mut version: u64, data: Value) -> Value {
+    while version < STORE_SCHEMA_VERSION {
+        // v0 -> v1: the payload is unchanged, it only gains the envelope.
+        version += 1;
+    }
+    data
+}
+
+/// Parses a JSON store, accepting both versioned and legacy unversioned files.
+/// Returns `None` when the file is malformed or does not match `T`.
+pub fn read_store<T: DeserializeOwned>(raw: &str) -> Option<T> {
+    let value: Value = serde_json::from_str(raw).ok()?;
+    let (version, data) = unwrap_store(value);
+    if version > STORE_SCHEMA_VERSION {
+        crate::push_rust_log(
+            None,
+            "warn",
+            format!(
+                "Reading a store written by a newer LIBMALY (schema v{version}, expected v{STORE_SCHEMA_VERSION})"
+            ),
+        );
+    }
+    serde_json::from_value(migrate_store(version, data)).ok()
+}
+
+/// Serializes `data` wrapped in the current versioned envelope.
+pub fn write_store<T: Serialize>(data: &T, pretty: bool) -> Result<String, String> {
+    let envelope = StoreEnvelope {
+        schema_version: STORE_SCHEMA_VERSION,
+        data,
+    };
+    if pretty {
+        serde_json::to_string_pretty(&envelope)
+    } else {
+        serde_json::to_string(&envelope)
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Writes `data` as a versioned store to `path` through a temp file, so a
+/// crash mid-write cannot truncate the existing store.
+pub fn write_store_atomic<T: Serialize>(path: &Path, data: &T) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, write_store(data, false)?).map_err(|e| e.to_string())?;
+    std::fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
@@ -0,0 +1,88 @@
+use crate::data_paths::{app_data_root, read_store, write_store_atomic};
+use crate::{detect_engine, path_dedup_key, Game, KNOWN_ENGINES};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+// ── Engine overrides ───────────────────────────────────────────────────────
+// `detect_engine` guesses from marker files and is sometimes wrong. Users can
+// pin the engine of a game here; scans report the pinned value instead, and
+// engine-specific launch paths (EasyRPG) follow it too.
+
+const ENGINE_OVERRIDES_FILE: &str = "engine_overrides.json";
+
+/// Accepted in addition to `KNOWN_ENGINES` for engines the scanner has no
+/// heuristics for.
+const OTHER_ENGINE: &str = "Other";
+
+/// Game path (as `path_dedup_key`) -> engine.
+static OVERRIDES: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+
+fn overrides_path() -> PathBuf {
+    app_data_root().join(ENGINE_OVERRIDES_FILE)
+}
+
+fn overrides_state() -> &'static Mutex<HashMap<String, String>> {
+    OVERRIDES.get_or_init(|| {
+        let loaded = std::fs::read_to_string(overrides_path())
+            .ok()
+            .and_then(|raw| read_store(&raw))
+            .unwrap_or_default();
+        Mutex::new(loaded)
+    })
+}
+
+/// The pinned engine of the game at `game_path`, if any.
+pub fn get(game_path: &str) -> Option<String> {
+    let map = overrides_state().lock().unwrap();
+    map.get(&path_dedup_key(game_path)).cloned()
+}
+
+/// The override for the game at `game_path`, else what `detect_engine` finds
+/// in its folder.
+pub fn effective_engine(game_path: &str) -> Option<String> {
+    get(game_path).or_else(|| Path::new(game_path).parent().and_then(detect_engine))
+}
+
+/// Replaces the detected engine of every game that has an override.
+pub fn apply(games: &mut [Game]) {
+    let map = overrides_state().lock().unwrap();
+    if map.is_empty() {
+        return;
+    }
+    for game in games {
+        if let Some(engine) = map.get(&path_dedup_key(&game.path)) {
+            game.engine = Some(engine.clone());
+        }
+    }
+}
+
+/// Pins the engine of the game at `path` to one of the engines the scanner
+/// knows, or "Other"; `None` removes the pin. Returns the engine now in
+/// effect, so the caller can update its entry without rescanning.
+#[tauri::command]
+pub fn set_game_engine(path: String, engine: Option<String>) -> Result<Option<String>, String> {
+    let key = path_dedup_key(&path);
+    let mut map = overrides_state().lock().unwrap();
+    match engine.as_deref().map(str::trim) {
+        Some(name) => {
+            let canonical = KNOWN_ENGINES
+                .iter()
+                .chain([&OTHER_ENGINE])
+                .find(|known| known.eq_ignore_ascii_case(name))
+                .ok_or_else(|| {
+                    format!(
+                        "Unknown engine: {name}. Expected one of {}, {OTHER_ENGINE}",
+                        KNOWN_ENGINES.join(", ")
+                    )
+                })?;
+            map.insert(key, canonical.to_string());
+        }
+        None => {
+            map.remove(&key);
+        }
+    }
+    write_store_atomic(&overrides_path(), &*map)?;
+    drop(map);
+    Ok(effective_engine(&path))
+}
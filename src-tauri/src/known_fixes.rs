@@ -0,0 +1,287 @@
+use crate::data_paths::{app_data_root, read_store, write_store_atomic};
+use crate::metadata::{metadata_client, title_similarity, SendRetrying};
+use crate::path_dedup_key;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+// ── Known fixes ────────────────────────────────────────────────────────────
+// A small database of Wine/Proton configurations known to work for specific
+// games, in the spirit of ProtonDB reports. A few entries ship with the app;
+// users can import a community file on top (entries with the same id
+// replace the bundled ones). Applying a fix stores it as the game's launch
+// profile and can install its winetricks verbs into the game's prefix.
+
+const IMPORTED_FIXES_FILE: &str = "known_fixes.json";
+const LAUNCH_PROFILES_FILE: &str = "launch_profiles.json";
+
+/// Lowest `title_similarity` for a fix to be offered for a game.
+const MIN_FIX_SCORE: f32 = 0.6;
+
+const BUNDLED_FIXES: &str = r#"[
+  {
+    "id": "fate-stay-night",
+    "name": "Fate/stay night",
+    "aliases": ["Fate/stay night Realta Nua"],
+    "runner": "wine",
+    "winetricks": ["cjkfonts"],
+    "env": { "LANG": "ja_JP.UTF-8" },
+    "notes": "Needs a Japanese locale for text and file names."
+  },
+  {
+    "id": "saya-no-uta",
+    "name": "Saya no Uta",
+    "aliases": ["The Song of Saya"],
+    "runner": "wine",
+    "winetricks": ["cjkfonts"],
+    "env": { "LANG": "ja_JP.UTF-8" }
+  },
+  {
+    "id": "muv-luv",
+    "name": "Muv-Luv",
+    "aliases": ["Muv-Luv Alternative", "Muv-Luv Extra", "Muv-Luv Unlimited"],
+    "runner": "wine",
+    "winetricks": ["quartz", "lavfilters"],
+    "notes": "Opening movies need DirectShow filters."
+  },
+  {
+    "id": "umineko",
+    "name": "Umineko When They Cry",
+    "aliases": ["Umineko no Naku Koro ni"],
+    "runner": "wine",
+    "winetricks": ["d3dx9"],
+    "dll_overrides": ["d3dx9_43=n,b"]
+  },
+  {
+    "id": "eiyuu-senki",
+    "name": "Eiyuu Senki",
+    "aliases": ["Eiyuu Senki: The World Conquest"],
+    "runner": "proton",
+    "winetricks": ["quartz", "wmp9"],
+    "notes": "Event videos stay black without Windows Media Player."
+  }
+]"#;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct KnownFix {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// "wine" or "proton"; the frontend maps it to an installed runner.
+    #[serde(default)]
+    pub runner: Option<String>,
+    #[serde(default)]
+    pub winetricks: Vec<String>,
+    /// `WINEDLLOVERRIDES` entries such as "d3d9=n,b".
+    #[serde(default)]
+    pub dll_overrides: Vec<String>,
+    #[serde(default)]
+    pub launch_args: Option<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct KnownFixMatch {
+    pub fix: KnownFix,
+    /// `title_similarity` of the best matching name or alias.
+    pub score: f32,
+}
+
+/// Per-game launch settings written by `apply_known_fix`.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct LaunchProfile {
+    #[serde(default)]
+    pub runner: Option<String>,
+    #[serde(default)]
+    pub args: Option<String>,
+    /// Includes `WINEDLLOVERRIDES` when the fix overrides DLLs.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    #[serde(default)]
+    pub fix_id: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct AppliedFix {
+    pub fix_id: String,
+    pub profile: LaunchProfile,
+    /// Verbs installed into the prefix; empty when winetricks was not run.
+    pub winetricks_verbs: Vec<String>,
+    pub winetricks_output: Option<String>,
+}
+
+/// Game path (as `path_dedup_key`) -> launch profile.
+static PROFILES: OnceLock<Mutex<HashMap<String, LaunchProfile>>> = OnceLock::new();
+
+fn imported_fixes_path() -> PathBuf {
+    app_data_root().join(IMPORTED_FIXES_FILE)
+}
+
+fn profiles_path() -> PathBuf {
+    app_data_root().join(LAUNCH_PROFILES_FILE)
+}
+
+fn profiles_state() -> &'static Mutex<HashMap<String, LaunchProfile>> {
+    PROFILES.get_or_init(|| {
+        let loaded = std::fs::read_to_string(profiles_path())
+            .ok()
+            .and_then(|raw| read_store(&raw))
+            .unwrap_or_default();
+        Mutex::new(loaded)
+    })
+}
+
+/// Bundled fixes with the imported database layered on top.
+fn load_fixes() -> Vec<KnownFix> {
+    let mut fixes: Vec<KnownFix> = serde_json::from_str(BUNDLED_FIXES).unwrap_or_default();
+    let imported: Vec<KnownFix> = std::fs::read_to_string(imported_fixes_path())
+        .ok()
+        .and_then(|raw| read_store(&raw))
+        .unwrap_or_default();
+    for fix in imported {
+        match fixes.iter_mut().find(|f| f.id == fix.id) {
+            Some(existing) => *existing = fix,
+            None => fixes.push(fix),
+        }
+    }
+    fixes
+}
+
+fn parse_fixes(raw: &str) -> Result<Vec<KnownFix>, String> {
+    let fixes: Vec<KnownFix> = read_store(raw)
+        .ok_or_else(|| "Not a known-fixes database: expected a JSON list of fixes".to_string())?;
+    if let Some(bad) = fixes
+        .iter()
+        .find(|f| f.id.trim().is_empty() || f.name.trim().is_empty())
+    {
+        return Err(format!(
+            "Every fix needs an id and a name (found id '{}', name '{}')",
+            bad.id, bad.name
+        ));
+    }
+    Ok(fixes)
+}
+
+#[cfg(not(windows))]
+fn install_verbs(prefix: &str, verbs: &[String]) -> Result<String, String> {
+    crate::run_winetricks_for_prefix(prefix, verbs)
+}
+
+#[cfg(windows)]
+fn install_verbs(_prefix: &str, _verbs: &[String]) -> Result<String, String> {
+    Err("Winetricks is not available on Windows".to_string())
+}
+
+/// Fixes whose name or alias matches `game_name_or_id`, best first. An exact
+/// fix id always matches.
+#[tauri::command]
+pub fn get_known_fixes(game_name_or_id: String) -> Vec<KnownFixMatch> {
+    let query = game_name_or_id.trim();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let mut out: Vec<KnownFixMatch> = load_fixes()
+        .into_iter()
+        .filter_map(|fix| {
+            let score = if fix.id.eq_ignore_ascii_case(query) {
+                1.0
+            } else {
+                std::iter::once(&fix.name)
+                    .chain(&fix.aliases)
+                    .map(|name| title_similarity(query, name))
+                    .fold(0.0, f32::max)
+            };
+            (score >= MIN_FIX_SCORE).then_some(KnownFixMatch { fix, score })
+        })
+        .collect();
+    out.sort_by(|a, b| b.score.total_cmp(&a.score));
+    out
+}
+
+/// Replaces the imported fixes database with the one at `source`, a local
+/// file or an http(s) URL. Returns how many fixes it holds.
+#[tauri::command]
+pub async fn import_known_fixes(source: String) -> Result<usize, String> {
+    let source = source.trim();
+    let raw = if source.starts_with("http://") || source.starts_with("https://") {
+        let resp = metadata_client()
+            .get(source)
+            .send_retrying()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !resp.status().is_success() {
+            return Err(format!("Download failed: HTTP {}", resp.status()));
+        }
+        resp.text().await.map_err(|e| e.to_string())?
+    } else {
+        std::fs::read_to_string(source).map_err(|e| e.to_string())?
+    };
+    let fixes = parse_fixes(&raw)?;
+    write_store_atomic(&imported_fixes_path(), &fixes)?;
+    Ok(fixes.len())
+}
+
+/// The launch profile stored for the game at `game_path`, if any.
+#[tauri::command]
+pub fn get_launch_profile(game_path: String) -> Option<LaunchProfile> {
+    let map = profiles_state().lock().unwrap();
+    map.get(&path_dedup_key(&game_path)).cloned()
+}
+
+/// Stores fix `fix_id` as the launch profile of the game at `game_path`.
+/// With `run_winetricks` set, its verbs are also installed into `prefix`.
+#[tauri::command]
+pub fn apply_known_fix(
+    game_path: String,
+    fix_id: String,
+    prefix: Option<String>,
+    run_winetricks: Option<bool>,
+) -> Result<AppliedFix, String> {
+    let fix = load_fixes()
+        .into_iter()
+        .find(|f| f.id == fix_id)
+        .ok_or_else(|| format!("Unknown fix: {fix_id}"))?;
+
+    let mut env = fix.env.clone();
+    if !fix.dll_overrides.is_empty() {
+        env.insert("WINEDLLOVERRIDES".to_string(), fix.dll_overrides.join(";"));
+    }
+    let profile = LaunchProfile {
+        runner: fix.runner.clone(),
+        args: fix.launch_args.clone(),
+        env,
+        fix_id: Some(fix.id.clone()),
+    };
+
+    let (winetricks_verbs, winetricks_output) =
+        if run_winetricks.unwrap_or(false) && !fix.winetricks.is_empty() {
+            let prefix = prefix
+                .filter(|p| !p.trim().is_empty())
+                .ok_or_else(|| "A Wine prefix is needed to run winetricks".to_string())?;
+            let output = install_verbs(&prefix, &fix.winetricks)?;
+            (fix.winetricks.clone(), Some(output))
+        } else {
+            (Vec::new(), None)
+        };
+
+    let mut map = profiles_state().lock().unwrap();
+    map.insert(path_dedup_key(&game_path), profile.clone());
+    write_store_atomic(&profiles_path(), &*map)?;
+    drop(map);
+    crate::push_rust_log(
+        None,
+        "info",
+        format!("Applied known fix '{}' to {}", fix.id, game_path),
+    );
+    Ok(AppliedFix {
+        fix_id: fix.id,
+        profile,
+        winetricks_verbs,
+        winetricks_output,
+    })
+}
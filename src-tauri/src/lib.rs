@@ -1,45 +1,120 @@
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::{Mutex, OnceLock};
 use std::thread;
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
-use tauri::menu::{MenuBuilder, MenuItemBuilder, PredefinedMenuItem};
+use tauri::menu::{MenuBuilder, MenuItemBuilder, PredefinedMenuItem, SubmenuBuilder};
 use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
 use tauri::AppHandle;
 use tauri::Emitter;
 use tauri::Manager;
 use walkdir::WalkDir;
-#[cfg(windows)]
 use rusqlite::Connection;
-#[cfg(windows)]
 use rusqlite::types::ValueRef;
 
 mod metadata;
 use metadata::{
-    dlsite_is_logged_in, dlsite_login, dlsite_logout, f95_is_logged_in, f95_login, f95_logout,
-    fetch_dlsite_metadata, fetch_f95_metadata, fetch_fakku_metadata, fetch_johren_metadata,
-    fetch_mangagamer_metadata, fetch_vndb_metadata, fakku_is_logged_in, fakku_login,
-    fakku_logout, search_suggest_links,
+    clear_metadata_cache, dlsite_is_logged_in, dlsite_login, dlsite_logout, f95_is_logged_in,
+    f95_login, f95_logout, fakku_is_logged_in, fakku_login, fakku_logout, fetch_dlsite_metadata,
+    fetch_f95_changelog, fetch_f95_metadata, fetch_fakku_metadata, fetch_itch_metadata,
+    fetch_johren_metadata, fetch_mangagamer_metadata, fetch_protondb_rating, fetch_steam_metadata,
+    fetch_vndb_metadata, get_all_login_status, get_session_expiry, import_dlsite_purchases,
+    import_f95_watched, logout_all_sites, prefetch_metadata, refresh_sessions,
+    search_suggest_links, test_scrapers,
 };
 
 mod updater;
-use updater::{preview_update, update_game};
+use updater::{preview_update, rollback_update, update_game};
 
 mod screenshot;
 use screenshot::{
-    delete_screenshot_file, export_screenshots_zip, get_screenshots, open_screenshots_folder,
-    overwrite_screenshot_png, save_screenshot_tags, take_screenshot_manual,
-    get_screenshot_data_url,
+    delete_screenshot_file, export_screenshots_zip, get_screenshot_data_url,
+    get_screenshot_thumbnail, get_screenshots, open_screenshots_folder, overwrite_screenshot_png,
+    save_screenshot_tags, take_screenshot_manual,
 };
 mod data_paths;
-use data_paths::{app_data_root, crash_report_path, is_portable_mode};
+use data_paths::{app_data_root, crash_report_path, is_portable_mode, read_store, write_store};
+mod settings;
+use settings::{get_backend_settings, set_backend_settings};
+mod covers;
+use covers::normalize_covers;
+mod snapshots;
+use snapshots::{restore_data_snapshot, snapshot_data};
+mod autostart;
+use autostart::{get_autostart, set_autostart};
+mod playtime;
+use playtime::{get_playtime, reset_playtime};
+mod profiles;
+use profiles::{create_profile, delete_profile, list_profiles, switch_profile};
+mod applock;
+mod vault;
+use applock::{clear_app_lock, get_app_lock_status, set_app_lock, unlock_session, verify_app_lock};
+mod credentials;
+use credentials::{clear_credentials, has_credentials, save_credentials};
+mod archives;
+use archives::{extract_game, scan_archives, verify_file_hash};
+mod rpgmaker;
+use rpgmaker::check_rpgmaker_rtp;
+mod engine_overrides;
+use engine_overrides::set_game_engine;
+mod known_fixes;
+use known_fixes::{apply_known_fix, get_known_fixes, get_launch_profile, import_known_fixes};
+mod name_issues;
+use name_issues::detect_name_issues;
+mod sorting;
+use sorting::sort_games;
+mod library_export;
+use library_export::export_library_html;
+mod library_cache;
+use library_cache::{get_cached_library, rescan, save_library_snapshot};
+mod log_file;
+use log_file::{export_logs_zip, open_logs_folder};
 
 #[derive(Serialize, Deserialize, Clone)]
 struct Game {
     name: String,
     path: String,
+    /// Set by the scanner when `ScanOptions::detect_demos` is on.
+    #[serde(default)]
+    is_demo: bool,
+    /// Engine guessed from marker files, e.g. "Ren'Py" or "RPG Maker".
+    #[serde(default)]
+    engine: Option<String>,
+    /// Size of the launcher file (the `.lnk` target for shortcuts).
+    #[serde(default)]
+    size_bytes: u64,
+    /// Last-modified time of the launcher file, unix seconds.
+    #[serde(default)]
+    modified: u64,
+    /// Folder the scanner found the game in, when that is not the folder of
+    /// `path` (a `.lnk` shortcut pointing elsewhere).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source_dir: Option<String>,
+}
+
+/// Optional scanner behaviour; every flag defaults to off.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+struct ScanOptions {
+    /// Flag demo/trial builds by folder/exe name or a `demo`/`trial` marker file.
+    detect_demos: bool,
+    /// Exe stems kept even if the built-in block list would drop them
+    /// (e.g. a game whose launcher really is `config.exe`).
+    extra_allow: Vec<String>,
+    /// Exe stems to drop in addition to the built-in block list. Wins over
+    /// `extra_allow` when a stem is in both.
+    extra_block: Vec<String>,
+}
+
+impl ScanOptions {
+    /// Both lists match the whole exe stem, case-insensitively.
+    fn stem_in(list: &[String], stem: &str) -> bool {
+        list.iter().any(|s| s.trim().to_lowercase() == stem)
+    }
 }
 
 /// A recently-launched game entry (stored for tray quick-launch).
@@ -51,6 +126,15 @@ struct RecentGame {
 
 struct RecentGamesState(std::sync::Mutex<Vec<RecentGame>>);
 
+/// A library collection shown as a tray submenu.
+#[derive(Serialize, Deserialize, Clone)]
+struct TrayCollection {
+    name: String,
+    games: Vec<RecentGame>,
+}
+
+struct TrayCollectionsState(std::sync::Mutex<Vec<TrayCollection>>);
+
 #[derive(Serialize, Deserialize, Clone)]
 struct RustLogEntry {
     ts: u64,
@@ -72,6 +156,8 @@ struct SaveBackupResult {
     zip_path: String,
     files: usize,
     directories: Vec<String>,
+    /// Older backups deleted to honour `max_backups`.
+    pruned: Vec<String>,
 }
 
 static RUST_LOG_BUFFER: OnceLock<Mutex<Vec<RustLogEntry>>> = OnceLock::new();
@@ -155,7 +241,9 @@ fn dir_has_files(dir: &Path) -> bool {
         .any(|e| e.file_type().is_file())
 }
 
-fn detect_save_dirs(game_path: &str) -> Vec<PathBuf> {
+/// Detected save dirs for a game, plus any user-supplied `extra_dirs`. Every
+/// entry must exist and contain files.
+fn detect_save_dirs(game_path: &str, extra_dirs: &[String]) -> Vec<PathBuf> {
     let game = PathBuf::from(game_path);
     let variants = name_variants_from_game_path(&game);
 
@@ -256,29 +344,99 @@ fn detect_save_dirs(game_path: &str) -> Vec<PathBuf> {
         }
     }
 
+    for extra in extra_dirs {
+        let extra = extra.trim();
+        if !extra.is_empty() {
+            push_dir_if_exists_unique(&mut candidates, PathBuf::from(extra));
+        }
+    }
+
     candidates.into_iter().filter(|d| dir_has_files(d)).collect()
 }
 
+/// Save dirs found automatically, so the UI can show them next to the
+/// user's own additions.
+#[tauri::command]
+fn detect_save_dirs_preview(game_path: String) -> Vec<String> {
+    detect_save_dirs(&game_path, &[])
+        .iter()
+        .map(|d| d.to_string_lossy().to_string())
+        .collect()
+}
+
+/// Top-level folder name for the `idx`-th save dir inside a backup zip.
+fn save_dir_label(idx: usize, dir: &Path) -> String {
+    format!(
+        "{:02}_{}",
+        idx + 1,
+        sanitize_name_for_filename(
+            &dir.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "save".to_string())
+        )
+    )
+}
+
+fn save_backups_dir() -> PathBuf {
+    app_data_root().join("save-backups")
+}
+
+/// File-name prefix shared by every backup of one game.
+fn save_backup_label(game_path: &str) -> String {
+    Path::new(game_path)
+        .file_stem()
+        .map(|n| sanitize_name_for_filename(&n.to_string_lossy()))
+        .unwrap_or_else(|| "game".to_string())
+}
+
+/// Deletes all but the newest `keep` `<label>-<digits>.zip` backups. Other
+/// files in the folder, including other games' backups, are never touched.
+fn prune_save_backups(label: &str, keep: usize) -> Vec<String> {
+    let prefix = format!("{}-", label);
+    let mut backups: Vec<(u64, PathBuf)> = match std::fs::read_dir(save_backups_dir()) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let name = e.file_name().to_string_lossy().to_string();
+                let stamp = name.strip_prefix(&prefix)?.strip_suffix(".zip")?;
+                if stamp.is_empty() || !stamp.bytes().all(|b| b.is_ascii_digit()) {
+                    return None;
+                }
+                Some((stamp.parse().ok()?, e.path()))
+            })
+            .collect(),
+        Err(_) => return Vec::new(),
+    };
+    if backups.len() <= keep {
+        return Vec::new();
+    }
+    backups.sort_by_key(|(stamp, _)| std::cmp::Reverse(*stamp));
+    backups
+        .split_off(keep)
+        .into_iter()
+        .filter(|(_, path)| std::fs::remove_file(path).is_ok())
+        .map(|(_, path)| path.to_string_lossy().to_string())
+        .collect()
+}
+
 #[tauri::command]
 fn backup_save_files(
     game_path: String,
     output_path: Option<String>,
+    extra_dirs: Option<Vec<String>>,
+    max_backups: Option<usize>,
 ) -> Result<SaveBackupResult, String> {
-    let game = PathBuf::from(&game_path);
-    let dirs = detect_save_dirs(&game_path);
+    let dirs = detect_save_dirs(&game_path, &extra_dirs.unwrap_or_default());
     if dirs.is_empty() {
         return Err("No common save directories were detected for this game.".to_string());
     }
 
+    let label = save_backup_label(&game_path);
     let zip_path = if let Some(out) = output_path {
         PathBuf::from(out)
     } else {
-        let base = app_data_root().join("save-backups");
+        let base = save_backups_dir();
         std::fs::create_dir_all(&base).map_err(|e| e.to_string())?;
-        let label = game
-            .file_stem()
-            .map(|n| sanitize_name_for_filename(&n.to_string_lossy()))
-            .unwrap_or_else(|| "game".to_string());
         base.join(format!("{}-{}.zip", label, now_ms()))
     };
 
@@ -293,15 +451,7 @@ fn backup_save_files(
 
     let mut files_added = 0usize;
     for (idx, dir) in dirs.iter().enumerate() {
-        let root_label = format!(
-            "{:02}_{}",
-            idx + 1,
-            sanitize_name_for_filename(
-                &dir.file_name()
-                    .map(|n| n.to_string_lossy().to_string())
-                    .unwrap_or_else(|| "save".to_string())
-            )
-        );
+        let root_label = save_dir_label(idx, dir);
         for entry in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
             if !entry.file_type().is_file() {
                 continue;
@@ -327,6 +477,10 @@ fn backup_save_files(
     }
 
     zip.finish().map_err(|e| e.to_string())?;
+    let pruned = match max_backups {
+        Some(keep) => prune_save_backups(&label, keep.max(1)),
+        None => Vec::new(),
+    };
     Ok(SaveBackupResult {
         zip_path: zip_path.to_string_lossy().to_string(),
         files: files_added,
@@ -334,6 +488,114 @@ fn backup_save_files(
             .iter()
             .map(|d| d.to_string_lossy().to_string())
             .collect(),
+        pruned,
+    })
+}
+
+#[derive(Serialize)]
+struct RestoreResult {
+    files: usize,
+    directories: Vec<String>,
+    /// Backup of the saves as they were before the restore.
+    safety_backup: String,
+}
+
+/// Restores a zip made by `backup_save_files`. Each `NN_<dirname>` folder in
+/// the zip is copied back into the matching detected save directory; pass the
+/// same `extra_dirs` the backup used.
+#[tauri::command]
+fn restore_save_files(
+    game_path: String,
+    zip_path: String,
+    extra_dirs: Option<Vec<String>>,
+) -> Result<RestoreResult, String> {
+    let extra_dirs = extra_dirs.unwrap_or_default();
+    let dirs = detect_save_dirs(&game_path, &extra_dirs);
+    if dirs.is_empty() {
+        return Err("No common save directories were detected for this game.".to_string());
+    }
+
+    let file = std::fs::File::open(&zip_path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+
+    let labels: HashSet<String> = archive
+        .file_names()
+        .filter_map(|n| n.split('/').next())
+        .filter(|n| !n.is_empty())
+        .map(|n| n.to_string())
+        .collect();
+    if labels.len() != dirs.len() {
+        return Err(format!(
+            "Backup holds {} save folder(s) but {} were detected for this game; refusing to restore.",
+            labels.len(),
+            dirs.len()
+        ));
+    }
+    let targets: HashMap<String, &PathBuf> = dirs
+        .iter()
+        .enumerate()
+        .map(|(idx, dir)| (save_dir_label(idx, dir), dir))
+        .collect();
+    if let Some(unknown) = labels.iter().find(|l| !targets.contains_key(*l)) {
+        return Err(format!(
+            "Backup folder '{}' does not match any detected save directory; refusing to restore.",
+            unknown
+        ));
+    }
+
+    // Snapshot the current saves first so a bad restore can be undone with
+    // this same command.
+    let safety_path = save_backups_dir().join(format!(
+        "{}-{}.libmaly_restore_backup.zip",
+        save_backup_label(&game_path),
+        now_ms()
+    ));
+    let safety = backup_save_files(
+        game_path.clone(),
+        Some(safety_path.to_string_lossy().into_owned()),
+        Some(extra_dirs),
+        None,
+    )?;
+
+    let mut files = 0usize;
+    let mut touched: Vec<String> = Vec::new();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        if entry.is_dir() {
+            continue;
+        }
+        // Reject absolute paths and `..` so a crafted zip cannot escape the save dir.
+        let name = match entry.enclosed_name() {
+            Some(n) => n,
+            None => continue,
+        };
+        let mut parts = name.components();
+        let label = match parts.next() {
+            Some(c) => c.as_os_str().to_string_lossy().into_owned(),
+            None => continue,
+        };
+        let rel = parts.as_path();
+        let target_dir = match targets.get(&label) {
+            Some(d) if !rel.as_os_str().is_empty() => *d,
+            _ => continue,
+        };
+        let out_path = target_dir.join(rel);
+        if let Some(parent) = out_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let mut out = std::fs::File::create(&out_path).map_err(|e| e.to_string())?;
+        std::io::copy(&mut entry, &mut out).map_err(|e| e.to_string())?;
+        files += 1;
+        let dir_str = target_dir.to_string_lossy().to_string();
+        if !touched.contains(&dir_str) {
+            touched.push(dir_str);
+        }
+    }
+
+    Ok(RestoreResult {
+        files,
+        directories: touched,
+        safety_backup: safety.zip_path,
     })
 }
 
@@ -351,6 +613,7 @@ fn push_rust_log(app: Option<&AppHandle>, level: &str, message: impl Into<String
             logs.drain(0..overflow);
         }
     }
+    log_file::append(&entry);
     if let Some(app_handle) = app {
         let _ = app_handle.emit("rust-log", &entry);
     }
@@ -386,8 +649,14 @@ struct DirMtime {
     mtime: u64,
 }
 
-fn is_blocked(name: &str, path_str: &str) -> bool {
+fn is_blocked(name: &str, path_str: &str, options: &ScanOptions) -> bool {
     let n = name.to_lowercase();
+    if ScanOptions::stem_in(&options.extra_block, &n) {
+        return true;
+    }
+    if ScanOptions::stem_in(&options.extra_allow, &n) {
+        return false;
+    }
     if n.contains("crashhandler")
         || n.contains("uninstall")
         || n.starts_with("unins")
@@ -449,37 +718,254 @@ fn is_generic_name(name: &str) -> bool {
     )
 }
 
+/// True when a whole word of `name` marks a demo ("Demon" does not count).
+fn name_marks_demo(name: &str) -> bool {
+    if name.contains("体験版") {
+        return true;
+    }
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|w| w == "demo" || w == "trial")
+}
+
+fn dir_is_demo(dir: &std::path::Path) -> bool {
+    let dir_name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    name_marks_demo(&dir_name) || dir.join("demo").is_file() || dir.join("trial").is_file()
+}
+
+/// Engines `detect_engine` can report.
+const KNOWN_ENGINES: &[&str] = &["Ren'Py", "RPG Maker", "Unity", "KiriKiri", "NW.js"];
+
+/// Guesses the game engine from marker files in a game folder.
+fn detect_engine(dir: &std::path::Path) -> Option<String> {
+    let mut has_rpa = false;
+    let mut has_rgss = false;
+    let mut has_rpg_rt = false;
+    let mut has_unity_data = false;
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            if is_dir {
+                has_unity_data |= name.ends_with("_data");
+            } else {
+                has_rpa |= name.ends_with(".rpa");
+                has_rgss |= name.starts_with("game.rgss");
+                has_rpg_rt |= name == "rpg_rt.exe" || name == "rpg_rt.ldb";
+            }
+        }
+    }
+    let engine = if dir.join("renpy").is_dir() || has_rpa || dir_has_rpa(&dir.join("game")) {
+        "Ren'Py"
+    } else if has_rgss
+        || has_rpg_rt
+        || dir.join("www").join("js").join("rpg_core.js").is_file()
+        || dir.join("js").join("rmmz_core.js").is_file()
+    {
+        "RPG Maker"
+    } else if has_unity_data && dir.join("UnityPlayer.dll").is_file() {
+        "Unity"
+    } else if dir.join("data.xp3").is_file() {
+        "KiriKiri"
+    } else if dir.join("nw.dll").is_file() {
+        "NW.js"
+    } else {
+        return None;
+    };
+    Some(engine.to_string())
+}
+
+fn dir_has_rpa(dir: &std::path::Path) -> bool {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries.filter_map(|e| e.ok()).any(|e| {
+                e.file_name()
+                    .to_string_lossy()
+                    .to_lowercase()
+                    .ends_with(".rpa")
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// File types `scan_dir_shallow` treats as launchers.
+#[cfg(windows)]
+const SCAN_LAUNCHER_EXTS: &[&str] = &["exe", "bat", "cmd", "lnk"];
+#[cfg(not(windows))]
+const SCAN_LAUNCHER_EXTS: &[&str] = &["exe"];
+
+/// Target of a Windows shortcut, read straight from the Shell Link binary
+/// format (MS-SHLLINK) so scanning needs no COM. Tries the LinkInfo local
+/// path first, then the relative path. Returns `None` unless the target is
+/// an existing file.
+#[cfg(windows)]
+fn resolve_lnk_target(lnk: &std::path::Path) -> Option<PathBuf> {
+    const HAS_ID_LIST: u32 = 0x1;
+    const HAS_LINK_INFO: u32 = 0x2;
+    const HAS_NAME: u32 = 0x4;
+    const HAS_RELATIVE_PATH: u32 = 0x8;
+    const IS_UNICODE: u32 = 0x80;
+
+    let data = std::fs::read(lnk).ok()?;
+    let u16_at = |off: usize| -> Option<u16> {
+        Some(u16::from_le_bytes(data.get(off..off + 2)?.try_into().ok()?))
+    };
+    let u32_at = |off: usize| -> Option<u32> {
+        Some(u32::from_le_bytes(data.get(off..off + 4)?.try_into().ok()?))
+    };
+    let cstr_at = |off: usize| -> Option<String> {
+        let bytes = data.get(off..)?;
+        let end = bytes.iter().position(|&b| b == 0)?;
+        Some(String::from_utf8_lossy(&bytes[..end]).into_owned())
+    };
+    let wstr_at = |off: usize| -> Option<String> {
+        let mut units = Vec::new();
+        let mut i = off;
+        loop {
+            let u = u16_at(i)?;
+            if u == 0 {
+                break;
+            }
+            units.push(u);
+            i += 2;
+        }
+        Some(String::from_utf16_lossy(&units))
+    };
+
+    if u32_at(0)? != 0x4C {
+        return None;
+    }
+    let flags = u32_at(0x14)?;
+    let mut pos = 0x4C;
+    if flags & HAS_ID_LIST != 0 {
+        pos += 2 + u16_at(pos)? as usize;
+    }
+
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    if flags & HAS_LINK_INFO != 0 {
+        let info = pos;
+        let info_size = u32_at(info)? as usize;
+        let header_size = u32_at(info + 4)? as usize;
+        let info_flags = u32_at(info + 8)?;
+        if info_flags & 0x1 != 0 {
+            // Unicode offsets exist only in the extended header.
+            let base = if header_size >= 0x24 {
+                wstr_at(info + u32_at(info + 0x1C)? as usize)
+            } else {
+                cstr_at(info + u32_at(info + 0x10)? as usize)
+            };
+            let suffix = if header_size >= 0x24 {
+                wstr_at(info + u32_at(info + 0x20)? as usize)
+            } else {
+                cstr_at(info + u32_at(info + 0x18)? as usize)
+            };
+            if let Some(base) = base.filter(|b| !b.is_empty()) {
+                candidates.push(PathBuf::from(base + &suffix.unwrap_or_default()));
+            }
+        }
+        pos = info + info_size;
+    }
+
+    // StringData: counted strings in a fixed order (name, relative path, …).
+    let unicode = flags & IS_UNICODE != 0;
+    let read_string_data = |pos: &mut usize| -> Option<String> {
+        let count = u16_at(*pos)? as usize;
+        let start = *pos + 2;
+        let value = if unicode {
+            let units: Vec<u16> = (0..count)
+                .map(|i| u16_at(start + i * 2))
+                .collect::<Option<_>>()?;
+            *pos = start + count * 2;
+            String::from_utf16_lossy(&units)
+        } else {
+            *pos = start + count;
+            String::from_utf8_lossy(data.get(start..start + count)?).into_owned()
+        };
+        Some(value)
+    };
+    if flags & HAS_NAME != 0 {
+        read_string_data(&mut pos)?;
+    }
+    if flags & HAS_RELATIVE_PATH != 0 {
+        if let (Some(rel), Some(dir)) = (read_string_data(&mut pos), lnk.parent()) {
+            candidates.push(dir.join(rel));
+        }
+    }
+
+    candidates.into_iter().find(|c| c.is_file())
+}
+
+#[cfg(not(windows))]
+fn resolve_lnk_target(_lnk: &std::path::Path) -> Option<PathBuf> {
+    None
+}
+
 /// Collect every exe inside `dir` (non-recursive, single directory).
-fn scan_dir_shallow(dir: &std::path::Path) -> Vec<Game> {
+fn scan_dir_shallow(dir: &std::path::Path, options: &ScanOptions) -> Vec<Game> {
     let mut out = Vec::new();
     let entries = match std::fs::read_dir(dir) {
         Ok(e) => e,
         Err(_) => return out,
     };
+    // Checked at most once per directory, and only when an exe is found.
+    let mut dir_demo: Option<bool> = None;
+    let mut dir_engine: Option<Option<String>> = None;
     for entry in entries.filter_map(|e| e.ok()) {
         let p = entry.path();
         if !p.is_file() {
             continue;
         }
-        if p.extension().map(|e| e.to_string_lossy().to_lowercase()) != Some("exe".into()) {
+        let ext = p
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if !SCAN_LAUNCHER_EXTS.contains(&ext.as_str()) {
             continue;
         }
         let name_raw = match p.file_stem() {
             Some(n) => n.to_string_lossy().into_owned(),
             None => continue,
         };
-        let path_str = p.to_string_lossy().into_owned();
-        if is_blocked(&name_raw, &path_str) {
+        // Shortcuts are stored as the exe they point at.
+        let target = if ext == "lnk" {
+            match resolve_lnk_target(&p) {
+                Some(t) => t,
+                None => continue,
+            }
+        } else {
+            p.clone()
+        };
+        let path_str = target.to_string_lossy().into_owned();
+        let target_name = target
+            .file_stem()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| name_raw.clone());
+        if is_blocked(&name_raw, &path_str, options) || is_blocked(&target_name, &path_str, options)
+        {
             continue;
         }
-        if let Ok(meta) = p.metadata() {
-            if meta.len() < 100 * 1024 {
-                continue;
+        let meta = target.metadata().ok();
+        // Scripts are tiny by nature; the size floor only weeds out stub exes.
+        if ext == "exe" {
+            if let Some(meta) = &meta {
+                if meta.len() < 100 * 1024 {
+                    continue;
+                }
             }
         }
+        let size_bytes = meta.as_ref().map(|m| m.len()).unwrap_or(0);
+        let modified = meta
+            .and_then(|m| m.modified().ok())
+            .map(|t| t.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs())
+            .unwrap_or(0);
         // If the exe stem is a generic engine/launcher name (e.g. "Game", "nw",
         // "renpy"), prefer the parent folder name for a more descriptive title.
         // Example: D:\Games\072 project_Sonia\Game.exe  →  "072 project_Sonia"
+        let is_demo = options.detect_demos
+            && (name_marks_demo(&name_raw) || *dir_demo.get_or_insert_with(|| dir_is_demo(dir)));
         let name = if is_generic_name(&name_raw) {
             dir.file_name()
                 .map(|n| n.to_string_lossy().into_owned())
@@ -487,35 +973,133 @@ fn scan_dir_shallow(dir: &std::path::Path) -> Vec<Game> {
         } else {
             name_raw
         };
+        // Shortcuts may point into another folder; that folder has the markers.
+        let engine = match target.parent() {
+            Some(target_dir) if target_dir != dir => detect_engine(target_dir),
+            _ => dir_engine.get_or_insert_with(|| detect_engine(dir)).clone(),
+        };
+        let source_dir = (target.parent() != Some(dir)).then(|| dir.to_string_lossy().into_owned());
         out.push(Game {
             name,
             path: path_str,
+            is_demo,
+            engine,
+            size_bytes,
+            modified,
+            source_dir,
         });
     }
     out
 }
 
+/// The folder whose scan produced `game`: where its shortcut sits for `.lnk`
+/// games, otherwise the folder of its exe.
+fn game_scan_dir(game: &Game) -> String {
+    match &game.source_dir {
+        Some(dir) => dir.clone(),
+        None => Path::new(&game.path)
+            .parent()
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+    }
+}
+
+/// Comparison key for game paths. Windows paths are case-insensitive and
+/// `.lnk` targets may use either separator.
+fn path_dedup_key(path: &str) -> String {
+    if cfg!(windows) {
+        path.replace('/', "\\").to_lowercase()
+    } else {
+        path.to_string()
+    }
+}
+
 /// Full scan – walks the entire tree, returns games + directory mtime snapshot.
 #[tauri::command]
-fn scan_games(path: String) -> Result<(Vec<Game>, Vec<DirMtime>), String> {
+fn scan_games(
+    path: String,
+    options: Option<ScanOptions>,
+) -> Result<(Vec<Game>, Vec<DirMtime>), String> {
     let root = std::path::Path::new(&path);
-    let mut dir_mtimes: Vec<DirMtime> = Vec::new();
-    let mut games: Vec<Game> = Vec::new();
+    let options = options.unwrap_or_default();
 
-    for entry in WalkDir::new(root).into_iter().filter_map(|e| e.ok()) {
-        if entry.file_type().is_dir() {
-            dir_mtimes.push(DirMtime {
-                path: entry.path().to_string_lossy().into_owned(),
-                mtime: dir_mtime(entry.path()),
-            });
-            let shallow = scan_dir_shallow(entry.path());
-            games.extend(shallow);
+    // Walk once to list directories, then scan them in parallel: each shallow
+    // scan is independent and mostly waits on disk I/O.
+    let dirs: Vec<PathBuf> = WalkDir::new(root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_dir())
+        .map(|e| e.into_path())
+        .collect();
+
+    // Every directory gets an mtime entry, even when it holds no game, so
+    // incremental scans can tell it apart from a new one.
+    let (dir_mtimes, per_dir_games): (Vec<DirMtime>, Vec<Vec<Game>>) = dirs
+        .par_iter()
+        .map(|dir| {
+            let mtime = DirMtime {
+                path: dir.to_string_lossy().into_owned(),
+                mtime: dir_mtime(dir),
+            };
+            (mtime, scan_dir_shallow(dir, &options))
+        })
+        .unzip();
+    let mut games: Vec<Game> = per_dir_games.into_iter().flatten().collect();
+
+    // Deduplicate by path (a shortcut and its target collapse into one entry)
+    games.sort_by_cached_key(|g| path_dedup_key(&g.path));
+    games.dedup_by(|a, b| path_dedup_key(&a.path) == path_dedup_key(&b.path));
+    engine_overrides::apply(&mut games);
+
+    Ok((games, dir_mtimes))
+}
+
+/// Drops roots equal to or nested inside another root; scanning the outer one
+/// already covers them.
+fn outermost_roots(paths: Vec<String>) -> Vec<String> {
+    let mut roots: Vec<(PathBuf, String)> = paths
+        .into_iter()
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| (PathBuf::from(path_dedup_key(p.trim())), p))
+        .collect();
+    roots.sort_by_key(|(key, _)| key.components().count());
+    let mut kept: Vec<(PathBuf, String)> = Vec::new();
+    for (key, path) in roots {
+        if !kept.iter().any(|(outer, _)| key.starts_with(outer)) {
+            kept.push((key, path));
+        }
+    }
+    kept.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Full scan of several roots (drives, mount points) in one call. Overlapping
+/// roots are scanned once. The mtime snapshot holds absolute paths, so any
+/// single root can later go through `scan_games_incremental` with it.
+#[tauri::command]
+fn scan_multiple(
+    paths: Vec<String>,
+    options: Option<ScanOptions>,
+) -> Result<(Vec<Game>, Vec<DirMtime>), String> {
+    let mut games: Vec<Game> = Vec::new();
+    let mut dir_mtimes: Vec<DirMtime> = Vec::new();
+    for root in outermost_roots(paths) {
+        if !Path::new(&root).is_dir() {
+            push_rust_log(
+                None,
+                "warn",
+                format!("Skipping scan root that is not a folder: {root}"),
+            );
+            continue;
         }
+        let (root_games, root_mtimes) = scan_games(root, options.clone())?;
+        games.extend(root_games);
+        dir_mtimes.extend(root_mtimes);
     }
 
-    // Deduplicate by path
-    games.sort_by(|a, b| a.path.cmp(&b.path));
-    games.dedup_by(|a, b| a.path == b.path);
+    games.sort_by_cached_key(|g| path_dedup_key(&g.path));
+    games.dedup_by(|a, b| path_dedup_key(&a.path) == path_dedup_key(&b.path));
+    dir_mtimes.sort_by_cached_key(|d| path_dedup_key(&d.path));
+    dir_mtimes.dedup_by(|a, b| path_dedup_key(&a.path) == path_dedup_key(&b.path));
 
     Ok((games, dir_mtimes))
 }
@@ -527,8 +1111,10 @@ fn scan_games_incremental(
     path: String,
     cached_games: Vec<Game>,
     cached_mtimes: Vec<DirMtime>,
+    options: Option<ScanOptions>,
 ) -> Result<(Vec<Game>, Vec<DirMtime>), String> {
     let root = std::path::Path::new(&path);
+    let options = options.unwrap_or_default();
 
     // Build lookup: dir_path -> last known mtime
     let mtime_map: HashMap<String, u64> = cached_mtimes
@@ -539,11 +1125,7 @@ fn scan_games_incremental(
     // Build lookup: dir_path -> games that live in it (to evict stale ones)
     let mut cached_by_dir: HashMap<String, Vec<Game>> = HashMap::new();
     for g in cached_games {
-        let dir = std::path::Path::new(&g.path)
-            .parent()
-            .map(|p| p.to_string_lossy().into_owned())
-            .unwrap_or_default();
-        cached_by_dir.entry(dir).or_default().push(g);
+        cached_by_dir.entry(game_scan_dir(&g)).or_default().push(g);
     }
 
     let mut new_mtimes: Vec<DirMtime> = Vec::new();
@@ -570,16 +1152,80 @@ fn scan_games_incremental(
             }
         } else {
             // Directory is new or modified – re-scan it
-            merged_games.extend(scan_dir_shallow(dir_path));
+            merged_games.extend(scan_dir_shallow(dir_path, &options));
         }
     }
 
-    merged_games.sort_by(|a, b| a.path.cmp(&b.path));
-    merged_games.dedup_by(|a, b| a.path == b.path);
+    merged_games.sort_by_cached_key(|g| path_dedup_key(&g.path));
+    merged_games.dedup_by(|a, b| path_dedup_key(&a.path) == path_dedup_key(&b.path));
+    engine_overrides::apply(&mut merged_games);
 
     Ok((merged_games, new_mtimes))
 }
 
+/// Incremental scan of several roots. Cached games and mtimes are handed to
+/// the root that contains them; those under no listed root are dropped, so a
+/// removed root's games disappear without touching the other roots.
+#[tauri::command]
+fn scan_multiple_incremental(
+    paths: Vec<String>,
+    cached_games: Vec<Game>,
+    cached_mtimes: Vec<DirMtime>,
+    options: Option<ScanOptions>,
+) -> Result<(Vec<Game>, Vec<DirMtime>), String> {
+    let roots = outermost_roots(paths);
+    let keys: Vec<PathBuf> = roots
+        .iter()
+        .map(|r| PathBuf::from(path_dedup_key(r.trim())))
+        .collect();
+    let root_of = |path: &str| {
+        let key = PathBuf::from(path_dedup_key(path));
+        keys.iter().position(|root| key.starts_with(root))
+    };
+
+    let mut games_by_root: Vec<Vec<Game>> = vec![Vec::new(); roots.len()];
+    for g in cached_games {
+        if let Some(i) = root_of(&game_scan_dir(&g)) {
+            games_by_root[i].push(g);
+        }
+    }
+    let mut mtimes_by_root: Vec<Vec<DirMtime>> = vec![Vec::new(); roots.len()];
+    for d in cached_mtimes {
+        if let Some(i) = root_of(&d.path) {
+            mtimes_by_root[i].push(d);
+        }
+    }
+
+    let mut games: Vec<Game> = Vec::new();
+    let mut dir_mtimes: Vec<DirMtime> = Vec::new();
+    for ((root, root_games), root_mtimes) in
+        roots.into_iter().zip(games_by_root).zip(mtimes_by_root)
+    {
+        // An unplugged drive keeps its cached games until it is removed from the list.
+        if !Path::new(&root).is_dir() {
+            push_rust_log(
+                None,
+                "warn",
+                format!("Scan root is unavailable, keeping its cached games: {root}"),
+            );
+            games.extend(root_games);
+            dir_mtimes.extend(root_mtimes);
+            continue;
+        }
+        let (fresh_games, fresh_mtimes) =
+            scan_games_incremental(root, root_games, root_mtimes, options.clone())?;
+        games.extend(fresh_games);
+        dir_mtimes.extend(fresh_mtimes);
+    }
+
+    games.sort_by_cached_key(|g| path_dedup_key(&g.path));
+    games.dedup_by(|a, b| path_dedup_key(&a.path) == path_dedup_key(&b.path));
+    dir_mtimes.sort_by_cached_key(|d| path_dedup_key(&d.path));
+    dir_mtimes.dedup_by(|a, b| path_dedup_key(&a.path) == path_dedup_key(&b.path));
+
+    Ok((games, dir_mtimes))
+}
+
 #[derive(Serialize, Clone)]
 struct GameEndedPayload {
     path: String,
@@ -611,7 +1257,27 @@ struct WineRunner {
     name: String,
     path: String,
     kind: String, // "wine" | "proton"
-    flavor: Option<String>, // "official" | "ge"
+    flavor: Option<String>, // "official" | "ge" | "bottles"
+}
+
+/// Folders whose subfolders are user-managed Wine prefixes.
+#[cfg(not(windows))]
+fn wine_prefix_roots(home: &str) -> [PathBuf; 3] {
+    [
+        PathBuf::from(format!("{home}/.local/share/wineprefixes")),
+        PathBuf::from(format!("{home}/.wineprefixes")),
+        PathBuf::from(format!("{home}/Games")),
+    ]
+}
+
+/// Data folders of Bottles: the native install and the Flatpak.
+#[cfg(not(windows))]
+fn bottles_data_dirs(home: &str) -> [PathBuf; 2] {
+    let home = Path::new(home);
+    [
+        home.join(".local/share/bottles"),
+        home.join(".var/app/com.usebottles.bottles/data/bottles"),
+    ]
 }
 
 #[tauri::command]
@@ -734,6 +1400,36 @@ fn detect_wine_runners() -> Vec<WineRunner> {
                 }
             }
         }
+
+        // ── Bottles runners (Soda, Caffe, Wine-GE, …) ─────────────────────
+        for data_dir in bottles_data_dirs(&home) {
+            let Ok(entries) = std::fs::read_dir(data_dir.join("runners")) else {
+                continue;
+            };
+            let mut runner_dirs: Vec<_> = entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_dir())
+                .collect();
+            runner_dirs.sort_by_key(|e| e.file_name());
+            for entry in runner_dirs {
+                let bin = entry.path().join("bin");
+                let Some(wine_bin) = ["wine", "wine64"]
+                    .iter()
+                    .map(|b| bin.join(b))
+                    .find(|b| b.is_file())
+                else {
+                    continue;
+                };
+                let name = entry.file_name().to_string_lossy().to_string();
+                let is_ge = name.to_lowercase().contains("ge-");
+                push_runner!(
+                    format!("bottles/{name}"),
+                    wine_bin.to_string_lossy().to_string(),
+                    "wine",
+                    Some(if is_ge { "ge" } else { "bottles" }),
+                );
+            }
+        }
     }
     runners
 }
@@ -764,7 +1460,9 @@ struct InteropGameEntry {
     game_id: String,
     exe: String,
     args: Option<String>,
-    source: String, // "playnite" | "gog-galaxy"
+    source: String, // "playnite" | "gog-galaxy" | "heroic" | "itch" | "epic"
+    install_path: Option<String>,
+    prefix: Option<String>,
 }
 
 #[cfg(windows)]
@@ -778,7 +1476,6 @@ fn path_exists_file(path: &str) -> bool {
     p.is_file()
 }
 
-#[cfg(windows)]
 fn looks_executable(path: &std::path::Path) -> bool {
     path.extension()
         .map(|e| {
@@ -790,7 +1487,6 @@ fn looks_executable(path: &std::path::Path) -> bool {
         .unwrap_or(false)
 }
 
-#[cfg(windows)]
 fn score_exe_candidate(path: &std::path::Path) -> i64 {
     let stem = path
         .file_stem()
@@ -810,7 +1506,6 @@ fn score_exe_candidate(path: &std::path::Path) -> i64 {
     score
 }
 
-#[cfg(windows)]
 fn find_best_exe_in_install_dir(install_dir: &str) -> Option<String> {
     let root = std::path::Path::new(install_dir);
     if !root.is_dir() {
@@ -873,12 +1568,96 @@ fn detect_prefix_graphics(prefix: &std::path::Path) -> (bool, bool) {
     (has_dxvk, has_vkd3d)
 }
 
+/// DXVK/VKD3D status per prefix path, keyed by the prefix's change stamp so
+/// `list_wine_prefixes` only stats DLLs again for prefixes that changed.
+#[cfg(not(windows))]
+type PrefixGraphicsCache = HashMap<String, (u64, bool, bool)>;
+#[cfg(not(windows))]
+static PREFIX_GRAPHICS_CACHE: Mutex<Option<PrefixGraphicsCache>> = Mutex::new(None);
+
+/// Newest mtime of the prefix dir and its system DLL dirs. Installing or
+/// removing DXVK touches system32/syswow64 rather than the prefix root.
+#[cfg(not(windows))]
+fn prefix_change_stamp(prefix: &std::path::Path) -> u64 {
+    let win = prefix.join("drive_c").join("windows");
+    dir_mtime(prefix)
+        .max(dir_mtime(&win.join("system32")))
+        .max(dir_mtime(&win.join("syswow64")))
+}
+
+#[cfg(not(windows))]
+fn prefix_graphics_cached(prefix: &std::path::Path, force: bool) -> (bool, bool) {
+    let key = prefix.to_string_lossy().to_string();
+    let stamp = prefix_change_stamp(prefix);
+    if !force {
+        let cache = PREFIX_GRAPHICS_CACHE.lock().unwrap();
+        if let Some((cached_stamp, dxvk, vkd3d)) = cache.as_ref().and_then(|m| m.get(&key)) {
+            if *cached_stamp == stamp {
+                return (*dxvk, *vkd3d);
+            }
+        }
+    }
+    let (has_dxvk, has_vkd3d) = detect_prefix_graphics(prefix);
+    PREFIX_GRAPHICS_CACHE
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(key, (stamp, has_dxvk, has_vkd3d));
+    (has_dxvk, has_vkd3d)
+}
+
+/// Re-detects one prefix, bypassing the graphics cache.
 #[tauri::command]
-fn list_wine_prefixes() -> Vec<PrefixInfo> {
+fn refresh_prefix(path: String) -> Result<PrefixInfo, String> {
     #[cfg(windows)]
     {
-        Vec::new()
-    }
+        let _ = path;
+        Err("Wine prefixes are not supported on Windows".to_string())
+    }
+    #[cfg(not(windows))]
+    {
+        let prefix = std::path::Path::new(&path);
+        if !is_wine_prefix_dir(prefix) {
+            return Err(format!("Not a Wine prefix: {path}"));
+        }
+        let (has_dxvk, has_vkd3d) = prefix_graphics_cached(prefix, true);
+        // Mirror the naming used by list_wine_prefixes.
+        let file_name = |p: &std::path::Path| {
+            p.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default()
+        };
+        let parent = prefix.parent();
+        let in_compatdata = parent
+            .and_then(|p| p.parent())
+            .map(|p| file_name(p) == "compatdata")
+            .unwrap_or(false);
+        let (name, kind) = if file_name(prefix) == "pfx" && in_compatdata {
+            (
+                format!("compatdata/{}", parent.map(file_name).unwrap_or_default()),
+                "proton",
+            )
+        } else if file_name(prefix) == "prefix" {
+            (parent.map(file_name).unwrap_or_default(), "wine")
+        } else {
+            (file_name(prefix), "wine")
+        };
+        Ok(PrefixInfo {
+            name,
+            path,
+            kind: kind.to_string(),
+            has_dxvk,
+            has_vkd3d,
+        })
+    }
+}
+
+#[tauri::command]
+fn list_wine_prefixes() -> Vec<PrefixInfo> {
+    #[cfg(windows)]
+    {
+        Vec::new()
+    }
     #[cfg(not(windows))]
     {
         let home = std::env::var("HOME").unwrap_or_default();
@@ -909,13 +1688,7 @@ fn list_wine_prefixes() -> Vec<PrefixInfo> {
         }
 
         // User-managed Wine prefixes.
-        let wine_prefix_roots = [
-            format!("{home}/.local/share/wineprefixes"),
-            format!("{home}/.wineprefixes"),
-            format!("{home}/Games"),
-        ];
-        for root in &wine_prefix_roots {
-            let root_path = std::path::Path::new(root);
+        for root_path in &wine_prefix_roots(&home) {
             if !root_path.exists() {
                 continue;
             }
@@ -938,6 +1711,20 @@ fn list_wine_prefixes() -> Vec<PrefixInfo> {
             }
         }
 
+        // Bottles: every bottle is a prefix.
+        for data_dir in bottles_data_dirs(&home) {
+            let Ok(entries) = std::fs::read_dir(data_dir.join("bottles")) else {
+                continue;
+            };
+            for entry in entries.filter_map(|e| e.ok()) {
+                let p = entry.path();
+                if is_wine_prefix_dir(&p) {
+                    let name = format!("bottles/{}", entry.file_name().to_string_lossy());
+                    push_candidate(&mut candidates, &mut seen_paths, name, p, "wine");
+                }
+            }
+        }
+
         // Steam compatdata prefixes (Proton).
         let compat_roots = [
             format!("{home}/.steam/steam/steamapps/compatdata"),
@@ -968,7 +1755,7 @@ fn list_wine_prefixes() -> Vec<PrefixInfo> {
                 if !is_wine_prefix_dir(&path) {
                     return None;
                 }
-                let (has_dxvk, has_vkd3d) = detect_prefix_graphics(&path);
+                let (has_dxvk, has_vkd3d) = prefix_graphics_cached(&path, false);
                 Some(PrefixInfo {
                     name,
                     path: path.to_string_lossy().to_string(),
@@ -979,7 +1766,7 @@ fn list_wine_prefixes() -> Vec<PrefixInfo> {
             })
             .collect();
 
-        out.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        out.sort_by_key(|a| a.name.to_lowercase());
         out
     }
 }
@@ -1033,6 +1820,127 @@ fn create_wine_prefix(path: String, runner: Option<String>) -> Result<(), String
     }
 }
 
+/// Windows versions `configure_wine_prefix` accepts, as winecfg names them.
+#[cfg(not(windows))]
+const WINE_WINDOWS_VERSIONS: &[&str] = &["winxp", "win7", "win10", "win11"];
+
+/// Runs `wine <args>` in `prefix`, or `proton run <args>` when `runner` is
+/// Proton (`prefix` may then be the compatdata folder or its `pfx`).
+#[cfg(not(windows))]
+fn run_in_prefix(runner: Option<&str>, prefix: &Path, args: &[&str]) -> Result<(), String> {
+    let runner_cmd = runner.unwrap_or("wine");
+    let is_proton = Path::new(runner_cmd)
+        .file_name()
+        .map(|n| n.to_string_lossy().eq_ignore_ascii_case("proton"))
+        .unwrap_or(false);
+    let mut cmd = Command::new(runner_cmd);
+    if is_proton {
+        let compat_data = if prefix.ends_with("pfx") {
+            prefix.parent().unwrap_or(prefix)
+        } else {
+            prefix
+        };
+        cmd.arg("run").env("STEAM_COMPAT_DATA_PATH", compat_data);
+    } else {
+        cmd.env("WINEPREFIX", prefix);
+    }
+    let out = cmd
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run {runner_cmd}: {e}"))?;
+    if !out.status.success() {
+        let err = String::from_utf8_lossy(&out.stderr).trim().to_string();
+        return Err(if err.is_empty() {
+            format!("{} failed: {}", args.join(" "), out.status)
+        } else {
+            err
+        });
+    }
+    Ok(())
+}
+
+/// Sets the Windows version the prefix reports (`HKCU\Software\Wine`
+/// `Version`, as winecfg does) and/or its DPI (`HKCU\Control Panel\Desktop`
+/// `LogPixels`, 96 = 100%) through `reg add` in the prefix.
+#[tauri::command]
+fn configure_wine_prefix(
+    prefix: String,
+    windows_version: Option<String>,
+    dpi: Option<u32>,
+    runner: Option<String>,
+) -> Result<(), String> {
+    #[cfg(windows)]
+    {
+        let _ = (prefix, windows_version, dpi, runner);
+        Err("Wine prefixes are not supported on Windows".to_string())
+    }
+    #[cfg(not(windows))]
+    {
+        let pfx = resolve_wine_prefix(&prefix)?;
+        if windows_version.is_none() && dpi.is_none() {
+            return Err("Nothing to configure: pass a Windows version or a DPI".to_string());
+        }
+        let version = windows_version
+            .map(|v| v.trim().to_lowercase())
+            .map(|v| {
+                if WINE_WINDOWS_VERSIONS.contains(&v.as_str()) {
+                    Ok(v)
+                } else {
+                    Err(format!(
+                        "Unsupported Windows version: {v}. Expected one of {}",
+                        WINE_WINDOWS_VERSIONS.join(", ")
+                    ))
+                }
+            })
+            .transpose()?;
+        if let Some(dpi) = dpi {
+            if !(96..=480).contains(&dpi) {
+                return Err(format!("DPI must be between 96 and 480, got {dpi}"));
+            }
+        }
+
+        let runner = runner.as_deref();
+        if let Some(version) = version {
+            run_in_prefix(
+                runner,
+                &pfx,
+                &[
+                    "reg",
+                    "add",
+                    "HKEY_CURRENT_USER\\Software\\Wine",
+                    "/v",
+                    "Version",
+                    "/t",
+                    "REG_SZ",
+                    "/d",
+                    &version,
+                    "/f",
+                ],
+            )?;
+        }
+        if let Some(dpi) = dpi {
+            run_in_prefix(
+                runner,
+                &pfx,
+                &[
+                    "reg",
+                    "add",
+                    "HKEY_CURRENT_USER\\Control Panel\\Desktop",
+                    "/v",
+                    "LogPixels",
+                    "/t",
+                    "REG_DWORD",
+                    "/d",
+                    &dpi.to_string(),
+                    "/f",
+                ],
+            )?;
+        }
+        push_rust_log(None, "info", format!("Configured Wine prefix {prefix}"));
+        Ok(())
+    }
+}
+
 #[tauri::command]
 fn delete_wine_prefix(path: String) -> Result<(), String> {
     #[cfg(windows)]
@@ -1060,6 +1968,366 @@ fn delete_wine_prefix(path: String) -> Result<(), String> {
     }
 }
 
+/// Renames a user-managed prefix in place and returns its new path. Launch
+/// configs are stored by the frontend, which rewrites references to the old
+/// path using the returned value.
+#[tauri::command]
+fn rename_wine_prefix(old_path: String, new_name: String) -> Result<String, String> {
+    #[cfg(windows)]
+    {
+        let _ = (old_path, new_name);
+        Err("Wine prefixes are not supported on Windows".to_string())
+    }
+    #[cfg(not(windows))]
+    {
+        let new_name = new_name.trim();
+        if new_name.is_empty() || new_name == "." || new_name == ".." {
+            return Err("New prefix name is empty".to_string());
+        }
+        if new_name.contains('/') || new_name.contains('\\') {
+            return Err("Prefix name must not contain path separators".to_string());
+        }
+        let prefix = std::path::Path::new(&old_path);
+        if !is_wine_prefix_dir(prefix) {
+            return Err("The selected path does not look like a Wine prefix".to_string());
+        }
+        // `<name>/prefix` layouts are listed under the outer folder's name, so
+        // that folder is the one to rename.
+        let nested = prefix.file_name().map(|n| n == "prefix").unwrap_or(false);
+        let dir = if nested {
+            prefix.parent().ok_or("Invalid prefix path")?
+        } else {
+            prefix
+        };
+        let parent = dir.parent().ok_or("Refusing to rename root directory")?;
+        // `~/.wine`, Bottles bottles and Steam compatdata are named by their
+        // owners; only folders in the user-managed roots are ours to rename.
+        let home = std::env::var("HOME").unwrap_or_default();
+        let canonical = |p: &Path| std::fs::canonicalize(p).unwrap_or_else(|_| p.to_path_buf());
+        let parent_canonical = canonical(parent);
+        if !wine_prefix_roots(&home)
+            .iter()
+            .any(|root| canonical(root) == parent_canonical)
+        {
+            return Err(
+                "Only prefixes in ~/.local/share/wineprefixes, ~/.wineprefixes or ~/Games can be renamed"
+                    .to_string(),
+            );
+        }
+        let target_dir = parent.join(new_name);
+        if target_dir.exists() {
+            return Err(format!("{} already exists", target_dir.display()));
+        }
+        std::fs::rename(dir, &target_dir).map_err(|e| e.to_string())?;
+        let new_prefix = if nested {
+            target_dir.join("prefix")
+        } else {
+            target_dir
+        };
+        Ok(new_prefix.to_string_lossy().to_string())
+    }
+}
+
+#[cfg(not(windows))]
+#[derive(Serialize, Clone)]
+struct PrefixCloneProgress {
+    files_copied: u64,
+    bytes_copied: u64,
+    current: String,
+}
+
+#[derive(Serialize, Clone)]
+struct PrefixCloneResult {
+    dest_path: String,
+    files_copied: u64,
+    bytes_copied: u64,
+    /// References to the source prefix rewritten in the registry hives.
+    reg_paths_rewritten: u64,
+}
+
+/// Temp folders a prefix accumulates that are not worth duplicating.
+#[cfg(not(windows))]
+fn is_prefix_cache_dir(rel: &std::path::Path) -> bool {
+    let parts: Vec<String> = rel
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_lowercase())
+        .collect();
+    match parts.as_slice() {
+        [a, b, c] if a == "drive_c" && b == "windows" && c == "temp" => true,
+        [a, b, _, rest @ ..] if a == "drive_c" && b == "users" => {
+            matches!(rest, [t] if t == "temp")
+                || matches!(rest, [x, y, t] if x == "appdata" && y == "local" && t == "temp")
+        }
+        _ => false,
+    }
+}
+
+#[cfg(not(windows))]
+fn copy_prefix_tree(
+    app: &AppHandle,
+    src: &std::path::Path,
+    dest: &std::path::Path,
+) -> Result<PrefixCloneResult, String> {
+    let mut files_copied = 0u64;
+    let mut bytes_copied = 0u64;
+    // Symlinks are recreated as-is: dosdevices and the user shell folders
+    // point outside the prefix and must not be followed.
+    let walker = WalkDir::new(src)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| {
+            e.path()
+                .strip_prefix(src)
+                .map(|rel| !(e.file_type().is_dir() && is_prefix_cache_dir(rel)))
+                .unwrap_or(true)
+        });
+    for entry in walker {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let rel = entry.path().strip_prefix(src).map_err(|e| e.to_string())?;
+        let target = dest.join(rel);
+        let ft = entry.file_type();
+        if ft.is_dir() {
+            std::fs::create_dir_all(&target).map_err(|e| e.to_string())?;
+        } else if ft.is_symlink() {
+            let mut link = std::fs::read_link(entry.path()).map_err(|e| e.to_string())?;
+            // Absolute links into the prefix itself follow it to the clone.
+            if let Ok(inner) = link.strip_prefix(src) {
+                link = dest.join(inner);
+            }
+            std::os::unix::fs::symlink(&link, &target).map_err(|e| e.to_string())?;
+        } else {
+            bytes_copied += std::fs::copy(entry.path(), &target)
+                .map_err(|e| format!("Failed to copy {}: {e}", entry.path().display()))?;
+            files_copied += 1;
+            if files_copied.is_multiple_of(200) {
+                let _ = app.emit(
+                    "prefix-clone-progress",
+                    PrefixCloneProgress {
+                        files_copied,
+                        bytes_copied,
+                        current: rel.to_string_lossy().to_string(),
+                    },
+                );
+            }
+        }
+    }
+    let _ = app.emit(
+        "prefix-clone-progress",
+        PrefixCloneProgress {
+            files_copied,
+            bytes_copied,
+            current: String::new(),
+        },
+    );
+    let reg_paths_rewritten = rewrite_prefix_reg_paths(src, dest)?;
+    Ok(PrefixCloneResult {
+        dest_path: dest.to_string_lossy().to_string(),
+        files_copied,
+        bytes_copied,
+        reg_paths_rewritten,
+    })
+}
+
+/// Replaces whole-path occurrences of `from` in `data`: a match must not be
+/// followed by a character that would continue a file name, so `/a/pfx` does
+/// not touch `/a/pfx-old`. Returns the number of replacements.
+#[cfg(not(windows))]
+fn replace_path_bytes(data: &mut Vec<u8>, from: &[u8], to: &[u8]) -> u64 {
+    if from.is_empty() {
+        return 0;
+    }
+    let mut out = Vec::with_capacity(data.len());
+    let mut count = 0u64;
+    let mut i = 0;
+    while i < data.len() {
+        if data[i..].starts_with(from) {
+            let next = data.get(i + from.len()).copied();
+            let continues_name =
+                matches!(next, Some(c) if c.is_ascii_alphanumeric() || b"-_. ".contains(&c));
+            if !continues_name {
+                out.extend_from_slice(to);
+                i += from.len();
+                count += 1;
+                continue;
+            }
+        }
+        out.push(data[i]);
+        i += 1;
+    }
+    *data = out;
+    count
+}
+
+/// Points the registry hives of a cloned prefix at the clone: Wine stores
+/// absolute paths both as unix paths and as `Z:` drive paths with escaped
+/// backslashes.
+#[cfg(not(windows))]
+fn rewrite_prefix_reg_paths(src: &std::path::Path, dest: &std::path::Path) -> Result<u64, String> {
+    let unix = |p: &std::path::Path| p.to_string_lossy().trim_end_matches('/').to_string();
+    let reg_escaped = |p: &std::path::Path| unix(p).replace('/', "\\\\");
+    let pairs = [
+        (unix(src), unix(dest)),
+        (
+            format!("Z:{}", reg_escaped(src)),
+            format!("Z:{}", reg_escaped(dest)),
+        ),
+        (
+            format!("z:{}", reg_escaped(src)),
+            format!("z:{}", reg_escaped(dest)),
+        ),
+    ];
+    let mut total = 0u64;
+    for hive in ["system.reg", "user.reg", "userdef.reg"] {
+        let path = dest.join(hive);
+        let Ok(mut data) = std::fs::read(&path) else {
+            continue;
+        };
+        let count: u64 = pairs
+            .iter()
+            .map(|(from, to)| replace_path_bytes(&mut data, from.as_bytes(), to.as_bytes()))
+            .sum();
+        if count > 0 {
+            std::fs::write(&path, &data)
+                .map_err(|e| format!("Failed to update {}: {e}", path.display()))?;
+            total += count;
+        }
+    }
+    Ok(total)
+}
+
+/// Copies a whole prefix to `dest_path`, emitting `prefix-clone-progress`
+/// while it runs, and rewrites paths to the source in the clone's registry
+/// so it does not keep using the original. A failed copy removes the partial
+/// destination.
+#[tauri::command]
+async fn clone_wine_prefix(
+    app: AppHandle,
+    src_path: String,
+    dest_path: String,
+) -> Result<PrefixCloneResult, String> {
+    #[cfg(windows)]
+    {
+        let _ = (app, src_path, dest_path);
+        Err("Wine prefixes are not supported on Windows".to_string())
+    }
+    #[cfg(not(windows))]
+    {
+        let src = std::path::PathBuf::from(&src_path);
+        let dest = std::path::PathBuf::from(&dest_path);
+        if dest_path.trim().is_empty() {
+            return Err("Destination path is empty".to_string());
+        }
+        if !is_wine_prefix_dir(&src) {
+            return Err("The selected path does not look like a Wine prefix".to_string());
+        }
+        if dest.exists() {
+            return Err(format!("{} already exists", dest.display()));
+        }
+        if dest.starts_with(&src) {
+            return Err("Destination cannot be inside the source prefix".to_string());
+        }
+        tauri::async_runtime::spawn_blocking(move || {
+            let result = copy_prefix_tree(&app, &src, &dest);
+            if result.is_err() {
+                let _ = std::fs::remove_dir_all(&dest);
+            }
+            result
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+}
+
+/// Decodes the escaping Wine uses for key paths and string values in
+/// `*.reg` files (`\\` and `\"`).
+#[cfg(not(windows))]
+fn unescape_reg_string(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// String values of one key in a Wine registry hive file such as
+/// `user.reg`. `key` uses single backslashes, e.g. `Software\Wine\DllOverrides`.
+/// Non-string values (dword, hex) are skipped.
+#[cfg(not(windows))]
+fn read_reg_key_strings(
+    hive: &std::path::Path,
+    key: &str,
+) -> Result<Vec<(String, String)>, String> {
+    let raw = std::fs::read(hive).map_err(|e| format!("Failed to read {}: {e}", hive.display()))?;
+    let text = String::from_utf8_lossy(&raw);
+    let mut values = Vec::new();
+    let mut in_key = false;
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix('[') {
+            let name = rest.split(']').next().unwrap_or("");
+            in_key = unescape_reg_string(name).eq_ignore_ascii_case(key);
+            continue;
+        }
+        if !in_key || !line.starts_with('"') {
+            continue;
+        }
+        // "name"="value"
+        let Some(sep) = line.find("\"=\"") else {
+            continue;
+        };
+        let name = &line[1..sep];
+        let value = &line[sep + 3..];
+        let value = value.strip_suffix('"').unwrap_or(value);
+        values.push((unescape_reg_string(name), unescape_reg_string(value)));
+    }
+    Ok(values)
+}
+
+#[derive(Serialize, Clone)]
+struct DllOverride {
+    dll: String,
+    /// Load order as Wine stores it, e.g. "native,builtin" or "" (disabled).
+    mode: String,
+}
+
+/// Per-prefix DLL overrides from `HKCU\Software\Wine\DllOverrides`, read
+/// straight from `user.reg` so Wine does not have to be started.
+#[tauri::command]
+fn get_prefix_dll_overrides(prefix: String) -> Result<Vec<DllOverride>, String> {
+    #[cfg(windows)]
+    {
+        let _ = prefix;
+        Err("Wine prefixes are not supported on Windows".to_string())
+    }
+    #[cfg(not(windows))]
+    {
+        let root = std::path::Path::new(&prefix);
+        if !is_wine_prefix_dir(root) {
+            return Err("The selected path does not look like a Wine prefix".to_string());
+        }
+        let user_reg = root.join("user.reg");
+        if !user_reg.is_file() {
+            return Ok(Vec::new());
+        }
+        let mut out: Vec<DllOverride> =
+            read_reg_key_strings(&user_reg, "Software\\Wine\\DllOverrides")?
+                .into_iter()
+                .map(|(dll, mode)| DllOverride { dll, mode })
+                .collect();
+        out.sort_by_key(|o| o.dll.to_lowercase());
+        Ok(out)
+    }
+}
+
 #[cfg(not(windows))]
 fn run_winetricks_for_prefix(prefix: &str, verbs: &[String]) -> Result<String, String> {
     if verbs.is_empty() {
@@ -1099,6 +2367,171 @@ fn run_winetricks(prefix: String, verbs: Vec<String>) -> Result<String, String>
     }
 }
 
+/// Resolves `prefix` to the Wine prefix itself, accepting a Proton
+/// compatdata folder in place of its `pfx`.
+#[cfg(not(windows))]
+fn resolve_wine_prefix(prefix: &str) -> Result<PathBuf, String> {
+    let pfx = Path::new(prefix);
+    let wine_dir = if is_wine_prefix_dir(pfx) {
+        pfx.to_path_buf()
+    } else {
+        pfx.join("pfx")
+    };
+    if prefix.trim().is_empty() || !is_wine_prefix_dir(&wine_dir) {
+        return Err("The selected path does not look like a Wine prefix".to_string());
+    }
+    Ok(wine_dir)
+}
+
+/// Spawns an interactive tool without waiting for it; a thread reaps the
+/// child once the user closes it.
+#[cfg(not(windows))]
+fn spawn_detached(mut cmd: Command, name: &str) -> Result<(), String> {
+    let mut child = cmd
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to start {name}: {e}"))?;
+    thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+/// Opens winecfg for `prefix`. With a `runner` it goes through that Wine
+/// build (or `proton run`), otherwise `winecfg` must be on PATH.
+#[tauri::command]
+fn open_winecfg(prefix: String, runner: Option<String>) -> Result<(), String> {
+    #[cfg(windows)]
+    {
+        let _ = (prefix, runner);
+        Err("winecfg is not available on Windows".to_string())
+    }
+    #[cfg(not(windows))]
+    {
+        let wine_dir = resolve_wine_prefix(&prefix)?;
+        let cmd = match runner.as_deref().map(str::trim).filter(|r| !r.is_empty()) {
+            Some(runner_cmd) => {
+                let is_proton = Path::new(runner_cmd)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().eq_ignore_ascii_case("proton"))
+                    .unwrap_or(false);
+                let mut cmd = Command::new(runner_cmd);
+                if is_proton {
+                    let compat_data = wine_dir.parent().unwrap_or(&wine_dir);
+                    cmd.arg("run").env("STEAM_COMPAT_DATA_PATH", compat_data);
+                } else {
+                    cmd.env("WINEPREFIX", &wine_dir);
+                }
+                cmd.arg("winecfg");
+                cmd
+            }
+            None => {
+                if !command_in_path("winecfg") {
+                    return Err(
+                        "winecfg was not found on PATH. Install Wine or pick a runner".to_string(),
+                    );
+                }
+                let mut cmd = Command::new("winecfg");
+                cmd.env("WINEPREFIX", &wine_dir);
+                cmd
+            }
+        };
+        spawn_detached(cmd, "winecfg")
+    }
+}
+
+/// Opens the interactive winetricks menu for `prefix`.
+#[tauri::command]
+fn open_winetricks_gui(prefix: String) -> Result<(), String> {
+    #[cfg(windows)]
+    {
+        let _ = prefix;
+        Err("Winetricks is not available on Windows".to_string())
+    }
+    #[cfg(not(windows))]
+    {
+        let wine_dir = resolve_wine_prefix(&prefix)?;
+        if !command_in_path("winetricks") {
+            return Err("winetricks was not found on PATH. Install it to use this".to_string());
+        }
+        let mut cmd = Command::new("winetricks");
+        cmd.arg("--gui").env("WINEPREFIX", &wine_dir);
+        spawn_detached(cmd, "winetricks")
+    }
+}
+
+/// True when `path` is a real DLL rather than one of the placeholder files
+/// Wine puts in system32 for its builtins.
+#[cfg(not(windows))]
+fn is_native_dll(path: &Path) -> bool {
+    let Ok(bytes) = std::fs::read(path) else {
+        return false;
+    };
+    let placeholder = |marker: &[u8]| bytes.windows(marker.len()).any(|w| w == marker);
+    !placeholder(b"Wine builtin DLL") && !placeholder(b"Wine placeholder DLL")
+}
+
+/// Guesses the common verbs from the files and registry keys they leave
+/// behind, for prefixes set up without winetricks.log.
+#[cfg(not(windows))]
+fn probe_installed_verbs(prefix: &Path) -> Vec<String> {
+    let win = prefix.join("drive_c").join("windows");
+    let native = |dll: &str| {
+        is_native_dll(&win.join("system32").join(dll))
+            || is_native_dll(&win.join("syswow64").join(dll))
+    };
+    let system_reg = std::fs::read_to_string(prefix.join("system.reg")).unwrap_or_default();
+    let mut verbs = Vec::new();
+    if native("dxgi.dll") && (native("d3d11.dll") || native("d3d9.dll")) {
+        verbs.push("dxvk");
+    }
+    if native("d3d12.dll") {
+        verbs.push("vkd3d");
+    }
+    let fonts = win.join("Fonts");
+    if ["arial.ttf", "times.ttf", "cour.ttf"]
+        .iter()
+        .all(|f| fonts.join(f).is_file())
+    {
+        verbs.push("corefonts");
+    }
+    if system_reg.contains("\\\\VisualStudio\\\\14.0\\\\VC\\\\Runtimes")
+        && native("vcruntime140.dll")
+    {
+        verbs.push("vcrun2019");
+    }
+    verbs.into_iter().map(str::to_string).collect()
+}
+
+/// Verbs applied to `prefix`, in install order, from the winetricks.log
+/// winetricks keeps there. Prefixes without a log get a best-effort probe
+/// for dxvk, vkd3d, corefonts and vcrun2019 instead.
+#[tauri::command]
+fn list_installed_verbs(prefix: String) -> Result<Vec<String>, String> {
+    #[cfg(windows)]
+    {
+        let _ = prefix;
+        Err("Wine prefixes are not supported on Windows".to_string())
+    }
+    #[cfg(not(windows))]
+    {
+        let wine_dir = resolve_wine_prefix(&prefix)?;
+        let Ok(log) = std::fs::read_to_string(wine_dir.join("winetricks.log")) else {
+            return Ok(probe_installed_verbs(&wine_dir));
+        };
+        let mut seen = HashSet::new();
+        Ok(log
+            .lines()
+            .map(str::trim)
+            .filter(|verb| !verb.is_empty() && !verb.starts_with(['#', '-']))
+            .filter(|verb| seen.insert(verb.to_string()))
+            .map(str::to_string)
+            .collect())
+    }
+}
+
 #[tauri::command]
 fn install_dxvk_vkd3d(
     prefix: String,
@@ -1215,12 +2648,11 @@ fn import_lutris_games() -> Vec<LutrisGameEntry> {
             }
         }
 
-        out.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        out.sort_by_key(|a| a.name.to_lowercase());
         out
     }
 }
 
-#[cfg(windows)]
 fn sqlite_table_columns(conn: &Connection, table: &str) -> HashSet<String> {
     let mut out = HashSet::new();
     let pragma = format!("PRAGMA table_info({table})");
@@ -1238,7 +2670,6 @@ fn sqlite_table_columns(conn: &Connection, table: &str) -> HashSet<String> {
     out
 }
 
-#[cfg(windows)]
 fn first_existing_column(cols: &HashSet<String>, candidates: &[&str]) -> Option<String> {
     candidates
         .iter()
@@ -1246,7 +2677,6 @@ fn first_existing_column(cols: &HashSet<String>, candidates: &[&str]) -> Option<
         .map(|s| (*s).to_string())
 }
 
-#[cfg(windows)]
 fn row_value_opt(row: &rusqlite::Row<'_>, idx: usize) -> Option<String> {
     let v = row.get_ref(idx).ok()?;
     match v {
@@ -1394,9 +2824,11 @@ fn import_playnite_games() -> Vec<InteropGameEntry> {
                 exe,
                 args: args.filter(|s| !s.trim().is_empty()),
                 source: "playnite".to_string(),
+                install_path: install_dir,
+                prefix: None,
             });
         }
-        out.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        out.sort_by_key(|a| a.name.to_lowercase());
         out
     }
 }
@@ -1527,9 +2959,403 @@ fn import_gog_galaxy_games() -> Vec<InteropGameEntry> {
                 exe,
                 args: args.filter(|s| !s.trim().is_empty()),
                 source: "gog-galaxy".to_string(),
+                install_path: None,
+                prefix: None,
             });
         }
-        out.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        out.sort_by_key(|a| a.name.to_lowercase());
+        out
+    }
+}
+
+/// Installed games from the Epic Games Launcher's `.item` manifests.
+#[tauri::command]
+fn import_epic_games() -> Vec<InteropGameEntry> {
+    #[cfg(not(windows))]
+    {
+        Vec::new()
+    }
+    #[cfg(windows)]
+    {
+        let program_data =
+            std::env::var("PROGRAMDATA").unwrap_or_else(|_| "C:\\ProgramData".to_string());
+        let manifests_dir = std::path::Path::new(&program_data)
+            .join("Epic")
+            .join("EpicGamesLauncher")
+            .join("Data")
+            .join("Manifests");
+        let Ok(entries) = std::fs::read_dir(&manifests_dir) else {
+            return Vec::new();
+        };
+
+        let mut out = Vec::<InteropGameEntry>::new();
+        let mut seen_exe = HashSet::<String>::new();
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path
+                .extension()
+                .map(|x| !x.eq_ignore_ascii_case("item"))
+                .unwrap_or(true)
+            {
+                continue;
+            }
+            let Some(manifest) = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+            else {
+                continue;
+            };
+            let field = |key: &str| {
+                manifest[key]
+                    .as_str()
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+            };
+            let (Some(install), Some(launch)) =
+                (field("InstallLocation"), field("LaunchExecutable"))
+            else {
+                continue;
+            };
+            let install = normalize_windows_path(&install);
+            let exe =
+                normalize_windows_path(&format!("{}\\{}", install.trim_end_matches('\\'), launch));
+            if !path_exists_file(&exe) || !seen_exe.insert(exe.to_lowercase()) {
+                continue;
+            }
+            let game_id = field("AppName")
+                .or_else(|| field("CatalogItemId"))
+                .unwrap_or_else(|| exe.clone());
+            let name = field("DisplayName").unwrap_or_else(|| game_id.clone());
+            out.push(InteropGameEntry {
+                name,
+                game_id,
+                exe,
+                args: field("LaunchCommand"),
+                source: "epic".to_string(),
+                install_path: Some(install),
+                prefix: None,
+            });
+        }
+        out.sort_by_key(|g| g.name.to_lowercase());
+        out
+    }
+}
+
+/// `rel` under `base`, with Windows separators on Windows.
+fn itch_join(base: &str, rel: &str) -> String {
+    let joined = Path::new(base).join(rel).to_string_lossy().into_owned();
+    #[cfg(windows)]
+    {
+        normalize_windows_path(&joined)
+    }
+    #[cfg(not(windows))]
+    {
+        joined
+    }
+}
+
+/// Launch target of an itch cave from its `verdict` JSON (butler's install
+/// scan), as an absolute path.
+fn itch_verdict_exe(verdict: &str) -> Option<String> {
+    let v: serde_json::Value = serde_json::from_str(verdict).ok()?;
+    let base = v["basePath"].as_str()?;
+    v["candidates"].as_array()?.iter().find_map(|c| {
+        let exe = itch_join(base, c["path"].as_str()?);
+        Path::new(&exe).is_file().then_some(exe)
+    })
+}
+
+/// Data folders of the itch desktop app, including the Flatpak on Linux.
+fn itch_data_dirs() -> Vec<PathBuf> {
+    #[cfg(windows)]
+    {
+        let appdata = std::env::var("APPDATA").unwrap_or_default();
+        vec![Path::new(&appdata).join("itch")]
+    }
+    #[cfg(not(windows))]
+    {
+        let home = PathBuf::from(std::env::var("HOME").unwrap_or_default());
+        vec![
+            home.join(".config").join("itch"),
+            home.join(".var/app/io.itch.itch/config/itch"),
+            home.join("Library/Application Support/itch"),
+        ]
+    }
+}
+
+/// Installed games from the itch desktop app, read from butler's SQLite
+/// database.
+#[tauri::command]
+fn import_itch_games() -> Vec<InteropGameEntry> {
+    let Some(db_path) = itch_data_dirs()
+        .into_iter()
+        .flat_map(|dir| {
+            [
+                dir.join("db").join("butler.db"),
+                dir.join("butler.db"),
+                dir.join("db.sqlite"),
+            ]
+        })
+        .find(|p| p.is_file())
+    else {
+        return Vec::new();
+    };
+    let Ok(conn) = Connection::open(db_path) else {
+        return Vec::new();
+    };
+
+    let cave_cols = sqlite_table_columns(&conn, "caves");
+    let required = ["game_id", "install_folder_name", "install_location_id"];
+    if !required.iter().all(|c| cave_cols.contains(*c))
+        || sqlite_table_columns(&conn, "install_locations").is_empty()
+    {
+        return Vec::new();
+    }
+    let title_col = first_existing_column(&sqlite_table_columns(&conn, "games"), &["title"]);
+    let title_expr = title_col.map_or("NULL".to_string(), |c| format!("g.{c}"));
+    let verdict_expr = if cave_cols.contains("verdict") {
+        "c.verdict"
+    } else {
+        "NULL"
+    };
+    let sql = format!(
+        "SELECT c.game_id, {title_expr}, l.path, c.install_folder_name, {verdict_expr} \
+         FROM caves c \
+         LEFT JOIN games g ON g.id = c.game_id \
+         LEFT JOIN install_locations l ON l.id = c.install_location_id"
+    );
+    let Ok(mut stmt) = conn.prepare(&sql) else {
+        return Vec::new();
+    };
+    let Ok(mut rows) = stmt.query([]) else {
+        return Vec::new();
+    };
+
+    let mut out = Vec::<InteropGameEntry>::new();
+    let mut seen_exe = HashSet::<String>::new();
+    while let Ok(Some(row)) = rows.next() {
+        let game_id = row_value_opt(row, 0).unwrap_or_default();
+        let title = row_value_opt(row, 1);
+        let location = row_value_opt(row, 2);
+        let folder = row_value_opt(row, 3);
+        let verdict = row_value_opt(row, 4);
+        let (Some(location), Some(folder)) = (location, folder) else {
+            continue;
+        };
+        let install_dir = itch_join(&location, &folder);
+        if !Path::new(&install_dir).is_dir() {
+            continue;
+        }
+        let exe = verdict
+            .as_deref()
+            .and_then(itch_verdict_exe)
+            .or_else(|| find_best_exe_in_install_dir(&install_dir));
+        let Some(exe) = exe else {
+            continue;
+        };
+        if !seen_exe.insert(path_dedup_key(&exe)) {
+            continue;
+        }
+        let name = title
+            .filter(|t| !t.is_empty())
+            .unwrap_or_else(|| folder.clone());
+        out.push(InteropGameEntry {
+            name,
+            game_id,
+            exe,
+            args: None,
+            source: "itch".to_string(),
+            install_path: Some(install_dir),
+            prefix: None,
+        });
+    }
+    out.sort_by_key(|g| g.name.to_lowercase());
+    out
+}
+
+/// Heroic config folders: native, Flatpak and macOS installs.
+#[cfg(not(windows))]
+fn heroic_config_dirs(home: &Path) -> Vec<PathBuf> {
+    [
+        home.join(".config").join("heroic"),
+        home.join(".var")
+            .join("app")
+            .join("com.heroicgameslauncher.hgl")
+            .join("config")
+            .join("heroic"),
+        home.join("Library")
+            .join("Application Support")
+            .join("heroic"),
+    ]
+    .into_iter()
+    .filter(|dir| dir.is_dir())
+    .collect()
+}
+
+#[cfg(not(windows))]
+fn read_json_file(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+/// Primary play task of a GOG install, from its `goggame-<id>.info`, as
+/// `(title, exe, arguments)`.
+#[cfg(not(windows))]
+fn gog_info_play_task(
+    install_dir: &Path,
+    app_name: &str,
+) -> (Option<String>, Option<String>, Option<String>) {
+    let Some(info) = read_json_file(&install_dir.join(format!("goggame-{app_name}.info"))) else {
+        return (None, None, None);
+    };
+    let title = info["name"].as_str().map(str::to_string);
+    let task = info["playTasks"].as_array().and_then(|tasks| {
+        tasks
+            .iter()
+            .find(|t| t["isPrimary"].as_bool().unwrap_or(false))
+            .or_else(|| tasks.first())
+    });
+    let exe = task
+        .and_then(|t| t["path"].as_str())
+        .map(|p| install_dir.join(p.replace('\\', "/")))
+        .filter(|p| p.is_file())
+        .map(|p| p.to_string_lossy().to_string());
+    let args = task
+        .and_then(|t| t["arguments"].as_str())
+        .map(str::to_string);
+    (title, exe, args)
+}
+
+#[cfg(not(windows))]
+struct HeroicInstall {
+    app_name: String,
+    title: String,
+    install_dir: PathBuf,
+    exe: Option<String>,
+    args: Option<String>,
+}
+
+/// Installed Epic (legendary) and GOG games from Heroic Games Launcher, with
+/// the Wine prefix and launcher arguments from each game's Heroic config.
+#[tauri::command]
+fn import_heroic_games() -> Vec<InteropGameEntry> {
+    #[cfg(windows)]
+    {
+        Vec::new()
+    }
+    #[cfg(not(windows))]
+    {
+        let home = PathBuf::from(std::env::var("HOME").unwrap_or_default());
+        let mut out = Vec::<InteropGameEntry>::new();
+        let mut seen_exe = HashSet::<String>::new();
+
+        for config_dir in heroic_config_dirs(&home) {
+            let mut installed: Vec<HeroicInstall> = Vec::new();
+
+            let legendary = read_json_file(
+                &config_dir
+                    .join("legendaryConfig")
+                    .join("legendary")
+                    .join("installed.json"),
+            );
+            if let Some(games) = legendary.as_ref().and_then(|v| v.as_object()) {
+                for (app_name, game) in games {
+                    if game["is_dlc"].as_bool().unwrap_or(false) {
+                        continue;
+                    }
+                    let Some(install_path) = game["install_path"].as_str() else {
+                        continue;
+                    };
+                    let install_dir = PathBuf::from(install_path);
+                    let exe = game["executable"]
+                        .as_str()
+                        .filter(|e| !e.is_empty())
+                        .map(|e| install_dir.join(e.replace('\\', "/")))
+                        .filter(|p| p.is_file())
+                        .map(|p| p.to_string_lossy().to_string());
+                    let title = game["title"].as_str().unwrap_or(app_name).to_string();
+                    installed.push(HeroicInstall {
+                        app_name: app_name.clone(),
+                        title,
+                        install_dir,
+                        exe,
+                        args: None,
+                    });
+                }
+            }
+
+            let gog = read_json_file(&config_dir.join("gog_store").join("installed.json"));
+            let gog_games = gog
+                .as_ref()
+                .and_then(|v| v["installed"].as_array())
+                .cloned()
+                .unwrap_or_default();
+            for game in gog_games {
+                if game["is_dlc"].as_bool().unwrap_or(false) {
+                    continue;
+                }
+                let (Some(app_name), Some(install_path)) =
+                    (game["appName"].as_str(), game["install_path"].as_str())
+                else {
+                    continue;
+                };
+                let install_dir = PathBuf::from(install_path);
+                let (title, exe, args) = gog_info_play_task(&install_dir, app_name);
+                let title = title.unwrap_or_else(|| format!("GOG {app_name}"));
+                installed.push(HeroicInstall {
+                    app_name: app_name.to_string(),
+                    title,
+                    install_dir,
+                    exe,
+                    args,
+                });
+            }
+
+            for HeroicInstall {
+                app_name,
+                title: name,
+                install_dir,
+                exe,
+                args,
+            } in installed
+            {
+                if !install_dir.is_dir() {
+                    continue;
+                }
+                let install_path = install_dir.to_string_lossy().to_string();
+                let Some(exe) = exe.or_else(|| find_best_exe_in_install_dir(&install_path)) else {
+                    continue;
+                };
+                if !seen_exe.insert(exe.to_lowercase()) {
+                    continue;
+                }
+                let game_config = read_json_file(
+                    &config_dir
+                        .join("gamesConfig")
+                        .join(format!("{app_name}.json")),
+                );
+                let settings = game_config.as_ref().map(|v| &v[app_name.as_str()]);
+                let prefix = settings
+                    .and_then(|s| s["winePrefix"].as_str())
+                    .filter(|p| !p.is_empty())
+                    .map(str::to_string);
+                let args = settings
+                    .and_then(|s| s["launcherArgs"].as_str())
+                    .map(str::to_string)
+                    .or(args)
+                    .filter(|s| !s.trim().is_empty());
+                out.push(InteropGameEntry {
+                    name,
+                    game_id: app_name,
+                    exe,
+                    args,
+                    source: "heroic".to_string(),
+                    install_path: Some(install_path),
+                    prefix,
+                });
+            }
+        }
+
+        out.sort_by_key(|g| g.name.to_lowercase());
         out
     }
 }
@@ -1540,33 +3366,355 @@ fn split_args(s: &str) -> Vec<String> {
     let mut current = String::new();
     let mut in_quotes: Option<char> = None;
 
-    for c in s.chars() {
-        match c {
-            '"' | '\'' => {
-                if in_quotes == Some(c) {
-                    in_quotes = None;
-                } else if in_quotes.is_none() {
-                    in_quotes = Some(c);
+    for c in s.chars() {
+        match c {
+            '"' | '\'' => {
+                if in_quotes == Some(c) {
+                    in_quotes = None;
+                } else if in_quotes.is_none() {
+                    in_quotes = Some(c);
+                } else {
+                    current.push(c);
+                }
+            }
+            ' ' | '\t' if in_quotes.is_none() => {
+                if !current.is_empty() {
+                    args.push(current.clone());
+                    current.clear();
+                }
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        args.push(current);
+    }
+    args
+}
+
+/// Performance overlays injected into Wine/Proton launches on Linux.
+#[derive(Deserialize, Clone, Default)]
+#[serde(default)]
+struct PerfOverlay {
+    /// Sets `MANGOHUD=1` (requires the MangoHud Vulkan layer).
+    mangohud: bool,
+    /// Sets `DXVK_HUD`, using `dxvk_hud_config` or "fps,memory".
+    dxvk_hud: bool,
+    dxvk_hud_config: Option<String>,
+}
+
+#[cfg(not(windows))]
+fn command_in_path(name: &str) -> bool {
+    std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).any(|dir| dir.join(name).is_file()))
+        .unwrap_or(false)
+}
+
+/// Rebuilds `inner` as arguments to `wrapper`, carrying over its env and
+/// working directory.
+#[cfg(not(windows))]
+fn wrap_command(inner: &Command, wrapper: &[String]) -> Command {
+    let mut cmd = Command::new(&wrapper[0]);
+    cmd.args(&wrapper[1..])
+        .arg(inner.get_program())
+        .args(inner.get_args());
+    for (key, value) in inner.get_envs() {
+        match value {
+            Some(v) => cmd.env(key, v),
+            None => cmd.env_remove(key),
+        };
+    }
+    if let Some(dir) = inner.get_current_dir() {
+        cmd.current_dir(dir);
+    }
+    cmd
+}
+
+#[cfg(not(windows))]
+fn apply_perf_overlay(cmd: &mut Command, overlay: &PerfOverlay, app: &AppHandle) {
+    if overlay.mangohud {
+        if !command_in_path("mangohud") {
+            push_rust_log(
+                Some(app),
+                "warn",
+                "MangoHud overlay enabled but mangohud is not installed; the overlay will not show",
+            );
+        }
+        cmd.env("MANGOHUD", "1");
+    }
+    if overlay.dxvk_hud {
+        let hud = overlay
+            .dxvk_hud_config
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .unwrap_or("fps,memory");
+        cmd.env("DXVK_HUD", hud);
+    }
+}
+
+/// Launcher exits faster than this are assumed to have handed off to a child.
+const LAUNCHER_HANDOFF_SECS: u64 = 5;
+const CHILD_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Pids (other than ours) whose executable lives under `dir`.
+#[cfg(windows)]
+fn processes_under(dir: &Path) -> Vec<u32> {
+    use std::os::windows::ffi::OsStringExt;
+    use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+    use winapi::um::processthreadsapi::OpenProcess;
+    use winapi::um::tlhelp32::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+        TH32CS_SNAPPROCESS,
+    };
+    use winapi::um::winbase::QueryFullProcessImageNameW;
+    use winapi::um::winnt::PROCESS_QUERY_LIMITED_INFORMATION;
+
+    let own = std::process::id();
+    let dir_key = path_dedup_key(&dir.to_string_lossy());
+    let mut out = Vec::new();
+    unsafe {
+        let snap = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+        if snap == INVALID_HANDLE_VALUE {
+            return out;
+        }
+        let mut entry: PROCESSENTRY32W = std::mem::zeroed();
+        entry.dwSize = std::mem::size_of::<PROCESSENTRY32W>() as u32;
+        let mut more = Process32FirstW(snap, &mut entry) != 0;
+        while more {
+            let pid = entry.th32ProcessID;
+            if pid != own && pid != 0 {
+                let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+                if !handle.is_null() {
+                    let mut buf = [0u16; 1024];
+                    let mut len = buf.len() as u32;
+                    if QueryFullProcessImageNameW(handle, 0, buf.as_mut_ptr(), &mut len) != 0 {
+                        let exe = std::ffi::OsString::from_wide(&buf[..len as usize]);
+                        let exe_key = path_dedup_key(&exe.to_string_lossy());
+                        if Path::new(&exe_key).starts_with(Path::new(&dir_key)) {
+                            out.push(pid);
+                        }
+                    }
+                    CloseHandle(handle);
+                }
+            }
+            more = Process32NextW(snap, &mut entry) != 0;
+        }
+        CloseHandle(snap);
+    }
+    out
+}
+
+/// Pids (other than ours) whose executable lives under `dir`. Wine processes
+/// report the preloader as their exe, so the program path at the start of
+/// the command line is checked too.
+#[cfg(target_os = "linux")]
+fn processes_under(dir: &Path) -> Vec<u32> {
+    let own = std::process::id();
+    let entries = match std::fs::read_dir("/proc") {
+        Ok(e) => e,
+        Err(_) => return Vec::new(),
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.file_name().to_string_lossy().parse::<u32>().ok())
+        .filter(|&pid| pid != own)
+        .filter(|pid| {
+            let proc_dir = PathBuf::from(format!("/proc/{}", pid));
+            if let Ok(exe) = std::fs::read_link(proc_dir.join("exe")) {
+                if exe.starts_with(dir) {
+                    return true;
+                }
+            }
+            let raw = match std::fs::read(proc_dir.join("cmdline")) {
+                Ok(raw) => raw,
+                Err(_) => return false,
+            };
+            let program = String::from_utf8_lossy(raw.split(|b| *b == 0).next().unwrap_or(&[]));
+            // Wine maps the unix root to drive Z:.
+            let unix = match program.get(..2) {
+                Some("Z:") | Some("z:") => program[2..].replace('\\', "/"),
+                _ => program.to_string(),
+            };
+            !unix.is_empty() && Path::new(&unix).starts_with(dir)
+        })
+        .collect()
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn processes_under(_dir: &Path) -> Vec<u32> {
+    Vec::new()
+}
+
+#[derive(Serialize, Clone)]
+struct LaunchFailedPayload {
+    path: String,
+    error: String,
+}
+
+/// Runs a user pre-launch/post-exit hook to completion in the game folder.
+/// Returns whether it exited successfully.
+fn run_launch_hook(app: &AppHandle, stage: &str, hook: &str, dir: Option<&Path>) -> bool {
+    let parts = split_args(hook);
+    let (program, rest) = match parts.split_first() {
+        Some(p) => p,
+        None => return true,
+    };
+    let mut cmd = Command::new(program);
+    cmd.args(rest);
+    if let Some(d) = dir {
+        cmd.current_dir(d);
+    }
+    match cmd.status() {
+        Ok(status) if status.success() => {
+            push_rust_log(
+                Some(app),
+                "info",
+                format!("{} hook finished: {}", stage, status),
+            );
+            true
+        }
+        Ok(status) => {
+            push_rust_log(
+                Some(app),
+                "error",
+                format!("{} hook failed: {}", stage, status),
+            );
+            false
+        }
+        Err(e) => {
+            push_rust_log(
+                Some(app),
+                "error",
+                format!("{} hook could not start: {}", stage, e),
+            );
+            false
+        }
+    }
+}
+
+/// Builds the game command shared by `launch_game` and `launch_game_debug`.
+/// On Windows the exe always runs directly; elsewhere it goes through
+/// `easyrpg` (a prepared EasyRPG Player command), the Wine or Proton
+/// `runner`, or runs directly. `debug` turns on Wine/Proton logging.
+#[allow(clippy::too_many_arguments)]
+fn build_launch_command(
+    app: &AppHandle,
+    path: &str,
+    runner: Option<&str>,
+    prefix: Option<&str>,
+    args: Option<&str>,
+    perf_overlay: Option<&PerfOverlay>,
+    env: Option<HashMap<String, String>>,
+    wrapper: Option<&[String]>,
+    easyrpg: Option<Command>,
+    debug: bool,
+) -> Command {
+    let parent = Path::new(path).parent();
+    let mut command = {
+        #[cfg(windows)]
+        {
+            let _ = (runner, prefix, easyrpg, debug); // unused on Windows
+            let mut cmd = Command::new(path);
+            if let Some(p) = parent {
+                cmd.current_dir(p);
+            }
+            cmd
+        }
+        #[cfg(not(windows))]
+        {
+            if let Some(cmd) = easyrpg {
+                cmd
+            } else if let Some(runner_path) = runner {
+                let is_proton = Path::new(runner_path)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().eq_ignore_ascii_case("proton"))
+                    .unwrap_or(false);
+                let mut cmd = Command::new(runner_path);
+                if is_proton {
+                    cmd.arg("run");
+                    // Proton requires STEAM_COMPAT_DATA_PATH (the Wine prefix parent)
+                    if let Some(pfx) = prefix {
+                        cmd.env("STEAM_COMPAT_DATA_PATH", pfx);
+                    }
+                    // Proton also needs STEAM_COMPAT_CLIENT_INSTALL_PATH
+                    if let Ok(steam_root) = std::env::var("HOME") {
+                        let steam_path = format!("{steam_root}/.local/share/Steam");
+                        if Path::new(&steam_path).exists() {
+                            cmd.env("STEAM_COMPAT_CLIENT_INSTALL_PATH", &steam_path);
+                        }
+                    }
+                    // Proton picks its own, more verbose WINEDEBUG for logs.
+                    if debug {
+                        cmd.env("PROTON_LOG", "1");
+                    }
                 } else {
-                    current.push(c);
+                    // Wine — set WINEPREFIX if provided
+                    if let Some(pfx) = prefix {
+                        cmd.env("WINEPREFIX", pfx);
+                    }
+                    if debug {
+                        cmd.env("WINEDEBUG", "+err,+seh");
+                    }
                 }
-            }
-            ' ' | '\t' if in_quotes.is_none() => {
-                if !current.is_empty() {
-                    args.push(current.clone());
-                    current.clear();
+                cmd.arg(path);
+                if let Some(p) = parent {
+                    cmd.current_dir(p);
                 }
+                cmd
+            } else {
+                // No runner — attempt to run directly (native or Wine-managed script)
+                let mut cmd = Command::new(path);
+                if let Some(p) = parent {
+                    cmd.current_dir(p);
+                }
+                cmd
             }
-            _ => current.push(c),
         }
+    };
+
+    if let Some(arg_str) = args {
+        command.args(split_args(arg_str));
     }
-    if !current.is_empty() {
-        args.push(current);
+
+    #[cfg(not(windows))]
+    if let Some(overlay) = perf_overlay {
+        apply_perf_overlay(&mut command, overlay, app);
     }
-    args
+    #[cfg(windows)]
+    let _ = (perf_overlay, app);
+
+    if let Some(vars) = env {
+        command.envs(vars);
+    }
+
+    #[cfg(not(windows))]
+    if let Some(wrapper) = wrapper {
+        if !wrapper.is_empty() {
+            command = wrap_command(&command, wrapper);
+        }
+    }
+    #[cfg(windows)]
+    let _ = wrapper;
+
+    command
 }
 
+/// Launches a game and tracks it until it exits. `env` is applied last, so it
+/// overrides inherited variables and, only when it names them, the
+/// `WINEPREFIX`/`STEAM_COMPAT_*`/overlay variables set here. Tray quick-launch
+/// passes no extra env. `wrapper` (e.g. `["gamemoderun"]` or
+/// `["gamescope", "-W", "1920", "--"]`) is prepended to the whole invocation on
+/// non-Windows platforms; the wrapper is then the tracked process.
+/// `pre_launch` runs before the game and cancels the launch (with a
+/// `game-launch-failed` event) if it fails; `post_exit` runs once the game has
+/// exited, however it exited. `track_children` (on by default) keeps the
+/// session open while processes from the game folder are still running after
+/// a launcher exits within a few seconds. `use_easyrpg` runs an RPG Maker
+/// 2000/2003 game with EasyRPG Player instead of Wine (not on Windows) and
+/// fails up front when the game or the player does not qualify.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 fn launch_game(
     app: AppHandle,
     path: String,
@@ -1574,69 +3722,62 @@ fn launch_game(
     prefix: Option<String>,
     args: Option<String>,
     boss_key: Option<screenshot::BossKeyConfig>,
+    screenshot_config: Option<screenshot::ScreenshotConfig>,
+    perf_overlay: Option<PerfOverlay>,
+    env: Option<HashMap<String, String>>,
+    wrapper: Option<Vec<String>>,
+    pre_launch: Option<String>,
+    post_exit: Option<String>,
+    track_children: Option<bool>,
+    use_easyrpg: Option<bool>,
 ) -> Result<(), String> {
+    applock::ensure_unlocked()?;
+    #[cfg(not(windows))]
+    let easyrpg = match use_easyrpg {
+        Some(true) => Some(rpgmaker::easyrpg_launch_command(&path)?),
+        _ => None,
+    };
+    #[cfg(windows)]
+    let easyrpg = match use_easyrpg {
+        Some(true) => {
+            return Err("EasyRPG Player launching is not supported on Windows".to_string())
+        }
+        _ => None,
+    };
     let path_clone = path.clone();
     thread::spawn(move || {
         let parent = std::path::Path::new(&path_clone).parent();
 
-        // Build the command — on Windows always run directly; on other platforms
-        // optionally wrap via Wine or Proton.
-        let mut command = {
-            #[cfg(windows)]
-            {
-                let _ = (&runner, &prefix); // unused on Windows
-                let mut cmd = Command::new(&path_clone);
-                if let Some(p) = parent {
-                    cmd.current_dir(p);
-                }
-                cmd
+        if let Some(ref hook) = pre_launch {
+            if !run_launch_hook(&app, "Pre-launch", hook, parent) {
+                let _ = app.emit(
+                    "game-launch-failed",
+                    LaunchFailedPayload {
+                        path: path_clone.clone(),
+                        error: "Pre-launch hook failed".to_string(),
+                    },
+                );
+                return;
             }
-            #[cfg(not(windows))]
-            {
-                if let Some(ref runner_path) = runner {
-                    let is_proton = std::path::Path::new(runner_path)
-                        .file_name()
-                        .map(|n| n.to_string_lossy().eq_ignore_ascii_case("proton"))
-                        .unwrap_or(false);
-                    let mut cmd = Command::new(runner_path);
-                    if is_proton {
-                        cmd.arg("run");
-                        // Proton requires STEAM_COMPAT_DATA_PATH (the Wine prefix parent)
-                        if let Some(ref pfx) = prefix {
-                            cmd.env("STEAM_COMPAT_DATA_PATH", pfx);
-                        }
-                        // Proton also needs STEAM_COMPAT_CLIENT_INSTALL_PATH
-                        if let Ok(steam_root) = std::env::var("HOME") {
-                            let steam_path = format!("{steam_root}/.local/share/Steam");
-                            if std::path::Path::new(&steam_path).exists() {
-                                cmd.env("STEAM_COMPAT_CLIENT_INSTALL_PATH", &steam_path);
-                            }
-                        }
-                    } else {
-                        // Wine — set WINEPREFIX if provided
-                        if let Some(ref pfx) = prefix {
-                            cmd.env("WINEPREFIX", pfx);
-                        }
-                    }
-                    cmd.arg(&path_clone);
-                    if let Some(p) = parent {
-                        cmd.current_dir(p);
-                    }
-                    cmd
-                } else {
-                    // No runner — attempt to run directly (native or Wine-managed script)
-                    let mut cmd = Command::new(&path_clone);
-                    if let Some(p) = parent {
-                        cmd.current_dir(p);
-                    }
-                    cmd
-                }
+        }
+        let run_post_exit = |app: &AppHandle| {
+            if let Some(ref hook) = post_exit {
+                run_launch_hook(app, "Post-exit", hook, parent);
             }
         };
 
-        if let Some(arg_str) = args {
-            command.args(split_args(&arg_str));
-        }
+        let mut command = build_launch_command(
+            &app,
+            &path_clone,
+            runner.as_deref(),
+            prefix.as_deref(),
+            args.as_deref(),
+            perf_overlay.as_ref(),
+            env,
+            wrapper.as_deref(),
+            easyrpg,
+            false,
+        );
 
         match command.spawn() {
             Ok(mut child) => {
@@ -1659,14 +3800,32 @@ fn launch_game(
                 let exe_hk = path_clone.clone();
                 let app_hk = app.clone();
                 let boss_hk = boss_key.clone();
+                let config_hk = screenshot_config.clone().unwrap_or_default();
                 thread::spawn(move || {
-                    screenshot::start_hotkey_listener(pid, exe_hk, app_hk, boss_hk, tx);
+                    screenshot::start_hotkey_listener(pid, exe_hk, app_hk, boss_hk, config_hk, tx);
                 });
                 let hotkey_thread_id = rx.recv().unwrap_or(0);
 
                 let start_time = Instant::now();
                 let _ = child.wait();
+
+                // Launchers like Ren'Py or NW.js start the real game and exit
+                // at once; follow whatever they left running in the game folder.
+                if track_children.unwrap_or(true)
+                    && start_time.elapsed().as_secs() < LAUNCHER_HANDOFF_SECS
+                {
+                    if let Some(dir) = parent {
+                        while let Some(&child_pid) = processes_under(dir).first() {
+                            let state = app.state::<screenshot::ActiveGameState>();
+                            if let Some(active) = state.0.lock().unwrap().as_mut() {
+                                active.pid = child_pid;
+                            }
+                            thread::sleep(CHILD_POLL_INTERVAL);
+                        }
+                    }
+                }
                 let duration = start_time.elapsed().as_secs();
+                run_post_exit(&app);
 
                 // Tear down hotkey thread
                 screenshot::stop_hotkey_thread(hotkey_thread_id);
@@ -1677,6 +3836,14 @@ fn launch_game(
                     *state.0.lock().unwrap() = None;
                 }
 
+                if let Err(e) = playtime::record_session(&path_clone, duration) {
+                    push_rust_log(
+                        Some(&app),
+                        "warn",
+                        format!("Failed to save playtime: {}", e),
+                    );
+                }
+
                 let _ = app.emit(
                     "game-finished",
                     GameEndedPayload {
@@ -1687,12 +3854,179 @@ fn launch_game(
             }
             Err(e) => {
                 push_rust_log(Some(&app), "error", format!("Failed to launch game: {}", e));
+                // Undo whatever the pre-launch hook set up.
+                run_post_exit(&app);
             }
         }
     });
     Ok(())
 }
 
+/// How much of a debug launch log `launch_game_debug` returns inline.
+const DEBUG_LOG_TAIL_BYTES: u64 = 64 * 1024;
+
+#[derive(Serialize)]
+struct DebugLaunchResult {
+    /// `None` when the game was killed by a signal.
+    exit_code: Option<i32>,
+    /// Complete stdout/stderr of the run.
+    log_path: String,
+    /// The last `DEBUG_LOG_TAIL_BYTES` of the log.
+    output: String,
+    duration_secs: u64,
+}
+
+/// Last `max` bytes of a file, lossily decoded.
+fn read_file_tail(path: &Path, max: u64) -> Result<String, String> {
+    use std::io::{Read, Seek, SeekFrom};
+    let mut file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let len = file.metadata().map_err(|e| e.to_string())?.len();
+    file.seek(SeekFrom::Start(len.saturating_sub(max)))
+        .map_err(|e| e.to_string())?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).map_err(|e| e.to_string())?;
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+/// Runs a game once for a bug report and waits for it to exit. Its stdout and
+/// stderr go to `logs/launch-<exe>-<time>.log` in the data folder; Wine runs
+/// with `WINEDEBUG=+err,+seh` and Proton with `PROTON_LOG=1`, writing its own
+/// log to the same folder. Arguments mean the same as for `launch_game`, but
+/// no hotkeys, hooks or playtime tracking are set up.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn launch_game_debug(
+    app: AppHandle,
+    path: String,
+    runner: Option<String>,
+    prefix: Option<String>,
+    args: Option<String>,
+    env: Option<HashMap<String, String>>,
+    wrapper: Option<Vec<String>>,
+    use_easyrpg: Option<bool>,
+) -> Result<DebugLaunchResult, String> {
+    applock::ensure_unlocked()?;
+    #[cfg(not(windows))]
+    let easyrpg = match use_easyrpg {
+        Some(true) => Some(rpgmaker::easyrpg_launch_command(&path)?),
+        _ => None,
+    };
+    #[cfg(windows)]
+    let easyrpg = match use_easyrpg {
+        Some(true) => {
+            return Err("EasyRPG Player launching is not supported on Windows".to_string())
+        }
+        _ => None,
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let log_dir = app_data_root().join("logs");
+        std::fs::create_dir_all(&log_dir).map_err(|e| e.to_string())?;
+        let stem = Path::new(&path)
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "game".to_string());
+        let log_path = log_dir.join(format!("launch-{stem}-{}.log", now_ms()));
+        let log = std::fs::File::create(&log_path).map_err(|e| e.to_string())?;
+        let log_err = log.try_clone().map_err(|e| e.to_string())?;
+
+        let mut command = build_launch_command(
+            &app,
+            &path,
+            runner.as_deref(),
+            prefix.as_deref(),
+            args.as_deref(),
+            None,
+            env,
+            wrapper.as_deref(),
+            easyrpg,
+            true,
+        );
+        #[cfg(not(windows))]
+        command.env("PROTON_LOG_DIR", &log_dir);
+        command
+            .stdin(std::process::Stdio::null())
+            .stdout(log)
+            .stderr(log_err);
+
+        push_rust_log(
+            Some(&app),
+            "info",
+            format!("Debug launch of {path}, logging to {}", log_path.display()),
+        );
+        let started = Instant::now();
+        let status = command
+            .status()
+            .map_err(|e| format!("Failed to launch game: {e}"))?;
+        Ok(DebugLaunchResult {
+            exit_code: status.code(),
+            log_path: log_path.to_string_lossy().into_owned(),
+            output: read_file_tail(&log_path, DEBUG_LOG_TAIL_BYTES)?,
+            duration_secs: started.elapsed().as_secs(),
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[derive(Serialize)]
+struct ProtonLog {
+    path: String,
+    /// The last `DEBUG_LOG_TAIL_BYTES` of the log.
+    contents: String,
+    /// Unix seconds.
+    modified: u64,
+}
+
+/// The newest Proton log (`steam-<appid>.log`, or that app's log when
+/// `app_id` is given). `launch_game_debug` has Proton write to the `logs`
+/// data folder; plain `PROTON_LOG=1` launches write to the home folder, so
+/// both are searched.
+#[tauri::command]
+fn get_proton_log(app_id: Option<String>) -> Result<ProtonLog, String> {
+    #[cfg(windows)]
+    {
+        let _ = app_id;
+        Err("Proton is not available on Windows".to_string())
+    }
+    #[cfg(not(windows))]
+    {
+        let wanted = app_id.map(|id| format!("steam-{}.log", id.trim()));
+        let mut dirs = vec![app_data_root().join("logs")];
+        if let Some(home) = std::env::var_os("HOME") {
+            dirs.push(PathBuf::from(home));
+        }
+        let newest = dirs
+            .iter()
+            .filter_map(|dir| std::fs::read_dir(dir).ok())
+            .flat_map(|entries| entries.filter_map(|e| e.ok()))
+            .filter(|e| {
+                let name = e.file_name().to_string_lossy().to_string();
+                match &wanted {
+                    Some(w) => name == *w,
+                    None => name.starts_with("steam-") && name.ends_with(".log"),
+                }
+            })
+            .filter_map(|e| {
+                let modified = e.metadata().ok()?.modified().ok()?;
+                Some((modified, e.path()))
+            })
+            .max_by_key(|(modified, _)| *modified);
+        let (modified, path) = newest.ok_or_else(|| {
+            "No Proton log found. Launch the game with Proton logging (PROTON_LOG=1) first"
+                .to_string()
+        })?;
+        Ok(ProtonLog {
+            path: path.to_string_lossy().into_owned(),
+            contents: read_file_tail(&path, DEBUG_LOG_TAIL_BYTES)?,
+            modified: modified
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        })
+    }
+}
+
 /// Kills the currently-running game process.
 #[tauri::command]
 fn kill_game(app: AppHandle) -> Result<(), String> {
@@ -1743,6 +4077,87 @@ struct AppUpdateInfo {
     /// Direct download URL for the platform-appropriate asset (zip/tar.gz).
     /// Empty string when no matching asset was found in the release.
     download_url: String,
+    /// SHA-256 of the download, from a `<asset>.sha256` file or a checksums
+    /// list published with the release.
+    sha256: Option<String>,
+}
+
+const LATEST_RELEASE_API_URL: &str =
+    "https://api.github.com/repos/Baconana-chan/Libmaly/releases/latest";
+
+/// The SHA-256 the release publishes for `asset_name`: a `<asset>.sha256`
+/// asset wins, else a `SHA256SUMS`/`checksums` style list (`<hash>  <file>`).
+async fn fetch_release_sha256(
+    client: &reqwest::Client,
+    assets: &[serde_json::Value],
+    asset_name: &str,
+) -> Option<String> {
+    let asset_name = asset_name.to_lowercase();
+    let name_of = |a: &serde_json::Value| a["name"].as_str().unwrap_or("").to_lowercase();
+    let sidecar = assets
+        .iter()
+        .find(|a| name_of(a) == format!("{asset_name}.sha256"));
+    let list = assets.iter().find(|a| {
+        let name = name_of(a);
+        name.contains("sha256sum") || name.contains("checksums")
+    });
+    let (asset, is_list) = match (sidecar, list) {
+        (Some(a), _) => (a, false),
+        (None, Some(a)) => (a, true),
+        (None, None) => return None,
+    };
+    let body = client
+        .get(asset["browser_download_url"].as_str()?)
+        .send()
+        .await
+        .ok()?
+        .text()
+        .await
+        .ok()?;
+    let is_hash = |h: &str| h.len() == 64 && h.chars().all(|c| c.is_ascii_hexdigit());
+    body.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let hash = parts.next()?.to_lowercase();
+        if !is_hash(&hash) {
+            return None;
+        }
+        // Sidecars may list just the hash; lists must name the file
+        // (`*` marks binary mode in sha256sum output).
+        let file = parts
+            .next()
+            .map(|f| f.trim_start_matches('*').to_lowercase());
+        let matches = match file {
+            Some(f) => f == asset_name || f.ends_with(&format!("/{asset_name}")),
+            None => !is_list,
+        };
+        matches.then_some(hash)
+    })
+}
+
+/// The SHA-256 the latest release publishes for the asset at `download_url`.
+/// `Err` when the release cannot be fetched or does not carry that asset.
+async fn lookup_update_sha256(download_url: &str) -> Result<Option<String>, String> {
+    let client = reqwest::Client::builder()
+        .user_agent("libmaly-update-checker")
+        .timeout(std::time::Duration::from_secs(8))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let resp = client
+        .get(LATEST_RELEASE_API_URL)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        return Err(format!("HTTP {}", resp.status()));
+    }
+    let json: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
+    let assets = json["assets"].as_array().cloned().unwrap_or_default();
+    let asset_name = assets
+        .iter()
+        .find(|a| a["browser_download_url"].as_str() == Some(download_url))
+        .and_then(|a| a["name"].as_str())
+        .ok_or("the download is not an asset of the latest release")?;
+    Ok(fetch_release_sha256(&client, &assets, asset_name).await)
 }
 
 /// Checks the GitHub Releases API for a newer version of LIBMALY.
@@ -1777,7 +4192,7 @@ async fn check_app_update() -> Result<Option<AppUpdateInfo>, String> {
         .map_err(|e| e.to_string())?;
 
     let resp = client
-        .get("https://api.github.com/repos/Baconana-chan/Libmaly/releases/latest")
+        .get(LATEST_RELEASE_API_URL)
         .send()
         .await
         .map_err(|e| e.to_string())?;
@@ -1803,6 +4218,7 @@ async fn check_app_update() -> Result<Option<AppUpdateInfo>, String> {
 
     // Pick the best asset download URL for this platform
     let mut download_url = String::new();
+    let mut asset_name = String::new();
     if let Some(assets) = json["assets"].as_array() {
         // Prefer a .zip or .tar.gz archive over a setup installer so we can
         // do in-place extraction without needing admin rights.
@@ -1813,6 +4229,7 @@ async fn check_app_update() -> Result<Option<AppUpdateInfo>, String> {
                 let dl = asset["browser_download_url"].as_str().unwrap_or("");
                 if name.contains(keyword) && archive_exts.iter().any(|e| name.ends_with(e)) {
                     download_url = dl.to_string();
+                    asset_name = name;
                     break 'outer;
                 }
             }
@@ -1823,8 +4240,9 @@ async fn check_app_update() -> Result<Option<AppUpdateInfo>, String> {
                 for asset in assets {
                     let name = asset["name"].as_str().unwrap_or("").to_lowercase();
                     let dl = asset["browser_download_url"].as_str().unwrap_or("");
-                    if name.contains(keyword) && !dl.is_empty() {
+                    if name.contains(keyword) && !dl.is_empty() && !name.ends_with(".sha256") {
                         download_url = dl.to_string();
+                        asset_name = name;
                         break 'outer2;
                     }
                 }
@@ -1832,19 +4250,182 @@ async fn check_app_update() -> Result<Option<AppUpdateInfo>, String> {
         }
     }
 
+    let sha256 = match json["assets"].as_array() {
+        Some(assets) if !asset_name.is_empty() => {
+            fetch_release_sha256(&client, assets, &asset_name).await
+        }
+        _ => None,
+    };
+
     Ok(Some(AppUpdateInfo {
         version: tag,
         url,
         download_url,
+        sha256,
     }))
 }
 
+/// What `apply_update` would install, worked out without touching the
+/// install dir.
+#[derive(Serialize)]
+struct AppUpdatePreview {
+    archive_name: String,
+    /// `.exe`/`.msi` installers are run as-is rather than unpacked.
+    is_installer: bool,
+    file_count: usize,
+    /// Uncompressed size of all files, in bytes.
+    total_size: u64,
+    /// Top-level directory wrapping the whole archive; it is stripped on
+    /// install.
+    wrapper_dir: Option<String>,
+    exe_name: String,
+    /// Whether the archive carries a file named like the running exe.
+    replaces_exe: bool,
+}
+
+/// The file name of the asset at `download_url`.
+fn update_archive_name(download_url: &str) -> String {
+    download_url
+        .split('/')
+        .next_back()
+        .unwrap_or("update.zip")
+        .to_string()
+}
+
+async fn download_app_update(download_url: &str) -> Result<Vec<u8>, String> {
+    let client = reqwest::Client::builder()
+        .user_agent("libmaly-updater")
+        .timeout(std::time::Duration::from_secs(120))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let bytes = client
+        .get(download_url)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .bytes()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(bytes.to_vec())
+}
+
+/// Files in a downloaded update, as paths relative to the archive root
+/// (wrapper included) with their uncompressed sizes.
+struct UpdateListing {
+    files: Vec<(String, u64)>,
+    wrapper_dir: Option<String>,
+}
+
+fn list_update_archive(archive_path: &Path, staging: &Path) -> Result<UpdateListing, String> {
+    if archive_path.extension().is_some_and(|e| e == "zip") {
+        let f = std::fs::File::open(archive_path).map_err(|e| e.to_string())?;
+        let mut archive = zip::ZipArchive::new(f).map_err(|e| e.to_string())?;
+        let wrapper_dir = updater::zip_wrapper_dir(&updater::zip_entries(&mut archive)?)
+            .map(|w| w.to_string_lossy().into_owned());
+        let mut files = Vec::new();
+        for i in 0..archive.len() {
+            let entry = archive.by_index(i).map_err(|e| e.to_string())?;
+            if !entry.is_dir() {
+                files.push((entry.name().to_string(), entry.size()));
+            }
+        }
+        return Ok(UpdateListing { files, wrapper_dir });
+    }
+    // tar.gz / 7z have no in-process reader here, so list them extracted.
+    updater::extract_archive(archive_path, staging)?;
+    let root = updater::unwrap_single_dir(staging);
+    let wrapper_dir = root
+        .strip_prefix(staging)
+        .ok()
+        .filter(|rel| !rel.as_os_str().is_empty())
+        .map(|rel| rel.to_string_lossy().into_owned());
+    let files = WalkDir::new(staging)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| {
+            let rel = e.path().strip_prefix(staging).unwrap_or(e.path());
+            let size = e.metadata().map_or(0, |m| m.len());
+            (rel.to_string_lossy().replace('\\', "/"), size)
+        })
+        .collect();
+    Ok(UpdateListing { files, wrapper_dir })
+}
+
+/// Downloads the update at `download_url` to the temp dir and reports what
+/// `apply_update` would install: file count, uncompressed size, the wrapper
+/// directory and whether the running exe gets replaced. Nothing is
+/// installed and the app keeps running.
+#[tauri::command]
+async fn preview_app_update(download_url: String) -> Result<AppUpdatePreview, String> {
+    if download_url.is_empty() {
+        return Err("No download URL provided".to_string());
+    }
+    let exe_name = std::env::current_exe()
+        .map_err(|e| e.to_string())?
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let tmp_dir = std::env::temp_dir().join("libmaly-update-preview");
+    if tmp_dir.exists() {
+        std::fs::remove_dir_all(&tmp_dir).map_err(|e| e.to_string())?;
+    }
+    std::fs::create_dir_all(&tmp_dir).map_err(|e| e.to_string())?;
+
+    let archive_name = update_archive_name(&download_url);
+    let archive_path = tmp_dir.join(&archive_name);
+    let bytes = download_app_update(&download_url).await?;
+    std::fs::write(&archive_path, &bytes).map_err(|e| e.to_string())?;
+
+    let is_installer = archive_name.ends_with(".exe") || archive_name.ends_with(".msi");
+    let listed = if is_installer {
+        Ok(UpdateListing {
+            files: vec![(archive_name.clone(), bytes.len() as u64)],
+            wrapper_dir: None,
+        })
+    } else if updater::is_supported_archive(&archive_path) {
+        list_update_archive(&archive_path, &tmp_dir.join(".extract"))
+    } else {
+        Err(format!(
+            "Archive format not supported for auto-update: {}. Please install manually from the release page.",
+            archive_name
+        ))
+    };
+    let _ = std::fs::remove_dir_all(&tmp_dir);
+    let UpdateListing { files, wrapper_dir } = listed?;
+
+    let replaces_exe = !is_installer
+        && files.iter().any(|(name, _)| {
+            name.rsplit('/')
+                .next()
+                .is_some_and(|n| n.eq_ignore_ascii_case(&exe_name))
+        });
+    Ok(AppUpdatePreview {
+        archive_name,
+        is_installer,
+        file_count: files.len(),
+        total_size: files.iter().map(|(_, size)| size).sum(),
+        wrapper_dir,
+        exe_name,
+        replaces_exe,
+    })
+}
+
 /// Download the update archive, extract it next to the current executable, and
 /// launch a tiny platform script that will copy the files over once we exit.
 ///
 /// Keeps user data safe: default mode uses AppData, portable mode keeps data next to the executable.
+/// The download is checked against `expected_sha256` (from
+/// `AppUpdateInfo::sha256`) before anything is extracted; when it is not
+/// passed, the hash is looked up in the latest release.
 #[tauri::command]
-async fn apply_update(app: AppHandle, download_url: String) -> Result<(), String> {
+async fn apply_update(
+    app: AppHandle,
+    download_url: String,
+    expected_sha256: Option<String>,
+) -> Result<(), String> {
     use std::io::Write;
 
     if download_url.is_empty() {
@@ -1866,27 +4447,51 @@ async fn apply_update(app: AppHandle, download_url: String) -> Result<(), String
     std::fs::create_dir_all(&tmp_dir).map_err(|e| e.to_string())?;
 
     // 3. Download the archive
-    let client = reqwest::Client::builder()
-        .user_agent("libmaly-updater")
-        .timeout(std::time::Duration::from_secs(120))
-        .build()
-        .map_err(|e| e.to_string())?;
+    let bytes = download_app_update(&download_url).await?;
 
-    let bytes = client
-        .get(&download_url)
-        .send()
-        .await
-        .map_err(|e| e.to_string())?
-        .bytes()
-        .await
-        .map_err(|e| e.to_string())?;
+    // A truncated or tampered download must not reach the install dir.
+    // Without a hash from the caller, look it up in the release itself.
+    let expected_sha256 = match expected_sha256
+        .map(|h| h.trim().to_lowercase())
+        .filter(|h| !h.is_empty())
+    {
+        Some(hash) => Some(hash),
+        None => match lookup_update_sha256(&download_url).await {
+            Ok(Some(hash)) => Some(hash),
+            Ok(None) => {
+                push_rust_log(
+                    Some(&app),
+                    "warn",
+                    "The release publishes no SHA-256 for this update; skipping verification",
+                );
+                None
+            }
+            Err(e) => {
+                push_rust_log(
+                    Some(&app),
+                    "warn",
+                    format!(
+                        "No SHA-256 was supplied and the release's could not be looked up ({e}); skipping verification"
+                    ),
+                );
+                None
+            }
+        },
+    };
+    if let Some(expected) = expected_sha256 {
+        let actual: String = Sha256::digest(&bytes)
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        if actual != expected {
+            return Err(format!(
+                "The downloaded update is corrupt or incomplete (SHA-256 {actual}, expected {expected}). Please try again."
+            ));
+        }
+    }
 
     // 4. Save and extract the archive
-    let archive_name = download_url
-        .split('/')
-        .next_back()
-        .unwrap_or("update.zip")
-        .to_string();
+    let archive_name = update_archive_name(&download_url);
     let archive_path = tmp_dir.join(&archive_name);
     {
         let mut f = std::fs::File::create(&archive_path).map_err(|e| e.to_string())?;
@@ -1897,24 +4502,9 @@ async fn apply_update(app: AppHandle, download_url: String) -> Result<(), String
         let f = std::fs::File::open(&archive_path).map_err(|e| e.to_string())?;
         let mut archive = zip::ZipArchive::new(f).map_err(|e| e.to_string())?;
 
-        // Detect whether the zip has a single top-level directory wrapper
-        // (common pattern: "libmaly-1.2.0/libmaly.exe") and unwrap it.
-        let strip_prefix: Option<String> = {
-            let mut dirs = std::collections::HashSet::new();
-            for i in 0..archive.len() {
-                let entry = archive.by_index(i).map_err(|e| e.to_string())?;
-                if let Some(first) = entry.name().split('/').next() {
-                    if !first.is_empty() {
-                        dirs.insert(first.to_string());
-                    }
-                }
-            }
-            if dirs.len() == 1 {
-                dirs.into_iter().next()
-            } else {
-                None
-            }
-        };
+        // Unwrap a single top-level directory ("libmaly-1.2.0/libmaly.exe").
+        let strip_prefix = updater::zip_wrapper_dir(&updater::zip_entries(&mut archive)?)
+            .map(|w| w.to_string_lossy().into_owned());
 
         let f2 = std::fs::File::open(&archive_path).map_err(|e| e.to_string())?;
         let mut archive2 = zip::ZipArchive::new(f2).map_err(|e| e.to_string())?;
@@ -1957,10 +4547,20 @@ async fn apply_update(app: AppHandle, download_url: String) -> Result<(), String
         {
             return Err("Cannot run Windows installer on this OS.".to_string());
         }
+    } else if updater::is_supported_archive(&archive_path) {
+        // tar.gz / 7z: extract next to the archive, then move the (unwrapped)
+        // contents up so the script copies the same layout as for zips.
+        let staging = tmp_dir.join(".extract");
+        updater::extract_archive(&archive_path, &staging)?;
+        let root = updater::unwrap_single_dir(&staging);
+        for entry in std::fs::read_dir(&root).map_err(|e| e.to_string())? {
+            let entry = entry.map_err(|e| e.to_string())?;
+            std::fs::rename(entry.path(), tmp_dir.join(entry.file_name()))
+                .map_err(|e| e.to_string())?;
+        }
+        std::fs::remove_dir_all(&staging).map_err(|e| e.to_string())?;
+        std::fs::remove_file(&archive_path).map_err(|e| e.to_string())?;
     } else {
-        // For non-zip archives (tar.gz etc.) just leave the archive in tmp_dir;
-        // the script will deal with them or the user can update manually.
-        // For now we return an error suggesting manual install.
         return Err(format!(
             "Archive format not supported for auto-update: {}. Please install manually from the release page.",
             archive_name
@@ -2040,10 +4640,17 @@ async fn apply_update(app: AppHandle, download_url: String) -> Result<(), String
     Ok(())
 }
 
-/// Build the tray context-menu from a list of recent games.
+/// Tray submenus are capped so a large library cannot produce a menu that
+/// runs off the screen.
+const TRAY_MAX_COLLECTIONS: usize = 10;
+const TRAY_GAMES_PER_COLLECTION: usize = 15;
+
+/// Build the tray context-menu from a list of recent games, followed by one
+/// submenu per non-empty collection.
 fn build_tray_menu(
     app: &AppHandle,
     recent: &[RecentGame],
+    collections: &[TrayCollection],
 ) -> tauri::Result<tauri::menu::Menu<tauri::Wry>> {
     let title = MenuItemBuilder::with_id("_title", "LIBMALY")
         .enabled(false)
@@ -2069,6 +4676,41 @@ fn build_tray_menu(
         }
     }
 
+    let shown: Vec<(usize, &TrayCollection)> = collections
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| !c.games.is_empty())
+        .take(TRAY_MAX_COLLECTIONS)
+        .collect();
+    if !shown.is_empty() {
+        builder = builder.item(&PredefinedMenuItem::separator(app)?);
+    }
+    for (ci, collection) in shown {
+        let mut sub = SubmenuBuilder::with_id(app, format!("_col_{ci}"), &collection.name);
+        for (gi, game) in collection
+            .games
+            .iter()
+            .take(TRAY_GAMES_PER_COLLECTION)
+            .enumerate()
+        {
+            let item =
+                MenuItemBuilder::with_id(format!("collection_{ci}_{gi}"), &game.name).build(app)?;
+            sub = sub.item(&item);
+        }
+        let hidden = collection
+            .games
+            .len()
+            .saturating_sub(TRAY_GAMES_PER_COLLECTION);
+        if hidden > 0 {
+            let more =
+                MenuItemBuilder::with_id(format!("_col_{ci}_more"), format!("… {hidden} more"))
+                    .enabled(false)
+                    .build(app)?;
+            sub = sub.item(&more);
+        }
+        builder = builder.item(&sub.build()?);
+    }
+
     builder
         .item(&sep2)
         .item(&show)
@@ -2077,10 +4719,17 @@ fn build_tray_menu(
         .build()
 }
 
-/// Update the tray menu with a new list of recent games.
-fn refresh_tray(app: &AppHandle, recent: &[RecentGame]) {
+/// Rebuild the tray menu from the stored recent games and collections.
+fn refresh_tray(app: &AppHandle) {
+    let recent = app.state::<RecentGamesState>().0.lock().unwrap().clone();
+    let collections = app
+        .state::<TrayCollectionsState>()
+        .0
+        .lock()
+        .unwrap()
+        .clone();
     if let Some(tray) = app.tray_by_id("main-tray") {
-        if let Ok(menu) = build_tray_menu(app, recent) {
+        if let Ok(menu) = build_tray_menu(app, &recent, &collections) {
             let _ = tray.set_menu(Some(menu));
         }
     }
@@ -2089,11 +4738,53 @@ fn refresh_tray(app: &AppHandle, recent: &[RecentGame]) {
 /// Called by the frontend whenever the last-5 list changes.
 #[tauri::command]
 fn set_recent_games(app: AppHandle, games: Vec<RecentGame>) -> Result<(), String> {
-    *app.state::<RecentGamesState>().0.lock().unwrap() = games.clone();
-    refresh_tray(&app, &games);
+    *app.state::<RecentGamesState>().0.lock().unwrap() = games;
+    refresh_tray(&app);
+    Ok(())
+}
+
+/// Called by the frontend whenever its collections change; each becomes a
+/// tray submenu.
+#[tauri::command]
+fn set_tray_collections(app: AppHandle, collections: Vec<TrayCollection>) -> Result<(), String> {
+    *app.state::<TrayCollectionsState>().0.lock().unwrap() = collections;
+    refresh_tray(&app);
     Ok(())
 }
 
+/// Game path behind a tray item: `recent_<i>` or
+/// `collection_<collection>_<game>`.
+fn tray_game_path(app: &AppHandle, id: &str) -> Option<String> {
+    if let Some(idx) = id.strip_prefix("recent_") {
+        let idx: usize = idx.parse().ok()?;
+        let games = app.state::<RecentGamesState>().0.lock().unwrap().clone();
+        return games.get(idx).map(|g| g.path.clone());
+    }
+    let (ci, gi) = id.strip_prefix("collection_")?.split_once('_')?;
+    let (ci, gi): (usize, usize) = (ci.parse().ok()?, gi.parse().ok()?);
+    let collections = app
+        .state::<TrayCollectionsState>()
+        .0
+        .lock()
+        .unwrap()
+        .clone();
+    collections.get(ci)?.games.get(gi).map(|g| g.path.clone())
+}
+
+/// Quick-launches the game behind a tray item and brings the window up.
+fn launch_from_tray(app: &AppHandle, id: &str) {
+    if let Some(path) = tray_game_path(app, id) {
+        let app2 = app.clone();
+        thread::spawn(move || {
+            let _ = launch_game(
+                app2, path, None, None, None, None, None, None, None, None, None, None, None, None,
+            );
+        });
+    }
+    // Bring window to front when launching from tray
+    let _ = apply_window_visibility(app, "show");
+}
+
 /// Deletes the parent folder of the given .exe path.
 #[tauri::command]
 fn delete_game(path: String) -> Result<(), String> {
@@ -2105,6 +4796,246 @@ fn delete_game(path: String) -> Result<(), String> {
         .map_err(|e| format!("Failed to delete '{}': {}", parent.display(), e))
 }
 
+// ── Game fingerprint ───────────────────────────────────────────────────────
+
+/// Bytes hashed from each end of a file; large archives are only sampled.
+const FINGERPRINT_SAMPLE_BYTES: u64 = 64 * 1024;
+
+/// Engine data files (relative to the exe's folder) that identify a build
+/// better than the exe alone, which is often a stock engine binary.
+const FINGERPRINT_SIBLINGS: &[&str] = &[
+    "data.xp3",
+    "game.rpa",
+    "game/archive.rpa",
+    "game/scripts.rpa",
+    "Game.rgssad",
+    "Game.rgss2a",
+    "Game.rgss3a",
+    "data.win",
+    "package.nw",
+    "www/data/System.json",
+];
+
+/// Feeds size plus head/tail samples of `path` into `hasher`.
+fn hash_file_sample(hasher: &mut Sha256, path: &Path) -> std::io::Result<()> {
+    use std::io::{Read, Seek, SeekFrom};
+    let mut file = std::fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    hasher.update(len.to_le_bytes());
+    let mut buf = vec![0u8; FINGERPRINT_SAMPLE_BYTES.min(len) as usize];
+    file.read_exact(&mut buf)?;
+    hasher.update(&buf);
+    if len > FINGERPRINT_SAMPLE_BYTES * 2 {
+        file.seek(SeekFrom::End(-(FINGERPRINT_SAMPLE_BYTES as i64)))?;
+        file.read_exact(&mut buf)?;
+        hasher.update(&buf);
+    }
+    Ok(())
+}
+
+/// Stable identity for an install that survives moves and renames: a hash
+/// over the exe and known engine archives (size + sampled content), not
+/// their paths. Changes when the game files are updated.
+#[tauri::command]
+fn fingerprint_game(game_path: String) -> Result<String, String> {
+    let exe = Path::new(&game_path);
+    let dir = exe
+        .parent()
+        .ok_or_else(|| "Cannot determine game directory".to_string())?;
+    let mut hasher = Sha256::new();
+    hash_file_sample(&mut hasher, exe).map_err(|e| format!("Failed to read exe: {e}"))?;
+    for rel in FINGERPRINT_SIBLINGS {
+        let p = dir.join(rel);
+        if p.is_file() {
+            hasher.update(rel.as_bytes());
+            hash_file_sample(&mut hasher, &p).map_err(|e| e.to_string())?;
+        }
+    }
+    let digest = hasher.finalize();
+    let hex: String = digest[..16].iter().map(|b| format!("{b:02x}")).collect();
+    Ok(format!("fp1-{hex}"))
+}
+
+// ── Exe version resource ───────────────────────────────────────────────────
+
+#[derive(Serialize, Clone, Default)]
+struct ExeVersionInfo {
+    file_version: Option<String>,
+    product_version: Option<String>,
+    /// Often the developer/circle name; useful as a hint when metadata is offline.
+    company_name: Option<String>,
+}
+
+#[cfg(windows)]
+fn read_exe_version_resource(exe_path: &str) -> Option<ExeVersionInfo> {
+    use std::os::windows::ffi::OsStrExt;
+    use winapi::shared::minwindef::{LPVOID, UINT};
+    use winapi::um::winver::{GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW};
+
+    let wide = |s: &std::ffi::OsStr| -> Vec<u16> { s.encode_wide().chain(Some(0)).collect() };
+    let path_w = wide(std::ffi::OsStr::new(exe_path));
+    let size = unsafe { GetFileVersionInfoSizeW(path_w.as_ptr(), std::ptr::null_mut()) };
+    if size == 0 {
+        return None;
+    }
+    let mut block = vec![0u8; size as usize];
+    let ok = unsafe { GetFileVersionInfoW(path_w.as_ptr(), 0, size, block.as_mut_ptr() as LPVOID) };
+    if ok == 0 {
+        return None;
+    }
+
+    // Returns a pointer/length pair into `block` for the given sub-block.
+    let query = |sub: &str| -> Option<(LPVOID, UINT)> {
+        let sub_w = wide(std::ffi::OsStr::new(sub));
+        let mut ptr: LPVOID = std::ptr::null_mut();
+        let mut len: UINT = 0;
+        let ok =
+            unsafe { VerQueryValueW(block.as_ptr() as LPVOID, sub_w.as_ptr(), &mut ptr, &mut len) };
+        (ok != 0 && !ptr.is_null() && len > 0).then_some((ptr, len))
+    };
+
+    // Language/codepage pairs the string tables are keyed by; fall back to
+    // the usual US-English tables when the translation list is missing.
+    let mut tables: Vec<String> = Vec::new();
+    if let Some((ptr, len)) = query("\\VarFileInfo\\Translation") {
+        let pairs = unsafe { std::slice::from_raw_parts(ptr as *const u16, len as usize / 2) };
+        for pair in pairs.chunks_exact(2) {
+            tables.push(format!("{:04x}{:04x}", pair[0], pair[1]));
+        }
+    }
+    tables.extend(["040904b0".to_string(), "040904e4".to_string()]);
+
+    let string_value = |name: &str| -> Option<String> {
+        tables.iter().find_map(|table| {
+            let (ptr, len) = query(&format!("\\StringFileInfo\\{table}\\{name}"))?;
+            let chars = unsafe { std::slice::from_raw_parts(ptr as *const u16, len as usize) };
+            let value = String::from_utf16_lossy(chars)
+                .trim_end_matches('\0')
+                .trim()
+                .to_string();
+            (!value.is_empty()).then_some(value)
+        })
+    };
+
+    let info = ExeVersionInfo {
+        file_version: string_value("FileVersion"),
+        product_version: string_value("ProductVersion"),
+        company_name: string_value("CompanyName"),
+    };
+    if info.file_version.is_none() && info.product_version.is_none() && info.company_name.is_none()
+    {
+        return None;
+    }
+    Some(info)
+}
+
+/// Reads FileVersion/ProductVersion/CompanyName from a Windows exe's version
+/// resource. The frontend uses it as an offline fallback for the version and
+/// developer fields. Always `None` on other platforms.
+#[tauri::command]
+fn get_exe_version(exe_path: String) -> Option<ExeVersionInfo> {
+    #[cfg(windows)]
+    {
+        read_exe_version_resource(&exe_path)
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = exe_path;
+        None
+    }
+}
+
+// ── Game config files ──────────────────────────────────────────────────────
+
+/// Config files larger than this are assumed not to be hand-editable text.
+const MAX_GAME_CONFIG_BYTES: u64 = 512 * 1024;
+
+/// Canonical paths handed out by `read_game_config`; `write_game_config`
+/// only writes to these so the editor cannot be pointed at arbitrary files.
+static GAME_CONFIG_ALLOWLIST: Mutex<Option<HashSet<PathBuf>>> = Mutex::new(None);
+
+#[derive(Serialize, Clone)]
+struct GameConfigFile {
+    path: String,
+    name: String,
+    contents: String,
+}
+
+fn is_game_config_name(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    lower.ends_with(".ini") || lower == "settings.cfg" || lower == "options.rpy"
+}
+
+/// Finds editable config files next to the game exe (and in Ren'Py's
+/// `game/` folder), e.g. `config.ini`, `Game.ini`, `settings.cfg`.
+#[tauri::command]
+fn read_game_config(game_path: String) -> Result<Vec<GameConfigFile>, String> {
+    let game_dir = Path::new(&game_path)
+        .parent()
+        .ok_or_else(|| "Cannot determine game directory".to_string())?;
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    for dir in [game_dir.to_path_buf(), game_dir.join("game")] {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let p = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if p.is_file() && is_game_config_name(&name) {
+                candidates.push(p);
+            }
+        }
+    }
+    candidates.sort();
+
+    let mut out = Vec::new();
+    let mut allow = GAME_CONFIG_ALLOWLIST.lock().unwrap();
+    let allow = allow.get_or_insert_with(HashSet::new);
+    for p in candidates {
+        let too_big = p
+            .metadata()
+            .map(|m| m.len() > MAX_GAME_CONFIG_BYTES)
+            .unwrap_or(true);
+        if too_big {
+            continue;
+        }
+        // Skip binary files that happen to use a config extension.
+        let Ok(contents) = std::fs::read_to_string(&p) else {
+            continue;
+        };
+        if let Ok(canon) = p.canonicalize() {
+            allow.insert(canon);
+        }
+        out.push(GameConfigFile {
+            name: p
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            path: p.to_string_lossy().to_string(),
+            contents,
+        });
+    }
+    Ok(out)
+}
+
+/// Saves a config file previously returned by `read_game_config`.
+#[tauri::command]
+fn write_game_config(path: String, contents: String) -> Result<(), String> {
+    let canon = Path::new(&path)
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve '{path}': {e}"))?;
+    let allowed = GAME_CONFIG_ALLOWLIST
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|set| set.contains(&canon))
+        .unwrap_or(false);
+    if !allowed {
+        return Err("Only config files found by read_game_config can be written".to_string());
+    }
+    std::fs::write(&canon, contents).map_err(|e| e.to_string())
+}
+
 /// Lists every executable file (.exe / .sh / .bin / .app) directly inside
 /// `folder` (non-recursive). Returns full paths. No file-size or block-list
 /// filters — the user is explicitly choosing so we show everything.
@@ -2203,7 +5134,7 @@ fn import_steam_playtime() -> Vec<SteamEntry> {
         }
     });
     // Sort by playtime descending for convenience
-    results.sort_by(|a, b| b.played_minutes.cmp(&a.played_minutes));
+    results.sort_by_key(|r| std::cmp::Reverse(r.played_minutes));
     results
 }
 
@@ -2253,10 +5184,8 @@ fn parse_localconfig_vdf(src: &str, out: &mut Vec<SteamEntry>) {
             if depth == 1 {
                 if let Some((k, v)) = kv_pair(t) {
                     match k.to_lowercase().as_str() {
-                        "name" => {
-                            if name.is_empty() {
-                                name = v.to_string();
-                            }
+                        "name" if name.is_empty() => {
+                            name = v.to_string();
                         }
                         "playtime_forever" => {
                             playtime = v.parse().unwrap_or(0);
@@ -2359,6 +5288,153 @@ fn clear_last_crash_report(app: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+#[tauri::command]
+fn get_crash_report_path(app: AppHandle) -> String {
+    crash_report_path(&app, CRASH_REPORT_FILE)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Opens the crash report in the system's default JSON viewer/editor.
+#[tauri::command]
+fn open_crash_report(app: AppHandle) -> Result<(), String> {
+    use tauri_plugin_opener::OpenerExt;
+    let path = crash_report_path(&app, CRASH_REPORT_FILE);
+    if !path.exists() {
+        return Err("No crash report has been recorded.".to_string());
+    }
+    app.opener()
+        .open_path(path.to_string_lossy(), None::<&str>)
+        .map_err(|e| e.to_string())
+}
+
+// ── Diagnostics ────────────────────────────────────────────────────────────
+
+#[derive(Serialize)]
+struct DataFileStatus {
+    file: String,
+    exists: bool,
+    /// `false` when the file exists but is not valid JSON.
+    parses: bool,
+}
+
+#[derive(Serialize)]
+struct Diagnostics {
+    app_version: String,
+    platform: String,
+    os_version: Option<String>,
+    portable: bool,
+    data_root: String,
+    /// Free bytes on the drive holding the data root, when it can be read.
+    free_disk_bytes: Option<u64>,
+    screenshot_tools: Vec<String>,
+    wine_runners: usize,
+    data_files: Vec<DataFileStatus>,
+    /// Site id -> logged in.
+    login_status: HashMap<String, bool>,
+}
+
+#[cfg(windows)]
+fn free_disk_space(path: &Path) -> Option<u64> {
+    use std::os::windows::ffi::OsStrExt;
+    use winapi::shared::ntdef::ULARGE_INTEGER;
+    use winapi::um::fileapi::GetDiskFreeSpaceExW;
+
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut free: ULARGE_INTEGER = unsafe { std::mem::zeroed() };
+    let ok = unsafe {
+        GetDiskFreeSpaceExW(
+            wide.as_ptr(),
+            &mut free,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    if ok == 0 {
+        return None;
+    }
+    Some(unsafe { *free.QuadPart() })
+}
+
+#[cfg(not(windows))]
+fn free_disk_space(path: &Path) -> Option<u64> {
+    // POSIX output: filesystem, 1024-blocks, used, available, capacity, mount.
+    let out = Command::new("df").arg("-Pk").arg(path).output().ok()?;
+    let text = String::from_utf8_lossy(&out.stdout);
+    let available: u64 = text
+        .lines()
+        .nth(1)?
+        .split_whitespace()
+        .nth(3)?
+        .parse()
+        .ok()?;
+    Some(available * 1024)
+}
+
+#[cfg(windows)]
+fn os_version() -> Option<String> {
+    let out = Command::new("cmd").args(["/C", "ver"]).output().ok()?;
+    let v = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    (!v.is_empty()).then_some(v)
+}
+
+#[cfg(not(windows))]
+fn os_version() -> Option<String> {
+    let out = Command::new("uname").args(["-sr"]).output().ok()?;
+    let v = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    (!v.is_empty()).then_some(v)
+}
+
+fn data_file_status(path: &Path) -> DataFileStatus {
+    let exists = path.exists();
+    let parses = !exists
+        || std::fs::read_to_string(path)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+            .is_some();
+    DataFileStatus {
+        file: path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        exists,
+        parses,
+    }
+}
+
+/// Everything a bug report needs, gathered in one call.
+#[tauri::command]
+async fn get_diagnostics() -> Result<Diagnostics, String> {
+    let root = app_data_root();
+    // The data root may not exist yet on a fresh install; measure its drive.
+    let free_disk_bytes = root
+        .ancestors()
+        .find(|p| p.exists())
+        .and_then(free_disk_space);
+
+    let mut data_files: Vec<PathBuf> = vec![
+        settings::settings_path(),
+        metadata::suggest_cache_path(),
+        root.join(PORTABLE_STORAGE_FILE),
+    ];
+    data_files.extend(metadata::SITE_SESSIONS.iter().map(|s| s.cookies_path()));
+
+    let login_status = get_all_login_status().await.unwrap_or_default();
+
+    Ok(Diagnostics {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        platform: get_platform().to_string(),
+        os_version: os_version(),
+        portable: is_portable_mode(),
+        data_root: root.to_string_lossy().into_owned(),
+        free_disk_bytes,
+        screenshot_tools: screenshot::screenshot_tools(),
+        wine_runners: detect_wine_runners().len(),
+        data_files: data_files.iter().map(|p| data_file_status(p)).collect(),
+        login_status,
+    })
+}
+
 #[derive(Serialize)]
 struct StorageBootstrap {
     portable: bool,
@@ -2367,9 +5443,15 @@ struct StorageBootstrap {
 
 const PORTABLE_STORAGE_FILE: &str = "portable_storage.json";
 
+/// Frontend storage goes to `PORTABLE_STORAGE_FILE` in portable mode and in
+/// every non-default profile; `StorageBootstrap::portable` reports this.
+fn uses_file_storage() -> bool {
+    is_portable_mode() || data_paths::active_profile().is_some()
+}
+
 #[tauri::command]
 fn get_storage_bootstrap() -> Result<StorageBootstrap, String> {
-    if !is_portable_mode() {
+    if !uses_file_storage() {
         return Ok(StorageBootstrap {
             portable: false,
             entries: HashMap::new(),
@@ -2385,7 +5467,7 @@ fn get_storage_bootstrap() -> Result<StorageBootstrap, String> {
     }
 
     let raw = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
-    let entries: HashMap<String, String> = serde_json::from_str(&raw).unwrap_or_default();
+    let entries: HashMap<String, String> = read_store(&raw).unwrap_or_default();
     Ok(StorageBootstrap {
         portable: true,
         entries,
@@ -2394,19 +5476,234 @@ fn get_storage_bootstrap() -> Result<StorageBootstrap, String> {
 
 #[tauri::command]
 fn persist_storage_snapshot(entries: HashMap<String, String>) -> Result<(), String> {
-    if !is_portable_mode() {
+    if !uses_file_storage() {
         return Ok(());
     }
     let dir = app_data_root();
     std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
     let path = dir.join(PORTABLE_STORAGE_FILE);
-    let raw = serde_json::to_string(&entries).map_err(|e| e.to_string())?;
+    let raw = write_store(&entries, false)?;
     std::fs::write(path, raw).map_err(|e| e.to_string())
 }
 
+/// Shows, hides, toggles or focuses the main window. Shared by the tray and
+/// `set_window_visibility`.
+fn apply_window_visibility(app: &AppHandle, action: &str) -> Result<(), String> {
+    let w = app
+        .get_webview_window("main")
+        .ok_or_else(|| "Main window not found".to_string())?;
+    let show = |w: &tauri::WebviewWindow| -> Result<(), String> {
+        w.show().map_err(|e| e.to_string())?;
+        let _ = w.unminimize();
+        w.set_focus().map_err(|e| e.to_string())
+    };
+    match action {
+        "show" | "focus" => show(&w),
+        "hide" => w.hide().map_err(|e| e.to_string()),
+        "toggle" => {
+            if w.is_visible().unwrap_or(false) {
+                w.hide().map_err(|e| e.to_string())
+            } else {
+                show(&w)
+            }
+        }
+        other => Err(format!("Unknown window action: {}", other)),
+    }
+}
+
+/// Size the main window is reset to, matching `tauri.conf.json`.
+const DEFAULT_WINDOW_SIZE: (f64, f64) = (1280.0, 800.0);
+
+/// `false` when the window rect does not overlap any connected monitor, e.g.
+/// after the display it was on was unplugged.
+fn window_on_screen(w: &tauri::WebviewWindow) -> bool {
+    let (pos, size) = match (w.outer_position(), w.outer_size()) {
+        (Ok(p), Ok(s)) => (p, s),
+        _ => return true,
+    };
+    let monitors = match w.available_monitors() {
+        Ok(m) if !m.is_empty() => m,
+        // Nothing to compare against; assume it is fine.
+        _ => return true,
+    };
+    let (left, top) = (pos.x as i64, pos.y as i64);
+    let (right, bottom) = (left + size.width as i64, top + size.height as i64);
+    monitors.iter().any(|m| {
+        let mp = m.position();
+        let ms = m.size();
+        let (m_left, m_top) = (mp.x as i64, mp.y as i64);
+        let (m_right, m_bottom) = (m_left + ms.width as i64, m_top + ms.height as i64);
+        left < m_right && right > m_left && top < m_bottom && bottom > m_top
+    })
+}
+
+/// Moves the main window to the primary monitor at its default size, centred.
+fn reset_main_window(w: &tauri::WebviewWindow) -> Result<(), String> {
+    if w.is_maximized().unwrap_or(false) {
+        w.unmaximize().map_err(|e| e.to_string())?;
+    }
+    let (mut width, mut height) = DEFAULT_WINDOW_SIZE;
+    if let Ok(Some(m)) = w.primary_monitor() {
+        // Keep the window inside small screens.
+        let scale = m.scale_factor();
+        width = width.min(m.size().width as f64 / scale * 0.9);
+        height = height.min(m.size().height as f64 / scale * 0.9);
+        let p = m.position();
+        w.set_position(tauri::PhysicalPosition::new(p.x, p.y))
+            .map_err(|e| e.to_string())?;
+    }
+    w.set_size(tauri::LogicalSize::new(width, height))
+        .map_err(|e| e.to_string())?;
+    w.center().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn reset_window_position(app: AppHandle) -> Result<(), String> {
+    let w = app
+        .get_webview_window("main")
+        .ok_or_else(|| "Main window not found".to_string())?;
+    reset_main_window(&w)?;
+    apply_window_visibility(&app, "show")
+}
+
+#[tauri::command]
+fn set_window_visibility(app: AppHandle, action: String) -> Result<(), String> {
+    apply_window_visibility(&app, &action)
+}
+
+// ── Deep links ─────────────────────────────────────────────────────────────
+// `libmaly://launch?path=<exe>` starts a game and `libmaly://add?path=<exe>
+// &name=<title>` asks the frontend to add one, so browser extensions and
+// other tools can hand games to the launcher.
+
+#[derive(Serialize, Clone)]
+struct DeepLinkAdd {
+    path: String,
+    name: Option<String>,
+}
+
+/// Whether `path` is a game the user already has: in the saved library
+/// snapshot or with recorded playtime.
+fn is_library_game(path: &str) -> bool {
+    let key = path_dedup_key(path);
+    library_cache::load_snapshot()
+        .is_some_and(|s| s.games.iter().any(|g| path_dedup_key(&g.path) == key))
+        || get_playtime(None)
+            .games
+            .keys()
+            .any(|p| path_dedup_key(p) == key)
+}
+
+/// Asks before a deep link launches something that is not in the library,
+/// since any web page can open a `libmaly://` link.
+fn confirm_deep_link_launch(app: &AppHandle, path: &str) -> bool {
+    use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+    app.dialog()
+        .message(format!(
+            "A link asked LIBMALY to run a program that is not in your library:\n\n{path}\n\nOnly continue if you opened this link yourself."
+        ))
+        .title("Launch from link")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "Launch".to_string(),
+            "Cancel".to_string(),
+        ))
+        .blocking_show()
+}
+
+fn handle_deep_link(app: &AppHandle, url: &reqwest::Url) {
+    let warn = |reason: &str| {
+        push_rust_log(
+            Some(app),
+            "warn",
+            format!("Ignoring deep link {url}: {reason}"),
+        )
+    };
+    if url.scheme() != "libmaly" {
+        return warn("unknown scheme");
+    }
+    // `libmaly://launch?...` carries the action as the host; some platforms
+    // hand over `libmaly:launch?...` with it in the path instead.
+    let action = url
+        .host_str()
+        .unwrap_or_else(|| url.path().trim_matches('/'))
+        .to_lowercase();
+    let param = |key: &str| {
+        url.query_pairs()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let Some(path) = param("path") else {
+        return warn("missing path");
+    };
+    if !Path::new(&path).is_file() {
+        return warn("path does not exist");
+    }
+    match action.as_str() {
+        "launch" => {
+            let app2 = app.clone();
+            thread::spawn(move || {
+                if !is_library_game(&path) && !confirm_deep_link_launch(&app2, &path) {
+                    push_rust_log(
+                        Some(&app2),
+                        "warn",
+                        format!("Deep link launch of {path} was declined"),
+                    );
+                    return;
+                }
+                push_rust_log(
+                    Some(&app2),
+                    "info",
+                    format!("Launching {path} from a deep link"),
+                );
+                if let Err(e) = launch_game(
+                    app2.clone(),
+                    path,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                ) {
+                    push_rust_log(
+                        Some(&app2),
+                        "error",
+                        format!("Deep link launch failed: {e}"),
+                    );
+                }
+            });
+        }
+        "add" => {
+            let _ = app.emit(
+                "deep-link-add",
+                &DeepLinkAdd {
+                    path,
+                    name: param("name"),
+                },
+            );
+            let _ = apply_window_visibility(app, "show");
+        }
+        _ => warn("unknown action"),
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        // Must come first: a second launch (e.g. from a `libmaly://` link)
+        // hands its arguments to this instance, which passes deep links on
+        // to `on_open_url`, and then exits.
+        .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+            let _ = apply_window_visibility(app, "show");
+        }))
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_cli::init())
         .plugin(tauri_plugin_deep_link::init())
@@ -2414,38 +5711,81 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_autostart::init(
             tauri_plugin_autostart::MacosLauncher::LaunchAgent,
-            Some(vec!["--minimized"]),
+            Some(vec![autostart::MINIMIZED_ARG]),
         ))
         .plugin(tauri_plugin_notification::init())
         .manage(screenshot::ActiveGameState(std::sync::Mutex::new(None)))
         .manage(RecentGamesState(std::sync::Mutex::new(Vec::new())))
+        .manage(TrayCollectionsState(std::sync::Mutex::new(Vec::new())))
         .invoke_handler(tauri::generate_handler![
             scan_games,
             scan_games_incremental,
+            scan_multiple,
+            scan_multiple_incremental,
+            save_library_snapshot,
+            get_cached_library,
+            rescan,
+            scan_archives,
+            extract_game,
+            verify_file_hash,
+            check_rpgmaker_rtp,
+            set_game_engine,
+            get_known_fixes,
+            import_known_fixes,
+            apply_known_fix,
+            get_launch_profile,
+            detect_name_issues,
+            sort_games,
+            export_library_html,
             list_executables_in_folder,
+            read_game_config,
+            write_game_config,
+            get_exe_version,
+            fingerprint_game,
             get_platform,
             detect_wine_runners,
             list_wine_prefixes,
+            refresh_prefix,
+            rename_wine_prefix,
+            clone_wine_prefix,
+            get_prefix_dll_overrides,
             create_wine_prefix,
+            configure_wine_prefix,
             delete_wine_prefix,
             run_winetricks,
+            open_winecfg,
+            open_winetricks_gui,
+            list_installed_verbs,
             install_dxvk_vkd3d,
             import_lutris_games,
             import_playnite_games,
             import_gog_galaxy_games,
+            import_heroic_games,
+            import_itch_games,
+            import_epic_games,
             launch_game,
+            launch_game_debug,
+            get_proton_log,
             kill_game,
             delete_game,
             set_recent_games,
+            set_tray_collections,
             check_app_update,
             apply_update,
+            preview_app_update,
             fetch_f95_metadata,
+            fetch_f95_changelog,
             fetch_dlsite_metadata,
             fetch_vndb_metadata,
             fetch_mangagamer_metadata,
             fetch_johren_metadata,
             fetch_fakku_metadata,
+            fetch_itch_metadata,
+            fetch_steam_metadata,
+            fetch_protondb_rating,
+            clear_metadata_cache,
             search_suggest_links,
+            prefetch_metadata,
             f95_login,
             f95_logout,
             f95_is_logged_in,
@@ -2454,8 +5794,19 @@ pub fn run() {
             dlsite_is_logged_in,
             fakku_login,
             fakku_logout,
+            logout_all_sites,
+            get_all_login_status,
+            refresh_sessions,
+            import_f95_watched,
+            import_dlsite_purchases,
+            save_credentials,
+            clear_credentials,
+            has_credentials,
+            get_session_expiry,
+            test_scrapers,
             fakku_is_logged_in,
             update_game,
+            rollback_update,
             preview_update,
             get_screenshots,
             export_screenshots_zip,
@@ -2465,6 +5816,7 @@ pub fn run() {
             overwrite_screenshot_png,
             delete_screenshot_file,
             get_screenshot_data_url,
+            get_screenshot_thumbnail,
             backup_save_files,
             import_steam_playtime,
             set_tray_tooltip,
@@ -2473,14 +5825,84 @@ pub fn run() {
             read_string_from_file,
             get_recent_logs,
             clear_recent_logs,
+            open_logs_folder,
+            export_logs_zip,
             get_last_crash_report,
             clear_last_crash_report,
+            get_crash_report_path,
+            open_crash_report,
             get_storage_bootstrap,
             persist_storage_snapshot,
+            get_backend_settings,
+            set_backend_settings,
+            normalize_covers,
+            snapshot_data,
+            restore_data_snapshot,
+            get_diagnostics,
+            restore_save_files,
+            detect_save_dirs_preview,
+            set_window_visibility,
+            reset_window_position,
+            get_autostart,
+            set_autostart,
+            get_playtime,
+            reset_playtime,
+            list_profiles,
+            create_profile,
+            switch_profile,
+            delete_profile,
+            get_app_lock_status,
+            set_app_lock,
+            verify_app_lock,
+            unlock_session,
+            clear_app_lock,
         ])
         .setup(|app| {
             push_rust_log(Some(app.handle()), "info", "LIBMALY started");
 
+            // Rescue a window left on a monitor that is no longer connected.
+            if let Some(w) = app.get_webview_window("main") {
+                if !window_on_screen(&w) {
+                    push_rust_log(
+                        Some(app.handle()),
+                        "warn",
+                        "Main window was off-screen; moving it to the primary monitor",
+                    );
+                    let _ = reset_main_window(&w);
+                }
+            }
+
+            // Launched on login with `--minimized`: stay in the tray.
+            if std::env::args().any(|a| a == autostart::MINIMIZED_ARG) {
+                let _ = apply_window_visibility(app.handle(), "hide");
+            }
+
+            // Restore lapsed site logins so the first metadata fetch works.
+            if settings::current().refresh_sessions_on_startup {
+                let handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let _ = refresh_sessions(handle).await;
+                });
+            }
+
+            // Links opened while running, then any the app was started with.
+            {
+                use tauri_plugin_deep_link::DeepLinkExt;
+                let handle = app.handle().clone();
+                app.deep_link().on_open_url(move |event| {
+                    for url in event.urls() {
+                        handle_deep_link(&handle, &url);
+                    }
+                });
+                if let Ok(Some(urls)) = app.deep_link().get_current() {
+                    for url in urls {
+                        handle_deep_link(app.handle(), &url);
+                    }
+                }
+            }
+
+            library_cache::start_scheduler(app.handle().clone());
+
             // Capture panics into a persisted crash report file and in-app log stream.
             let app_for_panic = app.handle().clone();
             std::panic::set_hook(Box::new(move |panic_info| {
@@ -2508,10 +5930,23 @@ pub fn run() {
             }));
 
             // ── System tray ───────────────────────────────────────────────
-            let initial_menu = build_tray_menu(app.handle(), &[])?;
+            let initial_menu = build_tray_menu(app.handle(), &[], &[])?;
+            // A packaging mistake that drops the bundled icon should not take
+            // the app down; fall back to the icon embedded at build time.
+            let tray_icon = match app.default_window_icon() {
+                Some(icon) => icon.clone(),
+                None => {
+                    push_rust_log(
+                        Some(app.handle()),
+                        "warn",
+                        "Default window icon is missing; using the embedded tray icon",
+                    );
+                    tauri::include_image!("icons/32x32.png")
+                }
+            };
             #[allow(unused_mut)]
             let mut tray_builder = TrayIconBuilder::with_id("main-tray")
-                .icon(app.default_window_icon().unwrap().clone())
+                .icon(tray_icon)
                 .tooltip("LIBMALY")
                 .menu(&initial_menu)
                 .show_menu_on_left_click(false)
@@ -2519,30 +5954,12 @@ pub fn run() {
                     let id = event.id.as_ref();
                     match id {
                         "show" => {
-                            if let Some(w) = app.get_webview_window("main") {
-                                let _ = w.show();
-                                let _ = w.set_focus();
-                            }
+                            let _ = apply_window_visibility(app, "show");
                         }
                         "quit" => app.exit(0),
-                        _ if id.starts_with("recent_") => {
+                        _ if id.starts_with("recent_") || id.starts_with("collection_") => {
                             // Quick-launch game from tray
-                            if let Ok(idx) = id["recent_".len()..].parse::<usize>() {
-                                let games =
-                                    app.state::<RecentGamesState>().0.lock().unwrap().clone();
-                                if let Some(game) = games.get(idx) {
-                                    let path = game.path.clone();
-                                    let app2 = app.clone();
-                                    thread::spawn(move || {
-                                        let _ = launch_game(app2, path, None, None, None, None);
-                                    });
-                                }
-                            }
-                            // Bring window to front when launching from tray
-                            if let Some(w) = app.get_webview_window("main") {
-                                let _ = w.show();
-                                let _ = w.set_focus();
-                            }
+                            launch_from_tray(app, id);
                         }
                         _ => {}
                     }
@@ -2556,15 +5973,7 @@ pub fn run() {
                     } = event
                     {
                         if button == MouseButton::Left && button_state == MouseButtonState::Up {
-                            let app = tray.app_handle();
-                            if let Some(w) = app.get_webview_window("main") {
-                                if w.is_visible().unwrap_or(false) {
-                                    let _ = w.hide();
-                                } else {
-                                    let _ = w.show();
-                                    let _ = w.set_focus();
-                                }
-                            }
+                            let _ = apply_window_visibility(tray.app_handle(), "toggle");
                         }
                     }
                 });
@@ -2582,10 +5991,83 @@ pub fn run() {
         // ── Minimize to tray instead of closing ───────────────────────────
         .on_window_event(|window, event| {
             if let tauri::WindowEvent::CloseRequested { api, .. } = event {
-                api.prevent_close();
-                let _ = window.hide();
+                match settings::current().close_behavior.as_str() {
+                    "quit" => window.app_handle().exit(0),
+                    "ask" => {
+                        api.prevent_close();
+                        let _ = window.emit("confirm-close", ());
+                    }
+                    _ => {
+                        api.prevent_close();
+                        let _ = window.hide();
+                    }
+                }
             }
         })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_exe(root: &Path, rel: &str) {
+        let path = root.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        // Above the 100 KiB floor that weeds out stub exes.
+        std::fs::write(path, vec![0u8; 128 * 1024]).unwrap();
+    }
+
+    #[test]
+    fn parallel_scan_matches_sequential_walk() {
+        let root = std::env::temp_dir().join(format!("libmaly-scan-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        write_exe(&root, "Alpha/Alpha.exe");
+        write_exe(&root, "Beta/bin/Beta.exe");
+        write_exe(&root, "Beta/bin/extras/Gamma.exe");
+        write_exe(&root, "Delta/Delta.exe");
+        std::fs::create_dir_all(root.join("Empty/Nested")).unwrap();
+        std::fs::create_dir_all(root.join("Delta/saves")).unwrap();
+
+        let (games, mtimes) = scan_games(root.to_string_lossy().into_owned(), None).unwrap();
+
+        // What the sequential scan produced: one shallow scan per directory.
+        let mut expected_dirs: Vec<String> = Vec::new();
+        let mut expected_games: Vec<String> = Vec::new();
+        for entry in WalkDir::new(&root).into_iter().filter_map(|e| e.ok()) {
+            if entry.file_type().is_dir() {
+                expected_dirs.push(entry.path().to_string_lossy().into_owned());
+                expected_games.extend(
+                    scan_dir_shallow(entry.path(), &ScanOptions::default())
+                        .into_iter()
+                        .map(|g| g.path),
+                );
+            }
+        }
+        expected_dirs.sort();
+        expected_games.sort();
+
+        let mut found: Vec<String> = games.into_iter().map(|g| g.path).collect();
+        found.sort();
+        assert_eq!(found, expected_games);
+        assert_eq!(found.len(), 4);
+
+        let mut dirs: Vec<String> = mtimes.iter().map(|d| d.path.clone()).collect();
+        dirs.sort();
+        assert_eq!(dirs, expected_dirs);
+        for dir in ["", "Empty", "Empty/Nested", "Delta/saves"] {
+            let path = root.join(dir);
+            let path = path.to_string_lossy();
+            let path = path.trim_end_matches(['/', '\\']);
+            assert!(
+                mtimes
+                    .iter()
+                    .any(|d| d.path.trim_end_matches(['/', '\\']) == path && d.mtime > 0),
+                "no mtime for {path}"
+            );
+        }
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
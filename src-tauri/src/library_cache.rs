@@ -0,0 +1,200 @@
+use crate::data_paths::{app_data_root, read_store, write_store_atomic};
+use crate::{path_dedup_key, scan_multiple_incremental, DirMtime, Game, ScanOptions};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+// ── Library snapshot & scheduled scans ─────────────────────────────────────
+// The last scan result (roots, options, games and directory mtimes) is kept
+// on disk so incremental scans survive restarts and lost frontend storage.
+// With the `scheduled_scan` setting on, a background thread rescans those
+// roots every `scheduled_scan_interval_mins` and emits `library-updated` with
+// the games that appeared or disappeared, so download folders stay fresh
+// without a filesystem watcher.
+
+const LIBRARY_SNAPSHOT_FILE: &str = "library_snapshot.json";
+
+/// How often the scheduler checks whether a scan is due.
+const SCHEDULER_TICK: Duration = Duration::from_secs(30);
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct LibrarySnapshot {
+    pub roots: Vec<String>,
+    pub options: ScanOptions,
+    pub games: Vec<Game>,
+    pub mtimes: Vec<DirMtime>,
+    /// When the snapshot was taken, unix ms.
+    pub saved_at: u64,
+}
+
+#[derive(Serialize, Clone)]
+struct LibraryDiff {
+    added: Vec<Game>,
+    /// Paths of games that are gone.
+    removed: Vec<String>,
+}
+
+/// Held from loading a snapshot to saving the next one, so the scheduler and
+/// the `rescan` command cannot both scan the same snapshot and overwrite
+/// each other's result.
+static SNAPSHOT_LOCK: Mutex<()> = Mutex::new(());
+
+fn snapshot_path() -> PathBuf {
+    app_data_root().join(LIBRARY_SNAPSHOT_FILE)
+}
+
+pub fn load_snapshot() -> Option<LibrarySnapshot> {
+    let raw = std::fs::read_to_string(snapshot_path()).ok()?;
+    read_store(&raw)
+}
+
+/// Takes the `SNAPSHOT_LOCK` guard the caller already holds.
+fn save_snapshot(
+    _guard: &MutexGuard<'static, ()>,
+    snapshot: &LibrarySnapshot,
+) -> Result<(), String> {
+    write_store_atomic(&snapshot_path(), snapshot)
+}
+
+/// Records the result of a scan the frontend ran, so scheduled scans pick
+/// up from it.
+#[tauri::command]
+pub fn save_library_snapshot(
+    roots: Vec<String>,
+    games: Vec<Game>,
+    mtimes: Vec<DirMtime>,
+    options: Option<ScanOptions>,
+) -> Result<(), String> {
+    let guard = SNAPSHOT_LOCK.lock().unwrap();
+    save_snapshot(
+        &guard,
+        &LibrarySnapshot {
+            roots,
+            options: options.unwrap_or_default(),
+            games,
+            mtimes,
+            saved_at: crate::now_ms(),
+        },
+    )
+}
+
+/// The last saved scan result, if any.
+#[tauri::command]
+pub fn get_cached_library() -> Option<LibrarySnapshot> {
+    load_snapshot()
+}
+
+/// Incrementally rescans from the saved snapshot, so the frontend need not
+/// hold the cached games and mtimes itself. `roots` and `options` replace the
+/// saved ones when given. Returns the games and mtimes like
+/// `scan_multiple_incremental`.
+#[tauri::command]
+pub fn rescan(
+    roots: Option<Vec<String>>,
+    options: Option<ScanOptions>,
+) -> Result<(Vec<Game>, Vec<DirMtime>), String> {
+    let guard = SNAPSHOT_LOCK.lock().unwrap();
+    let mut previous = load_snapshot().unwrap_or_default();
+    if let Some(roots) = roots {
+        previous.roots = roots;
+    }
+    if let Some(options) = options {
+        previous.options = options;
+    }
+    if previous.roots.is_empty() {
+        return Err("No library folders to scan".to_string());
+    }
+    let (snapshot, _) = rescan_snapshot(&guard, previous)?;
+    Ok((snapshot.games, snapshot.mtimes))
+}
+
+/// Incrementally rescans the snapshot's roots, saves the new snapshot and
+/// returns it with the games added and removed since the last one. `previous`
+/// must have been loaded under the same `guard`.
+fn rescan_snapshot(
+    guard: &MutexGuard<'static, ()>,
+    previous: LibrarySnapshot,
+) -> Result<(LibrarySnapshot, LibraryDiff), String> {
+    let old_keys: HashSet<String> = previous
+        .games
+        .iter()
+        .map(|g| path_dedup_key(&g.path))
+        .collect();
+    let (games, mtimes) = scan_multiple_incremental(
+        previous.roots.clone(),
+        previous.games.clone(),
+        previous.mtimes,
+        Some(previous.options.clone()),
+    )?;
+    let new_keys: HashSet<String> = games.iter().map(|g| path_dedup_key(&g.path)).collect();
+    let diff = LibraryDiff {
+        added: games
+            .iter()
+            .filter(|g| !old_keys.contains(&path_dedup_key(&g.path)))
+            .cloned()
+            .collect(),
+        removed: previous
+            .games
+            .into_iter()
+            .filter(|g| !new_keys.contains(&path_dedup_key(&g.path)))
+            .map(|g| g.path)
+            .collect(),
+    };
+    let snapshot = LibrarySnapshot {
+        roots: previous.roots,
+        options: previous.options,
+        games,
+        mtimes,
+        saved_at: crate::now_ms(),
+    };
+    save_snapshot(guard, &snapshot)?;
+    Ok((snapshot, diff))
+}
+
+fn run_scheduled_scan(app: &AppHandle) {
+    let guard = SNAPSHOT_LOCK.lock().unwrap();
+    let Some(previous) = load_snapshot().filter(|s| !s.roots.is_empty()) else {
+        return;
+    };
+    match rescan_snapshot(&guard, previous) {
+        Ok((_, diff)) if !diff.added.is_empty() || !diff.removed.is_empty() => {
+            crate::push_rust_log(
+                Some(app),
+                "info",
+                format!(
+                    "Scheduled scan: {} new, {} removed",
+                    diff.added.len(),
+                    diff.removed.len()
+                ),
+            );
+            let _ = app.emit("library-updated", &diff);
+        }
+        Ok(_) => {}
+        Err(e) => crate::push_rust_log(Some(app), "warn", format!("Scheduled scan failed: {e}")),
+    }
+}
+
+/// Starts the scheduled-scan thread. It idles while the setting is off, so
+/// turning it on takes effect without a restart.
+pub fn start_scheduler(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut last_scan = Instant::now();
+        loop {
+            std::thread::sleep(SCHEDULER_TICK);
+            let settings = crate::settings::current();
+            if !settings.scheduled_scan {
+                last_scan = Instant::now();
+                continue;
+            }
+            let interval = Duration::from_secs(settings.scheduled_scan_interval_mins * 60);
+            if last_scan.elapsed() >= interval {
+                run_scheduled_scan(&app);
+                last_scan = Instant::now();
+            }
+        }
+    });
+}
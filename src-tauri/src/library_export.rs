@@ -0,0 +1,190 @@
+use crate::metadata::cached_metadata;
+use crate::playtime::{get_playtime, PlaytimeEntry};
+use crate::sorting::MetadataLink;
+use base64::Engine;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+// ── HTML library export ────────────────────────────────────────────────────
+// Writes the library as one self-contained HTML page: covers are embedded as
+// base64 JPEGs and the styles are inline, so the file can be shared or
+// archived on its own.
+
+/// Covers are downscaled to this width before embedding.
+const EXPORT_COVER_WIDTH: u32 = 240;
+const EXPORT_COVER_JPEG_QUALITY: u8 = 80;
+/// Covers larger than this on disk are skipped rather than decoded.
+const EXPORT_COVER_MAX_BYTES: u64 = 20 * 1024 * 1024;
+
+const EXPORT_CSS: &str = "\
+body{margin:0;padding:24px;background:#14151a;color:#e6e6e6;font-family:system-ui,sans-serif}\
+h1{margin:0 0 4px;font-size:24px}\
+.sub{margin:0 0 24px;color:#9a9aa5;font-size:13px}\
+.grid{display:grid;grid-template-columns:repeat(auto-fill,minmax(240px,1fr));gap:16px}\
+.card{background:#1e2028;border-radius:8px;overflow:hidden}\
+.card img{display:block;width:100%;aspect-ratio:3/4;object-fit:cover;background:#2a2d38}\
+.nocover{aspect-ratio:3/4;background:#2a2d38}\
+.body{padding:10px 12px}\
+.title{font-weight:600;font-size:15px;margin:0 0 2px}\
+.dev{color:#9a9aa5;font-size:12px;margin:0 0 6px}\
+.time{font-size:12px;margin:0 0 6px}\
+.tag{display:inline-block;margin:0 4px 4px 0;padding:1px 6px;border-radius:4px;\
+background:#2f3340;font-size:11px}";
+
+#[derive(Deserialize)]
+pub struct ExportGame {
+    pub path: String,
+    pub name: String,
+    /// Local cover file, usually in the cover cache.
+    #[serde(default)]
+    pub cover_path: Option<String>,
+    /// Metadata page used for the title, developer and tags.
+    #[serde(default)]
+    pub link: Option<MetadataLink>,
+    /// The user's own tags, listed before the metadata tags.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn format_playtime(secs: u64) -> String {
+    let (h, m) = (secs / 3600, secs % 3600 / 60);
+    if h > 0 {
+        format!("{h}h {m}m")
+    } else {
+        format!("{m}m")
+    }
+}
+
+/// The cover at `path` as a downscaled JPEG data URL.
+fn cover_data_url(path: &Path) -> Option<String> {
+    if path.metadata().ok()?.len() > EXPORT_COVER_MAX_BYTES {
+        return None;
+    }
+    let mut img = image::open(path).ok()?;
+    if img.width() > EXPORT_COVER_WIDTH {
+        img = img.resize(
+            EXPORT_COVER_WIDTH,
+            u32::MAX,
+            image::imageops::FilterType::Triangle,
+        );
+    }
+    let mut bytes = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut bytes, EXPORT_COVER_JPEG_QUALITY)
+        .encode_image(&img.to_rgb8())
+        .ok()?;
+    let b64 = base64::engine::general_purpose::STANDARD.encode(bytes);
+    Some(format!("data:image/jpeg;base64,{b64}"))
+}
+
+fn render_card(out: &mut String, game: &ExportGame, playtime: Option<&PlaytimeEntry>) {
+    let meta = game
+        .link
+        .as_ref()
+        .and_then(|l| cached_metadata(&l.source, &l.url));
+    let title = meta
+        .as_ref()
+        .and_then(|m| m.title.clone())
+        .unwrap_or_else(|| game.name.clone());
+    let developer = meta
+        .as_ref()
+        .and_then(|m| m.developer.clone().or_else(|| m.circle.clone()));
+    let mut tags = game.tags.clone();
+    for tag in meta.iter().flat_map(|m| &m.tags) {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.clone());
+        }
+    }
+
+    out.push_str("<div class=\"card\">");
+    match game
+        .cover_path
+        .as_deref()
+        .and_then(|p| cover_data_url(Path::new(p)))
+    {
+        Some(src) => {
+            let _ = write!(out, "<img src=\"{src}\" alt=\"\">");
+        }
+        None => out.push_str("<div class=\"nocover\"></div>"),
+    }
+    let _ = write!(
+        out,
+        "<div class=\"body\"><p class=\"title\">{}</p>",
+        escape_html(&title)
+    );
+    if let Some(dev) = developer {
+        let _ = write!(out, "<p class=\"dev\">{}</p>", escape_html(&dev));
+    }
+    if let Some(p) = playtime.filter(|p| p.total_secs > 0) {
+        let _ = write!(
+            out,
+            "<p class=\"time\">Played {} over {} session(s)</p>",
+            format_playtime(p.total_secs),
+            p.sessions
+        );
+    }
+    for tag in &tags {
+        let _ = write!(out, "<span class=\"tag\">{}</span>", escape_html(tag));
+    }
+    out.push_str("</div></div>");
+}
+
+/// Writes `games` to `output_path` as a self-contained HTML page with their
+/// covers, titles, developers, tags and playtime. Returns how many games it
+/// lists.
+#[tauri::command]
+pub async fn export_library_html(
+    output_path: String,
+    games: Vec<ExportGame>,
+) -> Result<usize, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let playtime: HashMap<String, PlaytimeEntry> = get_playtime(None).games;
+        let total_secs: u64 = games
+            .iter()
+            .filter_map(|g| playtime.get(&g.path))
+            .map(|p| p.total_secs)
+            .sum();
+
+        let mut html = String::new();
+        let _ = write!(
+            html,
+            "<!DOCTYPE html><html><head><meta charset=\"utf-8\">\
+             <meta name=\"viewport\" content=\"width=device-width,initial-scale=1\">\
+             <title>LIBMALY library</title><style>{EXPORT_CSS}</style></head><body>\
+             <h1>LIBMALY library</h1><p class=\"sub\">{} games, {} played</p>\
+             <div class=\"grid\">",
+            games.len(),
+            format_playtime(total_secs)
+        );
+        for game in &games {
+            render_card(&mut html, game, playtime.get(&game.path));
+        }
+        html.push_str("</div></body></html>\n");
+
+        if let Some(parent) = Path::new(&output_path).parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+        }
+        std::fs::write(&output_path, html).map_err(|e| e.to_string())?;
+        Ok(games.len())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
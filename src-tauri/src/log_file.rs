@@ -0,0 +1,116 @@
+use crate::data_paths::app_data_root;
+use crate::RustLogEntry;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+// ── Persistent log file ────────────────────────────────────────────────────
+// Every `push_rust_log` entry is also appended to `logs/libmaly.log` as one
+// JSON object per line, so the history survives restarts. The file rotates at
+// `MAX_LOG_FILE_BYTES` into `libmaly.log.1` .. `libmaly.log.{KEPT_LOG_FILES}`.
+
+const LOG_DIR: &str = "logs";
+const LOG_FILE: &str = "libmaly.log";
+const MAX_LOG_FILE_BYTES: u64 = 2 * 1024 * 1024;
+/// Rotated files kept besides the live one.
+const KEPT_LOG_FILES: usize = 3;
+
+/// Serializes appends and rotation across threads.
+static LOG_FILE_LOCK: Mutex<()> = Mutex::new(());
+
+fn logs_dir() -> PathBuf {
+    app_data_root().join(LOG_DIR)
+}
+
+fn rotated_path(dir: &Path, n: usize) -> PathBuf {
+    dir.join(format!("{LOG_FILE}.{n}"))
+}
+
+fn rotate(dir: &Path) -> std::io::Result<()> {
+    let _ = std::fs::remove_file(rotated_path(dir, KEPT_LOG_FILES));
+    for n in (1..KEPT_LOG_FILES).rev() {
+        let from = rotated_path(dir, n);
+        if from.exists() {
+            std::fs::rename(&from, rotated_path(dir, n + 1))?;
+        }
+    }
+    std::fs::rename(dir.join(LOG_FILE), rotated_path(dir, 1))
+}
+
+fn try_append(entry: &RustLogEntry) -> std::io::Result<()> {
+    let dir = logs_dir();
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(LOG_FILE);
+    if path.metadata().is_ok_and(|m| m.len() >= MAX_LOG_FILE_BYTES) {
+        rotate(&dir)?;
+    }
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?
+        .write_all(line.as_bytes())
+}
+
+/// Appends `entry` to the log file. Failures are dropped: logging must never
+/// take down the code that logs.
+pub(crate) fn append(entry: &RustLogEntry) {
+    let _guard = LOG_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let _ = try_append(entry);
+}
+
+/// The live log file and its rotations that exist, oldest first.
+fn log_files() -> Vec<PathBuf> {
+    let dir = logs_dir();
+    (1..=KEPT_LOG_FILES)
+        .rev()
+        .map(|n| rotated_path(&dir, n))
+        .chain(std::iter::once(dir.join(LOG_FILE)))
+        .filter(|p| p.is_file())
+        .collect()
+}
+
+/// Opens the folder holding the log files in the system file manager.
+#[tauri::command]
+pub fn open_logs_folder(app: AppHandle) -> Result<(), String> {
+    use tauri_plugin_opener::OpenerExt;
+    let dir = logs_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    app.opener()
+        .open_path(dir.to_string_lossy(), None::<&str>)
+        .map_err(|e| e.to_string())
+}
+
+/// Zips the log file and its rotations to `output_path`, e.g. to attach to a
+/// bug report. Returns how many files were added.
+#[tauri::command]
+pub fn export_logs_zip(output_path: String) -> Result<usize, String> {
+    let _guard = LOG_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let files = log_files();
+    if files.is_empty() {
+        return Err("No log file has been written yet.".to_string());
+    }
+    if let Some(parent) = Path::new(&output_path).parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+    }
+    let file = std::fs::File::create(&output_path).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for path in &files {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        zip.start_file(name, options).map_err(|e| e.to_string())?;
+        let mut src = std::fs::File::open(path).map_err(|e| e.to_string())?;
+        std::io::copy(&mut src, &mut zip).map_err(|e| e.to_string())?;
+    }
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(files.len())
+}
@@ -5,53 +5,290 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::io::BufReader;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
-use crate::data_paths::app_data_root;
+use crate::data_paths::{app_data_root, read_store, write_store};
 
 // ── Cookie store with disk persistence ────────────────────────────────────
 
-static COOKIE_STORE: Mutex<Option<Arc<CookieStoreMutex>>> = Mutex::new(None);
-
-fn cookies_path() -> PathBuf {
-    app_data_root().join("f95cookies.json")
+/// How a site session decides whether the user is signed in.
+enum SessionCheck {
+    /// Signed in when the page body satisfies the predicate.
+    Body(fn(&str) -> bool),
+    /// Signed in when the final URL (after redirects) contains one of these.
+    FinalUrl(&'static [&'static str]),
 }
 
-fn load_or_new_store() -> Arc<CookieStoreMutex> {
-    let path = cookies_path();
-    if path.exists() {
-        if let Ok(f) = std::fs::File::open(&path) {
-            #[allow(deprecated)]
-            if let Ok(store) = CookieStore::load_json(BufReader::new(f)) {
-                return Arc::new(CookieStoreMutex::new(store));
+/// One site's cookie jar, persisted as JSON under the app data root and
+/// loaded lazily on first use.
+pub struct SiteSession {
+    /// Short id used in status maps ("f95", "dlsite", …).
+    pub id: &'static str,
+    cookie_file: &'static str,
+    check_url: &'static str,
+    accept_language: &'static str,
+    check: SessionCheck,
+    /// Cookies that carry the login. Empty means "any persistent cookie".
+    auth_cookies: &'static [&'static str],
+    store: Mutex<Option<Arc<CookieStoreMutex>>>,
+    /// Set when the saved auth cookies had all expired by the time they were
+    /// loaded; cleared again by a successful login.
+    expired_on_load: AtomicBool,
+}
+
+/// Offline view of a site session, derived from cookie expiry dates only.
+#[derive(Serialize, Clone)]
+pub struct SessionExpiry {
+    pub site: String,
+    /// An unexpired auth cookie is present.
+    pub has_session: bool,
+    /// A saved session existed but its auth cookies have expired; the user
+    /// needs to sign in again.
+    pub expired: bool,
+    /// Unix seconds when the longest-lived auth cookie expires.
+    pub expires_at: Option<i64>,
+}
+
+impl SiteSession {
+    const fn new(
+        id: &'static str,
+        cookie_file: &'static str,
+        check_url: &'static str,
+        accept_language: &'static str,
+        check: SessionCheck,
+        auth_cookies: &'static [&'static str],
+    ) -> Self {
+        Self {
+            id,
+            cookie_file,
+            check_url,
+            accept_language,
+            check,
+            auth_cookies,
+            store: Mutex::new(None),
+            expired_on_load: AtomicBool::new(false),
+        }
+    }
+
+    fn is_auth_cookie(&self, cookie: &cookie_store::Cookie<'_>) -> bool {
+        cookie.is_persistent()
+            && (self.auth_cookies.is_empty() || self.auth_cookies.contains(&cookie.name()))
+    }
+
+    pub(crate) fn cookies_path(&self) -> PathBuf {
+        app_data_root().join(self.cookie_file)
+    }
+
+    fn load_or_new_store(&self, path: &std::path::Path) -> Arc<CookieStoreMutex> {
+        if path.exists() {
+            let raw = std::fs::read(path).unwrap_or_default();
+            let was_encrypted = crate::vault::is_encrypted(&raw);
+            // Undecryptable while the app is locked; `reload_sessions` retries
+            // after unlocking.
+            if let Ok(plain) = crate::vault::read_secret_file(path) {
+                // Load expired cookies too, so a lapsed login can be told
+                // apart from never having signed in.
+                #[allow(deprecated)]
+                if let Ok(all) = CookieStore::load_json_all(BufReader::new(&plain[..])) {
+                    let mut auth = all.iter_any().filter(|c| self.is_auth_cookie(c)).peekable();
+                    let lapsed = auth.peek().is_some() && auth.all(|c| c.is_expired());
+                    self.expired_on_load.store(lapsed, Ordering::Relaxed);
+
+                    let has_expired = all.iter_any().any(|c| c.is_expired());
+                    let live = CookieStore::from_cookies(
+                        all.iter_unexpired()
+                            .cloned()
+                            .map(Ok::<_, std::convert::Infallible>),
+                        false,
+                    )
+                    .unwrap_or_default();
+                    let migrate = !was_encrypted && crate::settings::current().encrypt_cookies;
+                    if has_expired || migrate {
+                        // Drop the dead cookies from disk as well, and encrypt
+                        // plaintext files from older versions.
+                        let _ = write_cookie_file(path, &live);
+                    }
+                    return Arc::new(CookieStoreMutex::new(live));
+                }
             }
         }
+        Arc::new(CookieStoreMutex::new(CookieStore::new(None)))
     }
-    Arc::new(CookieStoreMutex::new(CookieStore::new(None)))
-}
 
-fn save_cookies(store: &CookieStoreMutex) {
-    let path = cookies_path();
-    if let Some(parent) = path.parent() {
-        let _ = std::fs::create_dir_all(parent);
+    /// Expiry state of the session without touching the network.
+    pub fn expiry(&self) -> SessionExpiry {
+        let store = self.store();
+        let locked = store.lock().unwrap();
+        let mut has_session = false;
+        let mut expires_at: Option<i64> = None;
+        for cookie in locked.iter_unexpired().filter(|c| self.is_auth_cookie(c)) {
+            has_session = true;
+            if let cookie_store::CookieExpiration::AtUtc(at) = &cookie.expires {
+                let ts = at.unix_timestamp();
+                expires_at = Some(expires_at.map_or(ts, |cur| cur.max(ts)));
+            }
+        }
+        SessionExpiry {
+            site: self.id.to_string(),
+            has_session,
+            expired: !has_session && self.expired_on_load.load(Ordering::Relaxed),
+            expires_at,
+        }
+    }
+
+    /// The shared cookie store, loaded from disk on first access.
+    pub fn store(&self) -> Arc<CookieStoreMutex> {
+        let mut guard = self.store.lock().unwrap();
+        if guard.is_none() {
+            *guard = Some(self.load_or_new_store(&self.cookies_path()));
+        }
+        guard.as_ref().unwrap().clone()
+    }
+
+    /// Writes the current cookies to disk so the next launch stays signed in.
+    pub fn save(&self) {
+        invalidate_login_status();
+        self.expired_on_load.store(false, Ordering::Relaxed);
+        self.write_to_disk();
     }
-    if let Ok(mut f) = std::fs::File::create(&path) {
+
+    fn write_to_disk(&self) {
+        let path = self.cookies_path();
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let store = self.store();
         let locked = store.lock().unwrap();
-        #[allow(deprecated)]
-        let _ = locked.save_json(&mut f);
+        if let Err(e) = write_cookie_file(&path, &locked) {
+            crate::push_rust_log(
+                None,
+                "warn",
+                format!("Failed to save {} cookies: {}", self.id, e),
+            );
+        }
+    }
+
+    /// Replaces the store with an empty one and deletes the cookie file.
+    pub fn clear(&self) {
+        invalidate_login_status();
+        self.expired_on_load.store(false, Ordering::Relaxed);
+        *self.store.lock().unwrap() = Some(Arc::new(CookieStoreMutex::new(CookieStore::new(None))));
+        let _ = std::fs::remove_file(self.cookies_path());
+    }
+
+    /// Signs out: clears the cookies and removes the site's saved credentials
+    /// from the keyring, so no automatic re-login follows.
+    pub fn logout(&self) -> Result<(), String> {
+        self.clear();
+        crate::credentials::remove(self.id)
     }
+
+    pub fn http(&self) -> Client {
+        make_client(self.store())
+    }
+
+    /// Fetches the site's check page and applies its sign-in test.
+    pub async fn is_logged_in(&self) -> Result<bool, String> {
+        // A lapsed saved session cannot be signed in; skip the round-trip.
+        if self.expiry().expired {
+            return Ok(false);
+        }
+        let resp = self
+            .http()
+            .get(self.check_url)
+            .header("Accept-Language", self.accept_language)
+            .send_logged()
+            .await
+            .map_err(|e| e.to_string())?;
+        match &self.check {
+            SessionCheck::FinalUrl(markers) => {
+                let final_url = resp.url().to_string();
+                Ok(markers.iter().any(|m| final_url.contains(m)))
+            }
+            SessionCheck::Body(test) => {
+                let body = resp.text().await.map_err(|e| e.to_string())?;
+                Ok(test(&body))
+            }
+        }
+    }
+}
+
+static F95_SESSION: SiteSession = SiteSession::new(
+    "f95",
+    "f95cookies.json",
+    "https://f95zone.to/",
+    "en-US,en;q=0.9",
+    SessionCheck::Body(|body| body.contains("data-logged-in=\"true\"")),
+    // XenForo's remember-me cookie.
+    &["xf_user"],
+);
+
+static DLSITE_SESSION: SiteSession = SiteSession::new(
+    "dlsite",
+    "dlsite_cookies.json",
+    "https://www.dlsite.com/home/mypage/",
+    "en-US,en;q=0.9,ja;q=0.8",
+    // Signed-out visitors are redirected away from mypage.
+    SessionCheck::FinalUrl(&["/home/mypage", "/maniax/mypage"]),
+    &[],
+);
+
+static FAKKU_SESSION: SiteSession = SiteSession::new(
+    "fakku",
+    "fakku_cookies.json",
+    "https://www.fakku.net/",
+    "en-US,en;q=0.9",
+    SessionCheck::Body(fakku_login_looks_successful),
+    &[],
+);
+
+/// Every site with a persisted login, in display order.
+pub static SITE_SESSIONS: [&SiteSession; 3] = [&F95_SESSION, &DLSITE_SESSION, &FAKKU_SESSION];
+
+fn write_cookie_file(path: &std::path::Path, store: &CookieStore) -> Result<(), String> {
+    let mut buf = Vec::new();
+    #[allow(deprecated)]
+    store.save_json(&mut buf).map_err(|e| e.to_string())?;
+    crate::vault::write_secret_file(path, &buf)
 }
 
-fn ensure_store() -> Arc<CookieStoreMutex> {
-    let mut guard = COOKIE_STORE.lock().unwrap();
-    if guard.is_none() {
-        *guard = Some(load_or_new_store());
+/// Forgets the in-memory cookie stores so they are read from disk again, e.g.
+/// once the app is unlocked and encrypted files become readable.
+pub fn reload_sessions() {
+    for session in SITE_SESSIONS {
+        *session.store.lock().unwrap() = None;
     }
-    guard.as_ref().unwrap().clone()
+    invalidate_login_status();
 }
 
+/// Loads every saved session with the current key; pair with `resave_sessions`
+/// when the key is about to change.
+pub fn preload_sessions() {
+    for session in SITE_SESSIONS {
+        if session.cookies_path().exists() {
+            session.store();
+        }
+    }
+}
+
+/// Rewrites every saved cookie file with the current encryption settings.
+pub fn resave_sessions() {
+    for session in SITE_SESSIONS {
+        if session.cookies_path().exists() {
+            session.write_to_disk();
+        }
+    }
+}
+
+/// Connect and read timeout for every metadata request; without it a stalled
+/// server leaves a fetch hanging forever.
+const HTTP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+
 fn make_client(store: Arc<CookieStoreMutex>) -> Client {
     Client::builder()
         .cookie_provider(store)
+        .connect_timeout(HTTP_TIMEOUT)
+        .read_timeout(HTTP_TIMEOUT)
         .user_agent(
             "Mozilla/5.0 (Windows NT 10.0; Win64; x64) \
              AppleWebKit/537.36 (KHTML, like Gecko) \
@@ -62,14 +299,143 @@ fn make_client(store: Arc<CookieStoreMutex>) -> Client {
 }
 
 pub fn http() -> Client {
-    make_client(ensure_store())
+    F95_SESSION.http()
+}
+
+/// Cookie-less client for public APIs and store pages. Built once so requests
+/// share its connection pool.
+pub(crate) fn metadata_client() -> Client {
+    static CLIENT: std::sync::OnceLock<Client> = std::sync::OnceLock::new();
+    CLIENT
+        .get_or_init(|| {
+            Client::builder()
+                .connect_timeout(HTTP_TIMEOUT)
+                .read_timeout(HTTP_TIMEOUT)
+                .build()
+                .expect("failed to build reqwest client")
+        })
+        .clone()
+}
+
+// ── Debug request logging ──────────────────────────────────────────────────
+// Opt-in via the `debug_http_logging` / `debug_dump_html` backend settings, so
+// scraper breakage can be told apart from network trouble in the app log.
+
+trait SendLogged {
+    fn send_logged(
+        self,
+    ) -> impl std::future::Future<Output = reqwest::Result<reqwest::Response>> + Send;
+}
+
+impl SendLogged for reqwest::RequestBuilder {
+    async fn send_logged(self) -> reqwest::Result<reqwest::Response> {
+        if !crate::settings::current().debug_http_logging {
+            return self.send().await;
+        }
+        let (client, request) = self.build_split();
+        let request = request?;
+        let method = request.method().clone();
+        let url = request.url().to_string();
+        let started = std::time::Instant::now();
+        let result = client.execute(request).await;
+        let elapsed = started.elapsed().as_millis();
+        match &result {
+            Ok(resp) => {
+                let size = resp
+                    .content_length()
+                    .map(|n| format!("{n} bytes"))
+                    .unwrap_or_else(|| "size unknown".to_string());
+                crate::push_rust_log(
+                    None,
+                    "info",
+                    format!(
+                        "HTTP {method} {url} -> {} ({size}, {elapsed} ms)",
+                        resp.status()
+                    ),
+                );
+            }
+            Err(e) => crate::push_rust_log(
+                None,
+                "warn",
+                format!("HTTP {method} {url} failed after {elapsed} ms: {e}"),
+            ),
+        }
+        result
+    }
+}
+
+/// Extra attempts after a timeout, connection failure or 5xx answer.
+const HTTP_RETRIES: u32 = 2;
+
+pub(crate) trait SendRetrying {
+    fn send_retrying(
+        self,
+    ) -> impl std::future::Future<Output = reqwest::Result<reqwest::Response>> + Send;
+}
+
+impl SendRetrying for reqwest::RequestBuilder {
+    /// `send_logged` with exponential backoff (0.5 s, 1 s) on transient
+    /// failures. Requests with a streaming body cannot be cloned and are sent once.
+    async fn send_retrying(self) -> reqwest::Result<reqwest::Response> {
+        let mut request = self;
+        let mut delay = std::time::Duration::from_millis(500);
+        let mut attempt = 0;
+        loop {
+            let retry = if attempt < HTTP_RETRIES {
+                request.try_clone()
+            } else {
+                None
+            };
+            let result = request.send_logged().await;
+            let transient = match &result {
+                Ok(resp) => resp.status().is_server_error(),
+                Err(e) => e.is_timeout() || e.is_connect(),
+            };
+            match retry {
+                Some(next) if transient => {
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                    attempt += 1;
+                    request = next;
+                }
+                _ => return result,
+            }
+        }
+    }
+}
+
+/// Saves `body` to the temp directory when HTML dumps are enabled, for
+/// inspecting pages a scraper could not parse.
+fn debug_dump_html(url: &str, body: &str) {
+    if !crate::settings::current().debug_dump_html {
+        return;
+    }
+    let host = reqwest::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.replace('.', "_")))
+        .unwrap_or_else(|| "page".to_string());
+    let ts = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let dir = std::env::temp_dir().join("libmaly-scrape-dumps");
+    let path = dir.join(format!("{host}-{ts}.html"));
+    let written = std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(&path, body));
+    match written {
+        Ok(()) => crate::push_rust_log(
+            None,
+            "info",
+            format!("Saved HTML of {url} to {}", path.display()),
+        ),
+        Err(e) => crate::push_rust_log(None, "warn", format!("Failed to dump HTML of {url}: {e}")),
+    }
 }
 
 // ── Metadata struct ────────────────────────────────────────────────────────
 
 #[derive(Serialize, Deserialize, Clone, Default, Debug)]
 pub struct GameMetadata {
-    pub source: String, // "f95" | "dlsite" | "vndb" | "mangagamer" | "johren" | "fakku"
+    pub source: String, // "f95" | "dlsite" | "vndb" | "mangagamer" | "johren" | "fakku" | "itch" | "steam"
     pub source_url: String,
     pub title: Option<String>,
     pub version: Option<String>,
@@ -100,6 +466,155 @@ pub struct GameMetadata {
     pub product_format: Option<String>,
     pub file_format: Option<String>,
     pub file_size: Option<String>,
+    /// VNDB play length bucket: "Short", "Medium" or "Long".
+    #[serde(default)]
+    pub length: Option<String>,
+}
+
+// ── Metadata cache ─────────────────────────────────────────────────────────
+// Scraped pages are cached on disk, one file per source URL, so reopening a
+// detail panel does not re-scrape the site. The cover preference is applied
+// after the cache, so changing it needs no refetch.
+
+const METADATA_CACHE_DIR: &str = "metadata-cache";
+
+#[derive(Serialize, Deserialize)]
+struct MetadataCacheEntry {
+    saved_at: u64,
+    meta: GameMetadata,
+}
+
+fn metadata_cache_dir() -> PathBuf {
+    app_data_root().join(METADATA_CACHE_DIR)
+}
+
+fn metadata_cache_file(source: &str, url: &str) -> PathBuf {
+    use sha2::{Digest, Sha256};
+    let digest = Sha256::digest(format!("{source}|{}", url.trim()).as_bytes());
+    let hex: String = digest[..16].iter().map(|b| format!("{b:02x}")).collect();
+    metadata_cache_dir().join(format!("{hex}.json"))
+}
+
+/// VNDB entries rarely change; store pages carry versions and prices.
+fn default_metadata_ttl_secs(source: &str) -> u64 {
+    match source {
+        "vndb" => 7 * 24 * 60 * 60,
+        _ => 12 * 60 * 60,
+    }
+}
+
+/// Returns the cached scrape of `url` when younger than the TTL, otherwise
+/// runs `scrape` and writes the result through. `force_refresh` skips the
+/// lookup but still refreshes the cache.
+async fn cached_scrape(
+    source: &str,
+    url: &str,
+    ttl_secs: Option<u64>,
+    force_refresh: Option<bool>,
+    scrape: impl std::future::Future<Output = Result<GameMetadata, String>>,
+) -> Result<GameMetadata, String> {
+    let path = metadata_cache_file(source, url);
+    let ttl = ttl_secs.unwrap_or_else(|| default_metadata_ttl_secs(source));
+    if !force_refresh.unwrap_or(false) {
+        let cached = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|raw| read_store::<MetadataCacheEntry>(&raw))
+            .filter(|entry| unix_now_secs().saturating_sub(entry.saved_at) < ttl);
+        if let Some(entry) = cached {
+            return Ok(entry.meta);
+        }
+    }
+    let meta = scrape.await?;
+    let entry = MetadataCacheEntry {
+        saved_at: unix_now_secs(),
+        meta,
+    };
+    if let Ok(raw) = write_store(&entry, false) {
+        let _ = std::fs::create_dir_all(metadata_cache_dir());
+        let _ = std::fs::write(&path, raw);
+    }
+    Ok(entry.meta)
+}
+
+/// The cached metadata for `url` from `source`, however old.
+pub(crate) fn cached_metadata(source: &str, url: &str) -> Option<GameMetadata> {
+    let raw = std::fs::read_to_string(metadata_cache_file(source, url)).ok()?;
+    read_store::<MetadataCacheEntry>(&raw).map(|entry| entry.meta)
+}
+
+/// Deletes every cached metadata page. Returns how many were removed.
+#[tauri::command]
+pub fn clear_metadata_cache() -> Result<usize, String> {
+    let dir = metadata_cache_dir();
+    if !dir.exists() {
+        return Ok(0);
+    }
+    let count = std::fs::read_dir(&dir)
+        .map_err(|e| e.to_string())?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+        .count();
+    std::fs::remove_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(count)
+}
+
+// ── Cover selection ────────────────────────────────────────────────────────
+
+/// At most this many images are probed for the "largest" cover preference.
+const MAX_COVER_PROBES: usize = 6;
+
+/// Size in bytes reported by a HEAD request, used as a cheap stand-in for
+/// image dimensions.
+async fn probe_image_size(client: &Client, url: &str) -> Option<u64> {
+    let resp = client.head(url).send_logged().await.ok()?;
+    if !resp.status().is_success() {
+        return None;
+    }
+    resp.headers()
+        .get(reqwest::header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+}
+
+/// Re-picks `cover_url` per the `cover_preference` setting: "cover" keeps
+/// the site's cover, "first-screenshot" promotes the first screenshot and
+/// "largest" picks the biggest image. A displaced cover is kept as the first
+/// screenshot.
+async fn apply_cover_preference(mut meta: GameMetadata) -> GameMetadata {
+    let preference = crate::settings::current().cover_preference;
+    let chosen = match preference.as_str() {
+        "first-screenshot" => meta.screenshots.first().cloned(),
+        "largest" => {
+            let candidates: Vec<String> = meta
+                .cover_url
+                .iter()
+                .chain(meta.screenshots.iter())
+                .take(MAX_COVER_PROBES)
+                .cloned()
+                .collect();
+            let client = metadata_client();
+            let mut best: Option<(u64, String)> = None;
+            for url in candidates {
+                if let Some(size) = probe_image_size(&client, &url).await {
+                    if best.as_ref().map(|(b, _)| size > *b).unwrap_or(true) {
+                        best = Some((size, url));
+                    }
+                }
+            }
+            best.map(|(_, url)| url)
+        }
+        _ => None,
+    };
+    if let Some(new_cover) = chosen {
+        if meta.cover_url.as_deref() != Some(new_cover.as_str()) {
+            meta.screenshots.retain(|s| *s != new_cover);
+            if let Some(old) = meta.cover_url.take() {
+                meta.screenshots.insert(0, old);
+            }
+            meta.cover_url = Some(new_cover);
+        }
+    }
+    meta
 }
 
 // ── F95zone ────────────────────────────────────────────────────────────────
@@ -108,7 +623,7 @@ pub struct GameMetadata {
 async fn f95_get_login_state() -> Result<(String, bool), String> {
     let resp = http()
         .get("https://f95zone.to/login/")
-        .send()
+        .send_logged()
         .await
         .map_err(|e| e.to_string())?;
 
@@ -132,6 +647,7 @@ async fn f95_get_login_state() -> Result<(String, bool), String> {
 
 #[tauri::command]
 pub async fn f95_login(username: String, password: String) -> Result<bool, String> {
+    crate::applock::ensure_unlocked()?;
     let (token, already) = f95_get_login_state().await?;
     if already {
         return Ok(true);
@@ -148,7 +664,7 @@ pub async fn f95_login(username: String, password: String) -> Result<bool, Strin
     let resp = http()
         .post("https://f95zone.to/login/login")
         .form(&params)
-        .send()
+        .send_logged()
         .await
         .map_err(|e| e.to_string())?;
 
@@ -159,14 +675,14 @@ pub async fn f95_login(username: String, password: String) -> Result<bool, Strin
     if success {
         let check = http()
             .get("https://f95zone.to/")
-            .send()
+            .send_logged()
             .await
             .map_err(|e| e.to_string())?;
         let body = check.text().await.map_err(|e| e.to_string())?;
         let logged_in = !body.contains("data-logged-in=\"false\"");
         if logged_in {
             // Persist cookies so next app launch stays logged in
-            save_cookies(&ensure_store());
+            F95_SESSION.save();
         }
         return Ok(logged_in);
     }
@@ -176,21 +692,12 @@ pub async fn f95_login(username: String, password: String) -> Result<bool, Strin
 
 #[tauri::command]
 pub async fn f95_logout() -> Result<(), String> {
-    // Replace the store with a fresh empty one and delete the cookie file
-    *COOKIE_STORE.lock().unwrap() = Some(Arc::new(CookieStoreMutex::new(CookieStore::new(None))));
-    let _ = std::fs::remove_file(cookies_path());
-    Ok(())
+    F95_SESSION.logout()
 }
 
 #[tauri::command]
 pub async fn f95_is_logged_in() -> Result<bool, String> {
-    let resp = http()
-        .get("https://f95zone.to/")
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-    let body = resp.text().await.map_err(|e| e.to_string())?;
-    Ok(body.contains("data-logged-in=\"true\""))
+    check_session(&F95_SESSION).await
 }
 
 // ── DLsite auth ──────────────────────────────────────────────────────────────
@@ -201,57 +708,86 @@ pub async fn f95_is_logged_in() -> Result<bool, String> {
 //          header X-XSRF-TOKEN: <token>
 //   3. Verify via  www.dlsite.com/home/mypage  (redirects to /home/  if not logged in)
 
-static DLSITE_STORE: Mutex<Option<Arc<CookieStoreMutex>>> = Mutex::new(None);
-static SUGGEST_CACHE: std::sync::OnceLock<Mutex<HashMap<String, Vec<SearchResultItem>>>> =
+static SUGGEST_CACHE: std::sync::OnceLock<Mutex<HashMap<String, SuggestCacheEntry>>> =
     std::sync::OnceLock::new();
 
-fn suggest_cache() -> &'static Mutex<HashMap<String, Vec<SearchResultItem>>> {
-    SUGGEST_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+/// Cached suggestions older than this are ignored and pruned on load.
+const SUGGEST_CACHE_TTL_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Serialize, Deserialize, Clone)]
+struct SuggestCacheEntry {
+    /// Unix seconds when the entry was stored.
+    saved_at: u64,
+    items: Vec<SearchResultItem>,
 }
-
-fn dlsite_cookies_path() -> PathBuf {
-    app_data_root().join("dlsite_cookies.json")
+
+fn unix_now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+pub(crate) fn suggest_cache_path() -> PathBuf {
+    app_data_root().join("suggest-cache.json")
+}
+
+fn suggest_cache_fresh(entry: &SuggestCacheEntry, now: u64) -> bool {
+    now.saturating_sub(entry.saved_at) < SUGGEST_CACHE_TTL_SECS
+}
+
+fn suggest_cache() -> &'static Mutex<HashMap<String, SuggestCacheEntry>> {
+    SUGGEST_CACHE.get_or_init(|| {
+        let now = unix_now_secs();
+        let mut map: HashMap<String, SuggestCacheEntry> =
+            std::fs::read_to_string(suggest_cache_path())
+                .ok()
+                .and_then(|raw| read_store(&raw))
+                .unwrap_or_default();
+        map.retain(|_, entry| suggest_cache_fresh(entry, now));
+        Mutex::new(map)
+    })
+}
+
+fn suggest_cache_get(key: &str) -> Option<Vec<SearchResultItem>> {
+    let cache = suggest_cache().lock().unwrap();
+    cache
+        .get(key)
+        .filter(|entry| suggest_cache_fresh(entry, unix_now_secs()))
+        .map(|entry| entry.items.clone())
+}
+
+fn suggest_cache_put(key: String, items: Vec<SearchResultItem>) {
+    let now = unix_now_secs();
+    let raw = {
+        let mut cache = suggest_cache().lock().unwrap();
+        cache.retain(|_, entry| suggest_cache_fresh(entry, now));
+        cache.insert(
+            key,
+            SuggestCacheEntry {
+                saved_at: now,
+                items,
+            },
+        );
+        write_store(&*cache, false)
+    };
+    // Best-effort: the in-memory copy still serves this session if the write fails.
+    if let Ok(raw) = raw {
+        let path = suggest_cache_path();
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let _ = std::fs::write(path, raw);
+    }
 }
-
-fn dlsite_load_or_new_store() -> Arc<CookieStoreMutex> {
-    let path = dlsite_cookies_path();
-    if path.exists() {
-        if let Ok(f) = std::fs::File::open(&path) {
-            #[allow(deprecated)]
-            if let Ok(store) = CookieStore::load_json(BufReader::new(f)) {
-                return Arc::new(CookieStoreMutex::new(store));
-            }
-        }
-    }
-    Arc::new(CookieStoreMutex::new(CookieStore::new(None)))
-}
-
-fn dlsite_save_cookies(store: &CookieStoreMutex) {
-    let path = dlsite_cookies_path();
-    if let Some(parent) = path.parent() {
-        let _ = std::fs::create_dir_all(parent);
-    }
-    if let Ok(mut f) = std::fs::File::create(&path) {
-        let locked = store.lock().unwrap();
-        #[allow(deprecated)]
-        let _ = locked.save_json(&mut f);
-    }
-}
-
-fn dlsite_ensure_store() -> Arc<CookieStoreMutex> {
-    let mut guard = DLSITE_STORE.lock().unwrap();
-    if guard.is_none() {
-        *guard = Some(dlsite_load_or_new_store());
-    }
-    guard.as_ref().unwrap().clone()
-}
 
 pub fn dlsite_http() -> Client {
-    make_client(dlsite_ensure_store())
+    DLSITE_SESSION.http()
 }
 
 #[tauri::command]
 pub async fn dlsite_login(login_id: String, password: String) -> Result<bool, String> {
+    crate::applock::ensure_unlocked()?;
     // Step 1: GET login page to obtain the _token hidden field and initial cookies
     let page_resp = dlsite_http()
         .get("https://login.dlsite.com/login")
@@ -260,7 +796,7 @@ pub async fn dlsite_login(login_id: String, password: String) -> Result<bool, St
             "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
         )
         .header("Accept-Language", "en-US,en;q=0.9,ja;q=0.8")
-        .send()
+        .send_logged()
         .await
         .map_err(|e| format!("Failed to reach DLsite login page: {}", e))?;
 
@@ -297,7 +833,7 @@ pub async fn dlsite_login(login_id: String, password: String) -> Result<bool, St
         .header("Referer", "https://login.dlsite.com/login")
         .header("Origin", "https://login.dlsite.com")
         .form(&params)
-        .send()
+        .send_logged()
         .await
         .map_err(|e| format!("Login request failed: {}", e))?;
 
@@ -312,7 +848,7 @@ pub async fn dlsite_login(login_id: String, password: String) -> Result<bool, St
     let check = dlsite_http()
         .get("https://www.dlsite.com/home/mypage/")
         .header("Accept-Language", "en-US,en;q=0.9,ja;q=0.8")
-        .send()
+        .send_logged()
         .await
         .map_err(|e| e.to_string())?;
 
@@ -321,7 +857,7 @@ pub async fn dlsite_login(login_id: String, password: String) -> Result<bool, St
     let logged_in = final_url.contains("/home/mypage") || final_url.contains("/maniax/mypage");
 
     if logged_in {
-        dlsite_save_cookies(&dlsite_ensure_store());
+        DLSITE_SESSION.save();
     }
 
     Ok(logged_in)
@@ -329,65 +865,103 @@ pub async fn dlsite_login(login_id: String, password: String) -> Result<bool, St
 
 #[tauri::command]
 pub async fn dlsite_logout() -> Result<(), String> {
-    *DLSITE_STORE.lock().unwrap() = Some(Arc::new(CookieStoreMutex::new(CookieStore::new(None))));
-    let _ = std::fs::remove_file(dlsite_cookies_path());
-    Ok(())
+    DLSITE_SESSION.logout()
 }
 
 #[tauri::command]
 pub async fn dlsite_is_logged_in() -> Result<bool, String> {
-    let resp = dlsite_http()
-        .get("https://www.dlsite.com/home/mypage/")
-        .header("Accept-Language", "en-US,en;q=0.9,ja;q=0.8")
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-    let final_url = resp.url().to_string();
-    Ok(final_url.contains("/home/mypage") || final_url.contains("/maniax/mypage"))
+    check_session(&DLSITE_SESSION).await
 }
 
-// ── FAKKU auth ───────────────────────────────────────────────────────────────
-static FAKKU_STORE: Mutex<Option<Arc<CookieStoreMutex>>> = Mutex::new(None);
-
-fn fakku_cookies_path() -> PathBuf {
-    app_data_root().join("fakku_cookies.json")
+/// One work from the DLsite purchase history.
+#[derive(Serialize, Clone)]
+pub struct DlsitePurchase {
+    pub product_id: String,
+    pub title: String,
+    pub url: String,
+    pub cover_url: Option<String>,
 }
 
-fn fakku_load_or_new_store() -> Arc<CookieStoreMutex> {
-    let path = fakku_cookies_path();
-    if path.exists() {
-        if let Ok(f) = std::fs::File::open(&path) {
-            #[allow(deprecated)]
-            if let Ok(store) = CookieStore::load_json(BufReader::new(f)) {
-                return Arc::new(CookieStoreMutex::new(store));
-            }
-        }
-    }
-    Arc::new(CookieStoreMutex::new(CookieStore::new(None)))
-}
+/// Upper bound on purchase-history pages fetched.
+const DLSITE_PURCHASE_MAX_PAGES: usize = 100;
 
-fn fakku_save_cookies(store: &CookieStoreMutex) {
-    let path = fakku_cookies_path();
-    if let Some(parent) = path.parent() {
-        let _ = std::fs::create_dir_all(parent);
-    }
-    if let Ok(mut f) = std::fs::File::create(&path) {
-        let locked = store.lock().unwrap();
-        #[allow(deprecated)]
-        let _ = locked.save_json(&mut f);
-    }
-}
+/// Lists every work in the signed-in user's DLsite purchase history, page by
+/// page, until a page adds nothing new.
+#[tauri::command]
+pub async fn import_dlsite_purchases() -> Result<Vec<DlsitePurchase>, String> {
+    crate::applock::ensure_unlocked()?;
+    let row_sel = sel("table.work_list_main tr, .work_list_main li");
+    let link_sel = sel("a[href*=\"/product_id/\"]");
+    let img_sel = sel("img");
+
+    let mut purchases = Vec::new();
+    let mut seen = HashSet::<String>::new();
+    for page in 1..=DLSITE_PURCHASE_MAX_PAGES {
+        let url = format!(
+            "https://www.dlsite.com/home/mypage/userbuy/=/type/all/start/all/sort/1/order/1/page/{page}"
+        );
+        let resp = dlsite_http()
+            .get(&url)
+            .header("Accept-Language", "en-US,en;q=0.9,ja;q=0.8")
+            .send_logged()
+            .await
+            .map_err(|e| format!("Network error: {}", e))?;
+        // Signed-out visitors are redirected away from mypage.
+        if !resp.url().as_str().contains("/mypage") {
+            return Err("Sign in to DLsite to import your purchases.".into());
+        }
+        if !resp.status().is_success() {
+            return Err(format!("HTTP {}", resp.status()));
+        }
+        let body = resp.text().await.map_err(|e| e.to_string())?;
+        let doc = Html::parse_document(&body);
 
-fn fakku_ensure_store() -> Arc<CookieStoreMutex> {
-    let mut guard = FAKKU_STORE.lock().unwrap();
-    if guard.is_none() {
-        *guard = Some(fakku_load_or_new_store());
+        let before = purchases.len();
+        for row in doc.select(&row_sel) {
+            // Rows link the product from both the thumbnail and the name;
+            // the last link carries the title text.
+            let Some(link) = row
+                .select(&link_sel)
+                .filter(|a| !a.text().collect::<String>().trim().is_empty())
+                .last()
+            else {
+                continue;
+            };
+            let href = link.value().attr("href").unwrap_or("");
+            let Some(product_id) = dlsite_product_id(href) else {
+                continue;
+            };
+            if !seen.insert(product_id.clone()) {
+                continue;
+            }
+            let cover_url = row
+                .select(&img_sel)
+                .next()
+                .and_then(|i| i.value().attr("src").or_else(|| i.value().attr("data-src")))
+                .map(|s| {
+                    if s.starts_with("//") {
+                        format!("https:{}", s)
+                    } else {
+                        s.to_string()
+                    }
+                });
+            purchases.push(DlsitePurchase {
+                product_id,
+                title: link.text().collect::<String>().trim().to_string(),
+                url: href.to_string(),
+                cover_url,
+            });
+        }
+        if purchases.len() == before {
+            break;
+        }
     }
-    guard.as_ref().unwrap().clone()
+    Ok(purchases)
 }
 
+// ── FAKKU auth ───────────────────────────────────────────────────────────────
 fn fakku_http() -> Client {
-    make_client(fakku_ensure_store())
+    FAKKU_SESSION.http()
 }
 
 fn extract_fakku_csrf_token(doc: &Html) -> Option<String> {
@@ -430,11 +1004,12 @@ fn fakku_login_looks_successful(body: &str) -> bool {
 
 #[tauri::command]
 pub async fn fakku_login(email: String, password: String) -> Result<bool, String> {
+    crate::applock::ensure_unlocked()?;
     // 1) Load login page and CSRF.
     let page = fakku_http()
         .get("https://www.fakku.net/login")
         .header("Accept-Language", "en-US,en;q=0.9")
-        .send()
+        .send_logged()
         .await
         .map_err(|e| format!("Failed to reach FAKKU login page: {}", e))?;
     if !page.status().is_success() {
@@ -470,7 +1045,7 @@ pub async fn fakku_login(email: String, password: String) -> Result<bool, String
             .header("Accept-Language", "en-US,en;q=0.9")
             .header("x-csrf-token", csrf_header.clone())
             .form(&params)
-            .send()
+            .send_logged()
             .await
             .map_err(|e| format!("FAKKU login request failed: {}", e))?;
         if resp.status().is_success() || resp.status().is_redirection() {
@@ -499,7 +1074,7 @@ pub async fn fakku_login(email: String, password: String) -> Result<bool, String
                 .header("Content-Type", "application/json")
                 .header("x-csrf-token", csrf_header.clone())
                 .json(&payload)
-                .send()
+                .send_logged()
                 .await
             {
                 Ok(r) => r,
@@ -520,34 +1095,207 @@ pub async fn fakku_login(email: String, password: String) -> Result<bool, String
     let check = fakku_http()
         .get("https://www.fakku.net/")
         .header("Accept-Language", "en-US,en;q=0.9")
-        .send()
+        .send_logged()
         .await
         .map_err(|e| e.to_string())?;
     let check_body = check.text().await.map_err(|e| e.to_string())?;
     let logged_in = fakku_login_looks_successful(&check_body);
     if logged_in {
-        fakku_save_cookies(&fakku_ensure_store());
+        FAKKU_SESSION.save();
     }
     Ok(logged_in)
 }
 
 #[tauri::command]
 pub async fn fakku_logout() -> Result<(), String> {
-    *FAKKU_STORE.lock().unwrap() = Some(Arc::new(CookieStoreMutex::new(CookieStore::new(None))));
-    let _ = std::fs::remove_file(fakku_cookies_path());
-    Ok(())
+    FAKKU_SESSION.logout()
 }
 
 #[tauri::command]
 pub async fn fakku_is_logged_in() -> Result<bool, String> {
-    let resp = fakku_http()
-        .get("https://www.fakku.net/")
-        .header("Accept-Language", "en-US,en;q=0.9")
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-    let body = resp.text().await.map_err(|e| e.to_string())?;
-    Ok(fakku_login_looks_successful(&body))
+    check_session(&FAKKU_SESSION).await
+}
+
+/// How long `get_all_login_status` reuses its last answer.
+const LOGIN_STATUS_TTL: std::time::Duration = std::time::Duration::from_secs(60);
+
+static LOGIN_STATUS_CACHE: Mutex<Option<(std::time::Instant, HashMap<String, bool>)>> =
+    Mutex::new(None);
+
+fn invalidate_login_status() {
+    *LOGIN_STATUS_CACHE.lock().unwrap() = None;
+}
+
+/// Minimum gap between automatic re-login attempts per site, so a changed
+/// password does not hammer the login form.
+const RELOGIN_COOLDOWN: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+static LAST_RELOGIN: Mutex<Vec<(&'static str, std::time::Instant)>> = Mutex::new(Vec::new());
+
+/// Signs in again with the credentials saved in the keyring, if any.
+async fn relogin(site: &'static str) -> bool {
+    let creds = match crate::credentials::load(site) {
+        Some(c) => c,
+        None => return false,
+    };
+    {
+        let mut last = LAST_RELOGIN.lock().unwrap();
+        let now = std::time::Instant::now();
+        if last
+            .iter()
+            .any(|(id, at)| *id == site && now.duration_since(*at) < RELOGIN_COOLDOWN)
+        {
+            return false;
+        }
+        last.retain(|(id, _)| *id != site);
+        last.push((site, now));
+    }
+    let result = match site {
+        "f95" => f95_login(creds.user, creds.pass).await,
+        "dlsite" => dlsite_login(creds.user, creds.pass).await,
+        "fakku" => fakku_login(creds.user, creds.pass).await,
+        _ => Ok(false),
+    };
+    match result {
+        Ok(true) => {
+            crate::push_rust_log(
+                None,
+                "info",
+                format!("Signed in to {site} again automatically"),
+            );
+            true
+        }
+        Ok(false) => {
+            crate::push_rust_log(
+                None,
+                "warn",
+                format!("Automatic re-login to {site} was rejected; check the saved credentials"),
+            );
+            false
+        }
+        Err(e) => {
+            crate::push_rust_log(
+                None,
+                "warn",
+                format!("Automatic re-login to {site} failed: {e}"),
+            );
+            false
+        }
+    }
+}
+
+/// `is_logged_in`, retried through `relogin` when the session is gone.
+async fn check_session(session: &'static SiteSession) -> Result<bool, String> {
+    if session.is_logged_in().await? {
+        return Ok(true);
+    }
+    Ok(relogin(session.id).await)
+}
+
+/// Checks every site session concurrently and returns site id → signed in.
+/// Lapsed sessions with saved credentials are signed in again on the way.
+/// Answers are cached briefly; logging in or out invalidates the cache.
+#[tauri::command]
+pub async fn get_all_login_status() -> Result<HashMap<String, bool>, String> {
+    if let Some((at, status)) = LOGIN_STATUS_CACHE.lock().unwrap().as_ref() {
+        if at.elapsed() < LOGIN_STATUS_TTL {
+            return Ok(status.clone());
+        }
+    }
+    let handles: Vec<_> = SITE_SESSIONS
+        .iter()
+        .map(|&session| (session.id, tokio::spawn(check_session(session))))
+        .collect();
+    let mut status = HashMap::new();
+    let mut all_ok = true;
+    for (id, handle) in handles {
+        let logged_in = match handle.await {
+            Ok(Ok(v)) => v,
+            _ => {
+                all_ok = false;
+                false
+            }
+        };
+        status.insert(id.to_string(), logged_in);
+    }
+    // Don't pin a network failure as "signed out" for the whole TTL.
+    if all_ok {
+        *LOGIN_STATUS_CACHE.lock().unwrap() = Some((std::time::Instant::now(), status.clone()));
+    }
+    Ok(status)
+}
+
+/// Outcome of `refresh_sessions` for one site, also sent as a
+/// `session-status` event.
+#[derive(Serialize, Clone)]
+pub struct SessionStatus {
+    pub site: String,
+    pub logged_in: bool,
+    pub error: Option<String>,
+}
+
+/// Checks every site with saved cookies or credentials concurrently, signing
+/// in again where the session has lapsed. Emits `session-status` per site as
+/// each check finishes. Run in the background at startup when
+/// `refresh_sessions_on_startup` is on.
+#[tauri::command]
+pub async fn refresh_sessions(app: tauri::AppHandle) -> Result<Vec<SessionStatus>, String> {
+    use tauri::Emitter;
+    crate::applock::ensure_unlocked()?;
+    let handles: Vec<_> = SITE_SESSIONS
+        .iter()
+        .filter(|s| s.cookies_path().exists() || crate::credentials::load(s.id).is_some())
+        .map(|&session| {
+            let app = app.clone();
+            tokio::spawn(async move {
+                let (logged_in, error) = match check_session(session).await {
+                    Ok(v) => (v, None),
+                    Err(e) => (false, Some(e)),
+                };
+                let status = SessionStatus {
+                    site: session.id.to_string(),
+                    logged_in,
+                    error,
+                };
+                let _ = app.emit("session-status", &status);
+                status
+            })
+        })
+        .collect();
+    let mut statuses = Vec::new();
+    for handle in handles {
+        if let Ok(status) = handle.await {
+            statuses.push(status);
+        }
+    }
+    Ok(statuses)
+}
+
+/// Reports, per site, whether the saved login has expired, based on cookie
+/// expiry dates alone. Lets the UI prompt for a re-login without probing.
+#[tauri::command]
+pub fn get_session_expiry() -> Vec<SessionExpiry> {
+    SITE_SESSIONS.iter().map(|s| s.expiry()).collect()
+}
+
+/// Signs out of every site at once. Returns the ids of sites that had a saved
+/// session.
+#[tauri::command]
+pub async fn logout_all_sites() -> Result<Vec<String>, String> {
+    let mut cleared = Vec::new();
+    for session in SITE_SESSIONS {
+        if session.cookies_path().exists() {
+            cleared.push(session.id.to_string());
+        }
+        if let Err(e) = session.logout() {
+            crate::push_rust_log(
+                None,
+                "warn",
+                format!("Could not remove saved {} credentials: {e}", session.id),
+            );
+        }
+    }
+    Ok(cleared)
 }
 
 fn sel(s: &str) -> Selector {
@@ -605,16 +1353,35 @@ fn extract_field(html_text: &str, label: &str) -> Option<String> {
 }
 
 #[tauri::command]
-pub async fn fetch_f95_metadata(url: String) -> Result<GameMetadata, String> {
+pub async fn fetch_f95_metadata(
+    url: String,
+    ttl_secs: Option<u64>,
+    force_refresh: Option<bool>,
+) -> Result<GameMetadata, String> {
+    crate::applock::ensure_unlocked()?;
+    let meta = cached_scrape(
+        "f95",
+        &url,
+        ttl_secs,
+        force_refresh,
+        scrape_f95_metadata(url.clone()),
+    )
+    .await?;
+    Ok(apply_cover_preference(meta).await)
+}
+
+async fn scrape_f95_metadata(url: String) -> Result<GameMetadata, String> {
     let normalized_url = normalize_f95_thread_url(&url);
     let resp = http()
         .get(&normalized_url)
-        .send()
+        .send_logged()
         .await
         .map_err(|e| format!("Network error: {}", e))?;
 
     if !resp.status().is_success() {
-        return Err(format!("HTTP {}", resp.status()));
+        let status = resp.status();
+        debug_dump_html(&normalized_url, &resp.text().await.unwrap_or_default());
+        return Err(format!("HTTP {}", status));
     }
 
     let body = resp.text().await.map_err(|e| e.to_string())?;
@@ -799,6 +1566,10 @@ pub async fn fetch_f95_metadata(url: String) -> Result<GameMetadata, String> {
     // ── Rating ───────────────────────────────────────────────────────
     let rating = text_of(&doc, ".bratr-vote-content").map(|s| s.trim().to_string());
 
+    if title.is_empty() {
+        debug_dump_html(&normalized_url, &body);
+    }
+
     Ok(GameMetadata {
         source: "f95".into(),
         source_url: normalized_url,
@@ -829,22 +1600,219 @@ pub async fn fetch_f95_metadata(url: String) -> Result<GameMetadata, String> {
         product_format: None,
         file_format: None,
         file_size: None,
+        length: None,
     })
 }
 
+/// Flattens a post fragment to text, keeping `<br>`/block boundaries as
+/// line breaks and dropping runs of blank lines.
+fn html_fragment_to_lines(fragment_html: &str) -> String {
+    let with_breaks = fragment_html
+        .replace("<br>", "\n")
+        .replace("<br />", "\n")
+        .replace("</div>", "</div>\n")
+        .replace("</li>", "</li>\n");
+    let text = Html::parse_fragment(&with_breaks)
+        .root_element()
+        .text()
+        .collect::<String>();
+    let mut out: Vec<&str> = Vec::new();
+    for line in text.lines().map(|l| l.trim()) {
+        if line.is_empty() && out.last().map(|l| l.is_empty()).unwrap_or(true) {
+            continue;
+        }
+        out.push(line);
+    }
+    while out.last().map(|l| l.is_empty()).unwrap_or(false) {
+        out.pop();
+    }
+    out.join("\n")
+}
+
+/// Returns the "Changelog" section of an F95 thread's first post as plain
+/// text, from a spoiler titled Changelog or a `<b>Changelog</b>` heading.
+#[tauri::command]
+pub async fn fetch_f95_changelog(url: String) -> Result<Option<String>, String> {
+    crate::applock::ensure_unlocked()?;
+    let normalized_url = normalize_f95_thread_url(&url);
+    let resp = http()
+        .get(&normalized_url)
+        .send_logged()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+    if !resp.status().is_success() {
+        return Err(format!("HTTP {}", resp.status()));
+    }
+    let body = resp.text().await.map_err(|e| e.to_string())?;
+    let doc = Html::parse_document(&body);
+    let Some(post) = doc.select(&sel(".message-body .bbWrapper")).next() else {
+        return Ok(None);
+    };
+
+    // Spoiler blocks: <div class="bbCodeSpoiler"> with a title span.
+    let title_sel = sel(".bbCodeSpoiler-button-title");
+    let content_sel = sel(".bbCodeSpoiler-content, .bbCodeBlock-content");
+    for spoiler in post.select(&sel(".bbCodeSpoiler, .bbCodeBlock--spoiler")) {
+        let title = spoiler
+            .select(&title_sel)
+            .next()
+            .map(|t| t.text().collect::<String>())
+            .unwrap_or_default();
+        if !title.to_lowercase().contains("changelog") {
+            continue;
+        }
+        if let Some(content) = spoiler.select(&content_sel).next() {
+            let text = html_fragment_to_lines(&content.inner_html());
+            if !text.is_empty() {
+                return Ok(Some(text));
+            }
+        }
+    }
+
+    // Inline heading: everything after <b>Changelog</b> up to the next block.
+    // Changelogs use bold version headings, so don't stop at the next <b>.
+    let post_html = post.inner_html();
+    let Some(idx) = find_ascii_ci(&post_html, "<b>changelog</b>")
+        .or_else(|| find_ascii_ci(&post_html, "<b>changelog:</b>"))
+    else {
+        return Ok(None);
+    };
+    let after = &post_html[idx..];
+    // `after` starts with the ASCII "<b>", so byte 3 is a char boundary.
+    let end = after[3..]
+        .find("<div class=\"bbCodeBlock")
+        .map(|e| e + 3)
+        .unwrap_or_else(|| floor_char_boundary(after, 8000));
+    let text = html_fragment_to_lines(&after[..end]);
+    let text = text
+        .trim_start_matches("Changelog:")
+        .trim_start_matches("Changelog")
+        .trim()
+        .to_string();
+    Ok(if text.is_empty() { None } else { Some(text) })
+}
+
+/// Byte offset of the first ASCII-case-insensitive match of `needle` in
+/// `haystack`. Unlike searching a lowercased copy, the offset is always valid
+/// for `haystack` itself.
+fn find_ascii_ci(haystack: &str, needle: &str) -> Option<usize> {
+    let (h, n) = (haystack.as_bytes(), needle.as_bytes());
+    if n.is_empty() || n.len() > h.len() {
+        return None;
+    }
+    (0..=h.len() - n.len())
+        .filter(|&i| haystack.is_char_boundary(i))
+        .find(|&i| h[i..i + n.len()].eq_ignore_ascii_case(n))
+}
+
+/// The largest char boundary in `s` at or below `max`.
+fn floor_char_boundary(s: &str, max: usize) -> usize {
+    let mut end = max.min(s.len());
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    end
+}
+
+/// Upper bound on watched-thread pages fetched, in case pagination loops.
+const F95_WATCHED_MAX_PAGES: usize = 100;
+
+/// Lists the threads the signed-in user watches on F95zone, following the
+/// page navigation, as suggestion items with normalized thread URLs.
+#[tauri::command]
+pub async fn import_f95_watched() -> Result<Vec<SearchResultItem>, String> {
+    crate::applock::ensure_unlocked()?;
+    let item_sel = sel(".structItem--thread");
+    // The last link in the title is the thread; earlier ones are prefix filters.
+    let link_sel = sel(".structItem-title a[href*=\"/threads/\"]");
+    let next_sel = sel("a.pageNav-jump--next");
+
+    let mut items = Vec::new();
+    let mut seen = HashSet::<String>::new();
+    let mut url = "https://f95zone.to/watched/threads".to_string();
+    for _ in 0..F95_WATCHED_MAX_PAGES {
+        let resp = http()
+            .get(&url)
+            .send_logged()
+            .await
+            .map_err(|e| format!("Network error: {}", e))?;
+        if !resp.status().is_success() {
+            return Err(format!("HTTP {}", resp.status()));
+        }
+        let body = resp.text().await.map_err(|e| e.to_string())?;
+        if !body.contains("data-logged-in=\"true\"") {
+            return Err("Sign in to F95zone to import your watched threads.".into());
+        }
+        let doc = Html::parse_document(&body);
+        for row in doc.select(&item_sel) {
+            let Some(link) = row.select(&link_sel).last() else {
+                continue;
+            };
+            let href = link.value().attr("href").unwrap_or("");
+            let thread_url = normalize_f95_thread_url(&absolute_f95_url(href));
+            if !seen.insert(thread_url.clone()) {
+                continue;
+            }
+            items.push(SearchResultItem {
+                title: link.text().collect::<String>().trim().to_string(),
+                url: thread_url,
+                cover_url: None,
+                source: "F95zone".into(),
+                score: 0.0,
+            });
+        }
+        match doc
+            .select(&next_sel)
+            .next()
+            .and_then(|a| a.value().attr("href"))
+        {
+            Some(next) => url = absolute_f95_url(next),
+            None => break,
+        }
+    }
+    Ok(items)
+}
+
+fn absolute_f95_url(href: &str) -> String {
+    if href.starts_with("http") {
+        href.to_string()
+    } else {
+        format!("https://f95zone.to/{}", href.trim_start_matches('/'))
+    }
+}
+
 // ── DLsite ─────────────────────────────────────────────────────────────────
 
 #[tauri::command]
-pub async fn fetch_dlsite_metadata(url: String) -> Result<GameMetadata, String> {
+pub async fn fetch_dlsite_metadata(
+    url: String,
+    ttl_secs: Option<u64>,
+    force_refresh: Option<bool>,
+) -> Result<GameMetadata, String> {
+    crate::applock::ensure_unlocked()?;
+    let meta = cached_scrape(
+        "dlsite",
+        &url,
+        ttl_secs,
+        force_refresh,
+        scrape_dlsite_metadata(url.clone()),
+    )
+    .await?;
+    Ok(apply_cover_preference(meta).await)
+}
+
+async fn scrape_dlsite_metadata(url: String) -> Result<GameMetadata, String> {
     let resp = dlsite_http()
         .get(&url)
         .header("Accept-Language", "en-US,en;q=0.9,ja;q=0.8")
-        .send()
+        .send_logged()
         .await
         .map_err(|e| format!("Network error: {}", e))?;
 
     if !resp.status().is_success() {
-        return Err(format!("HTTP {}", resp.status()));
+        let status = resp.status();
+        debug_dump_html(&url, &resp.text().await.unwrap_or_default());
+        return Err(format!("HTTP {}", status));
     }
 
     let body = resp.text().await.map_err(|e| e.to_string())?;
@@ -854,6 +1822,9 @@ pub async fn fetch_dlsite_metadata(url: String) -> Result<GameMetadata, String>
     let title = text_of(&doc, "#work_name")
         .or_else(|| text_of(&doc, "h1.title"))
         .or_else(|| text_of(&doc, ".work_name"));
+    if title.is_none() {
+        debug_dump_html(&url, &body);
+    }
 
     // ── Cover ────────────────────────────────────────────────────────
     let cover_url = {
@@ -1092,6 +2063,7 @@ pub async fn fetch_dlsite_metadata(url: String) -> Result<GameMetadata, String>
         product_format,
         file_format,
         file_size,
+        length: None,
     })
 }
 
@@ -1145,6 +2117,11 @@ struct VndbItem {
     tags: Option<Vec<VndbTag>>,
     developers: Option<Vec<VndbDeveloper>>,
     relations: Option<Vec<VndbRelation>>,
+    /// Bayesian rating, 10-100.
+    rating: Option<f64>,
+    /// Average of user-submitted play times.
+    length_minutes: Option<u32>,
+    languages: Option<Vec<String>>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -1152,21 +2129,47 @@ struct VndbResponse {
     results: Option<Vec<VndbItem>>,
 }
 
+/// Buckets VNDB's average play time: under 10 hours is short, under 30 medium.
+fn vndb_length_bucket(minutes: u32) -> String {
+    match minutes {
+        0..600 => "Short",
+        600..1800 => "Medium",
+        _ => "Long",
+    }
+    .to_string()
+}
+
 #[tauri::command]
-pub async fn fetch_vndb_metadata(url: String) -> Result<GameMetadata, String> {
+pub async fn fetch_vndb_metadata(
+    url: String,
+    ttl_secs: Option<u64>,
+    force_refresh: Option<bool>,
+) -> Result<GameMetadata, String> {
+    let meta = cached_scrape(
+        "vndb",
+        &url,
+        ttl_secs,
+        force_refresh,
+        scrape_vndb_metadata(url.clone()),
+    )
+    .await?;
+    Ok(apply_cover_preference(meta).await)
+}
+
+async fn scrape_vndb_metadata(url: String) -> Result<GameMetadata, String> {
     let vn_id = parse_vndb_id_from_url(&url)
         .ok_or_else(|| "Expected VNDB URL like https://vndb.org/v1234".to_string())?;
 
     let body = serde_json::json!({
         "filters": ["id", "=", vn_id],
-        "fields": "id,title,alttitle,description,released,image.url,screenshots.url,tags.rating,tags.name,developers.name,developers.original,relations.relation,relations.title,relations.id"
+        "fields": "id,title,alttitle,description,released,image.url,screenshots.url,tags.rating,tags.name,developers.name,developers.original,relations.relation,relations.title,relations.id,rating,length_minutes,languages"
     });
 
-    let resp = reqwest::Client::new()
+    let resp = metadata_client()
         .post("https://api.vndb.org/kana/vn")
         .header("User-Agent", "LIBMALY/1.3")
         .json(&body)
-        .send()
+        .send_retrying()
         .await
         .map_err(|e| format!("VNDB API request failed: {}", e))?;
 
@@ -1233,6 +2236,13 @@ pub async fn fetch_vndb_metadata(url: String) -> Result<GameMetadata, String> {
         }
     });
 
+    let rating = item.rating.map(|r| format!("{:.1}", r / 10.0));
+    let language = item
+        .languages
+        .filter(|l| !l.is_empty())
+        .map(|l| l.join(", "));
+    let length = item.length_minutes.map(vndb_length_bucket);
+
     let relations = item
         .relations
         .unwrap_or_default()
@@ -1262,11 +2272,11 @@ pub async fn fetch_vndb_metadata(url: String) -> Result<GameMetadata, String> {
         relations,
         engine: None,
         os: None,
-        language: None,
+        language,
         censored: None,
         release_date: item.released.filter(|d| !d.is_empty() && d != "null"),
         last_updated: None,
-        rating: None,
+        rating,
         price: None,
         circle: None,
         series: None,
@@ -1278,6 +2288,7 @@ pub async fn fetch_vndb_metadata(url: String) -> Result<GameMetadata, String> {
         product_format: None,
         file_format: None,
         file_size: None,
+        length,
     })
 }
 
@@ -1357,9 +2368,75 @@ fn source_from_url(url: &str) -> Option<(&'static str, &'static str)> {
     if host.contains("fakku.net") {
         return Some(("fakku", "FAKKU"));
     }
+    if host == "itch.io" || host.ends_with(".itch.io") {
+        return Some(("itch", "itch.io"));
+    }
+    if host == "store.steampowered.com" && u.path().starts_with("/app/") {
+        return Some(("steam", "Steam"));
+    }
+    None
+}
+
+/// Value cell of a row in itch.io's "More information" table, looked up by
+/// its label ("Author", "Release date", …). Dates carry the full timestamp
+/// in an `abbr` title.
+fn itch_info_field(doc: &Html, label: &str) -> Option<String> {
+    let row_sel = sel(".game_info_panel_widget tr");
+    let cell_sel = sel("td");
+    let abbr_sel = sel("abbr[title]");
+    for row in doc.select(&row_sel) {
+        let mut cells = row.select(&cell_sel);
+        let (Some(key), Some(value)) = (cells.next(), cells.next()) else {
+            continue;
+        };
+        if !key
+            .text()
+            .collect::<String>()
+            .trim()
+            .eq_ignore_ascii_case(label)
+        {
+            continue;
+        }
+        let text = value
+            .select(&abbr_sel)
+            .next()
+            .and_then(|a| a.value().attr("title"))
+            .map(|t| t.to_string())
+            .unwrap_or_else(|| value.text().collect::<String>());
+        let text = text.trim().to_string();
+        if !text.is_empty() {
+            return Some(text);
+        }
+    }
     None
 }
 
+/// Link texts of an itch.io info-table row, e.g. its "Tags" or "Genre".
+fn itch_info_links(doc: &Html, label: &str) -> Vec<String> {
+    let row_sel = sel(".game_info_panel_widget tr");
+    let cell_sel = sel("td");
+    let a_sel = sel("a");
+    for row in doc.select(&row_sel) {
+        let mut cells = row.select(&cell_sel);
+        let (Some(key), Some(value)) = (cells.next(), cells.next()) else {
+            continue;
+        };
+        if key
+            .text()
+            .collect::<String>()
+            .trim()
+            .eq_ignore_ascii_case(label)
+        {
+            return value
+                .select(&a_sel)
+                .map(|a| a.text().collect::<String>().trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect();
+        }
+    }
+    Vec::new()
+}
+
 async fn fetch_store_metadata(url: String) -> Result<GameMetadata, String> {
     let (source_id, source_label) =
         source_from_url(&url).ok_or_else(|| "Unsupported store URL".to_string())?;
@@ -1367,16 +2444,18 @@ async fn fetch_store_metadata(url: String) -> Result<GameMetadata, String> {
     let client = if source_id == "fakku" {
         fakku_http()
     } else {
-        reqwest::Client::new()
+        metadata_client()
     };
     let resp = client
         .get(&source_url)
         .header("User-Agent", "LIBMALY/1.3")
-        .send()
+        .send_retrying()
         .await
         .map_err(|e| format!("{source_label} request failed: {e}"))?;
     if !resp.status().is_success() {
-        return Err(format!("{source_label} HTTP {}", resp.status()));
+        let status = resp.status();
+        debug_dump_html(&source_url, &resp.text().await.unwrap_or_default());
+        return Err(format!("{source_label} HTTP {}", status));
     }
     let body = resp
         .text()
@@ -1387,6 +2466,9 @@ async fn fetch_store_metadata(url: String) -> Result<GameMetadata, String> {
     let title = extract_meta(&doc, "og:title")
         .or_else(|| extract_meta(&doc, "twitter:title"))
         .or_else(|| text_first(&doc, &["h1.product-title", "h1[itemprop='name']", "h1.title", "h1"]));
+    if title.is_none() {
+        debug_dump_html(&source_url, &body);
+    }
 
     let overview = extract_meta(&doc, "og:description")
         .or_else(|| extract_meta(&doc, "twitter:description"))
@@ -1399,6 +2481,17 @@ async fn fetch_store_metadata(url: String) -> Result<GameMetadata, String> {
 
     let mut screenshots = Vec::<String>::new();
     let mut seen = HashSet::<String>::new();
+    if source_id == "itch" {
+        // The gallery links point at the full-size images.
+        for a in doc.select(&sel(".screenshot_list a")) {
+            if let Some(href) = a.value().attr("href") {
+                let abs = absolutize_url(&source_url, href);
+                if seen.insert(abs.to_lowercase()) {
+                    screenshots.push(abs);
+                }
+            }
+        }
+    }
     for src in [
         "img.product-gallery__image",
         ".product-gallery img",
@@ -1408,6 +2501,9 @@ async fn fetch_store_metadata(url: String) -> Result<GameMetadata, String> {
         ".slick-slide img",
         "img",
     ] {
+        if source_id == "itch" && !screenshots.is_empty() {
+            break;
+        }
         let s = sel(src);
         for img in doc.select(&s) {
             let raw = img
@@ -1441,6 +2537,16 @@ async fn fetch_store_metadata(url: String) -> Result<GameMetadata, String> {
     }
 
     let mut tags = Vec::<String>::new();
+    if source_id == "itch" {
+        for tag in itch_info_links(&doc, "Genre")
+            .into_iter()
+            .chain(itch_info_links(&doc, "Tags"))
+        {
+            if !tags.iter().any(|x| x.eq_ignore_ascii_case(&tag)) {
+                tags.push(tag);
+            }
+        }
+    }
     if let Some(kw) = extract_meta(&doc, "keywords") {
         tags.extend(split_keywords_to_tags(&kw));
     }
@@ -1490,6 +2596,17 @@ async fn fetch_store_metadata(url: String) -> Result<GameMetadata, String> {
             ".product-release",
         ],
     );
+    // itch.io keeps these in its info table, not in markup the selectors above know.
+    let (developer, release_date) = if source_id == "itch" {
+        (
+            itch_info_field(&doc, "Author").or(developer),
+            itch_info_field(&doc, "Release date")
+                .or_else(|| itch_info_field(&doc, "Published"))
+                .or(release_date),
+        )
+    } else {
+        (developer, release_date)
+    };
     let price = text_first(&doc, &[".price", "[itemprop='price']", ".product-price"]);
 
     Ok(GameMetadata {
@@ -1522,30 +2639,455 @@ async fn fetch_store_metadata(url: String) -> Result<GameMetadata, String> {
         product_format: None,
         file_format: None,
         file_size: None,
+        length: None,
     })
 }
 
 #[tauri::command]
-pub async fn fetch_mangagamer_metadata(url: String) -> Result<GameMetadata, String> {
-    fetch_store_metadata(url).await
+pub async fn fetch_mangagamer_metadata(
+    url: String,
+    ttl_secs: Option<u64>,
+    force_refresh: Option<bool>,
+) -> Result<GameMetadata, String> {
+    let meta = cached_scrape(
+        "mangagamer",
+        &url,
+        ttl_secs,
+        force_refresh,
+        fetch_store_metadata(url.clone()),
+    )
+    .await?;
+    Ok(apply_cover_preference(meta).await)
 }
 
 #[tauri::command]
-pub async fn fetch_johren_metadata(url: String) -> Result<GameMetadata, String> {
-    fetch_store_metadata(url).await
+pub async fn fetch_johren_metadata(
+    url: String,
+    ttl_secs: Option<u64>,
+    force_refresh: Option<bool>,
+) -> Result<GameMetadata, String> {
+    let meta = cached_scrape(
+        "johren",
+        &url,
+        ttl_secs,
+        force_refresh,
+        fetch_store_metadata(url.clone()),
+    )
+    .await?;
+    Ok(apply_cover_preference(meta).await)
 }
 
 #[tauri::command]
-pub async fn fetch_fakku_metadata(url: String) -> Result<GameMetadata, String> {
-    fetch_store_metadata(url).await
+pub async fn fetch_fakku_metadata(
+    url: String,
+    ttl_secs: Option<u64>,
+    force_refresh: Option<bool>,
+) -> Result<GameMetadata, String> {
+    crate::applock::ensure_unlocked()?;
+    let meta = cached_scrape(
+        "fakku",
+        &url,
+        ttl_secs,
+        force_refresh,
+        fetch_store_metadata(url.clone()),
+    )
+    .await?;
+    Ok(apply_cover_preference(meta).await)
 }
 
+// ── Steam ──────────────────────────────────────────────────────────────────
+
+/// Cookies Steam sets once the age gate has been passed; sent up front on the
+/// retry so adult titles return the store page instead of `/agecheck`.
+const STEAM_AGE_COOKIES: &str =
+    "birthtime=631152001; lastagecheckage=1-January-1990; wants_mature_content=1; mature_content=1";
+
+fn steam_app_id(url: &str) -> Option<String> {
+    let u = reqwest::Url::parse(url).ok()?;
+    let mut segments = u.path_segments()?;
+    if segments.next()? != "app" {
+        return None;
+    }
+    let id = segments.next()?;
+    if !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()) {
+        Some(id.to_string())
+    } else {
+        None
+    }
+}
+
+/// Value of a `.glance_ctn .dev_row` whose subtitle starts with `label`.
+fn steam_dev_row(doc: &Html, label: &str) -> Option<String> {
+    let row_sel = sel(".glance_ctn .dev_row");
+    let subtitle_sel = sel(".subtitle");
+    let summary_sel = sel(".summary");
+    doc.select(&row_sel).find_map(|row| {
+        let subtitle = row.select(&subtitle_sel).next()?.text().collect::<String>();
+        if !subtitle.trim().starts_with(label) {
+            return None;
+        }
+        let value = row.select(&summary_sel).next()?.text().collect::<String>();
+        let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+        if value.is_empty() {
+            None
+        } else {
+            Some(value)
+        }
+    })
+}
+
+async fn scrape_steam_metadata(url: String) -> Result<GameMetadata, String> {
+    let app_id = steam_app_id(&url).ok_or_else(|| {
+        "Expected Steam URL like https://store.steampowered.com/app/1234".to_string()
+    })?;
+    let source_url = format!("https://store.steampowered.com/app/{app_id}/");
+    let client = metadata_client();
+
+    let mut body = String::new();
+    for age_cookies in [None, Some(STEAM_AGE_COOKIES)] {
+        let mut req = client
+            .get(&source_url)
+            .header("Accept-Language", "en-US,en;q=0.9")
+            .header("User-Agent", "Mozilla/5.0");
+        if let Some(cookies) = age_cookies {
+            req = req.header(reqwest::header::COOKIE, cookies);
+        }
+        let resp = req
+            .send_retrying()
+            .await
+            .map_err(|e| format!("Steam request failed: {e}"))?;
+        if !resp.status().is_success() {
+            return Err(format!("Steam HTTP {}", resp.status()));
+        }
+        let gated = resp.url().path().starts_with("/agecheck");
+        body = resp.text().await.map_err(|e| e.to_string())?;
+        if !gated {
+            break;
+        }
+    }
+    let doc = Html::parse_document(&body);
+
+    let title = text_first(&doc, &[".apphub_AppName"]).or_else(|| extract_meta(&doc, "og:title"));
+    if title.is_none() {
+        debug_dump_html(&source_url, &body);
+        return Err("Steam page has no title (age gate or removed app?)".to_string());
+    }
+    let overview = text_first(&doc, &[".game_description_snippet"])
+        .or_else(|| extract_meta(&doc, "og:description"));
+    let cover_url = doc
+        .select(&sel("img.game_header_image_full"))
+        .next()
+        .and_then(|img| img.value().attr("src"))
+        .map(|s| s.to_string())
+        .or_else(|| extract_meta(&doc, "og:image"));
+
+    let mut tags = Vec::<String>::new();
+    for a in doc.select(&sel("a.app_tag")) {
+        let tag = a.text().collect::<String>().trim().to_string();
+        if !tag.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+            tags.push(tag);
+        }
+    }
+
+    let mut screenshots = Vec::<String>::new();
+    for a in doc.select(&sel("a.highlight_screenshot_link")) {
+        if let Some(href) = a.value().attr("href") {
+            let href = href.to_string();
+            if !screenshots.contains(&href) {
+                screenshots.push(href);
+            }
+        }
+    }
+
+    Ok(GameMetadata {
+        source: "steam".to_string(),
+        source_url,
+        title,
+        developer: steam_dev_row(&doc, "Developer").or_else(|| steam_dev_row(&doc, "Publisher")),
+        overview,
+        cover_url,
+        screenshots,
+        tags,
+        release_date: text_first(&doc, &[".release_date .date"]),
+        price: text_first(&doc, &[".game_purchase_price", ".discount_final_price"]),
+        ..Default::default()
+    })
+}
+
+#[tauri::command]
+pub async fn fetch_steam_metadata(
+    url: String,
+    ttl_secs: Option<u64>,
+    force_refresh: Option<bool>,
+) -> Result<GameMetadata, String> {
+    let meta = cached_scrape(
+        "steam",
+        &url,
+        ttl_secs,
+        force_refresh,
+        scrape_steam_metadata(url.clone()),
+    )
+    .await?;
+    Ok(apply_cover_preference(meta).await)
+}
+
+#[tauri::command]
+pub async fn fetch_itch_metadata(
+    url: String,
+    ttl_secs: Option<u64>,
+    force_refresh: Option<bool>,
+) -> Result<GameMetadata, String> {
+    let meta = cached_scrape(
+        "itch",
+        &url,
+        ttl_secs,
+        force_refresh,
+        fetch_store_metadata(url.clone()),
+    )
+    .await?;
+    Ok(apply_cover_preference(meta).await)
+}
+
+// ── ProtonDB ───────────────────────────────────────────────────────────────
+// Community compatibility summaries for Steam titles. Non-Steam games are
+// matched to a Steam app by name first. Summaries move slowly, so they are
+// cached in the metadata cache for a day.
+
+const PROTONDB_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// Lowest `title_similarity` for a Steam search hit to count as the game.
+const PROTONDB_MIN_NAME_SCORE: f32 = 0.75;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProtonDbRating {
+    pub app_id: String,
+    /// Steam's name for the app, when it was resolved from a search.
+    pub app_name: Option<String>,
+    /// "platinum", "gold", "silver", "bronze", "borked" or "pending".
+    pub tier: String,
+    pub trending_tier: Option<String>,
+    pub best_reported_tier: Option<String>,
+    /// How sure ProtonDB is of `tier`, e.g. "strong" or "low".
+    pub confidence: Option<String>,
+    pub total_reports: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProtonDbSummary {
+    tier: String,
+    #[serde(default)]
+    trending_tier: Option<String>,
+    #[serde(default)]
+    best_reported_tier: Option<String>,
+    #[serde(default)]
+    confidence: Option<String>,
+    #[serde(default)]
+    total: u32,
+}
+
+#[derive(Deserialize)]
+struct SteamSearchResponse {
+    #[serde(default)]
+    items: Vec<SteamSearchItem>,
+}
+
+#[derive(Deserialize)]
+struct SteamSearchItem {
+    id: u64,
+    name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ProtonDbCacheEntry {
+    saved_at: u64,
+    rating: ProtonDbRating,
+}
+
+/// The Steam app whose name best matches `name`, as `(app id, Steam name)`.
+async fn resolve_steam_app_id(name: &str) -> Result<(String, String), String> {
+    let url = format!(
+        "https://store.steampowered.com/api/storesearch/?term={}&l=english&cc=US",
+        urlencoding::encode(name)
+    );
+    let resp = metadata_client()
+        .get(&url)
+        .send_retrying()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        return Err(format!("Steam search failed: HTTP {}", resp.status()));
+    }
+    let found: SteamSearchResponse = resp.json().await.map_err(|e| e.to_string())?;
+    found
+        .items
+        .into_iter()
+        .map(|item| (title_similarity(name, &item.name), item))
+        .filter(|(score, _)| *score >= PROTONDB_MIN_NAME_SCORE)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, item)| (item.id.to_string(), item.name))
+        .ok_or_else(|| format!("No Steam app found for \"{name}\""))
+}
+
+/// ProtonDB tier, confidence and report count for a Steam app id, or for the
+/// Steam app matching a game name.
+#[tauri::command]
+pub async fn fetch_protondb_rating(
+    steam_app_id_or_name: String,
+    force_refresh: Option<bool>,
+) -> Result<ProtonDbRating, String> {
+    let query = steam_app_id_or_name.trim();
+    if query.is_empty() {
+        return Err("Enter a Steam app id or a game name".to_string());
+    }
+    let cache_path = metadata_cache_file("protondb", &query.to_lowercase());
+    if !force_refresh.unwrap_or(false) {
+        let cached = std::fs::read_to_string(&cache_path)
+            .ok()
+            .and_then(|raw| read_store::<ProtonDbCacheEntry>(&raw))
+            .filter(|entry| unix_now_secs().saturating_sub(entry.saved_at) < PROTONDB_TTL_SECS);
+        if let Some(entry) = cached {
+            return Ok(entry.rating);
+        }
+    }
+
+    let (app_id, app_name) = if query.chars().all(|c| c.is_ascii_digit()) {
+        (query.to_string(), None)
+    } else {
+        let (id, name) = resolve_steam_app_id(query).await?;
+        (id, Some(name))
+    };
+    let url = format!("https://www.protondb.com/api/v1/reports/summaries/{app_id}.json");
+    let resp = metadata_client()
+        .get(&url)
+        .send_retrying()
+        .await
+        .map_err(|e| e.to_string())?;
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(format!("ProtonDB has no reports for app {app_id}"));
+    }
+    if !resp.status().is_success() {
+        return Err(format!("ProtonDB request failed: HTTP {}", resp.status()));
+    }
+    let summary: ProtonDbSummary = resp.json().await.map_err(|e| e.to_string())?;
+    let rating = ProtonDbRating {
+        app_id,
+        app_name,
+        tier: summary.tier,
+        trending_tier: summary.trending_tier,
+        best_reported_tier: summary.best_reported_tier,
+        confidence: summary.confidence,
+        total_reports: summary.total,
+    };
+
+    let entry = ProtonDbCacheEntry {
+        saved_at: unix_now_secs(),
+        rating,
+    };
+    if let Ok(raw) = write_store(&entry, false) {
+        let _ = std::fs::create_dir_all(metadata_cache_dir());
+        let _ = std::fs::write(&cache_path, raw);
+    }
+    Ok(entry.rating)
+}
+
+// ── Scraper self-test ──────────────────────────────────────────────────────
+
+/// Pages known to stay up, used when `test_scrapers` gets no URL for a source.
+/// Sources without a long-lived public page here are only tested when the
+/// caller supplies one.
+const SCRAPER_SAMPLE_URLS: &[(&str, &str)] = &[
+    ("steam", "https://store.steampowered.com/app/413150/"),
+    ("vndb", "https://vndb.org/v17"),
+];
+
+#[derive(Serialize, Clone)]
+pub struct ScraperTestResult {
+    pub source: String,
+    pub url: String,
+    pub error: Option<String>,
+    /// GameMetadata field name → whether the scraper filled it.
+    pub fields: std::collections::BTreeMap<String, bool>,
+}
+
+fn metadata_field_presence(meta: &GameMetadata) -> std::collections::BTreeMap<String, bool> {
+    let mut out = std::collections::BTreeMap::new();
+    if let Ok(serde_json::Value::Object(map)) = serde_json::to_value(meta) {
+        for (key, value) in map {
+            if key == "source" || key == "source_url" {
+                continue;
+            }
+            let filled = match value {
+                serde_json::Value::Null => false,
+                serde_json::Value::String(s) => !s.trim().is_empty(),
+                serde_json::Value::Array(a) => !a.is_empty(),
+                _ => true,
+            };
+            out.insert(key, filled);
+        }
+    }
+    out
+}
+
+/// Diagnostic: scrapes the built-in sample pages plus any given in `urls` and
+/// reports which metadata fields came back filled, to spot scrapers broken
+/// by a site redesign. `urls` maps source id ("f95", "dlsite", "vndb",
+/// "mangagamer", "johren", "fakku", "itch", "steam") to a page to test and is
+/// merged over the built-in samples. Results are not written to the metadata
+/// cache. Requires the `scraper_self_test` backend setting.
+#[tauri::command]
+pub async fn test_scrapers(
+    urls: Option<HashMap<String, String>>,
+) -> Result<Vec<ScraperTestResult>, String> {
+    if !crate::settings::current().scraper_self_test {
+        return Err("Enable the scraper self-test in settings first".to_string());
+    }
+    // The raw scrapers skip the metadata cache and the cover preference, but
+    // F95zone, DLsite and FAKKU still send the signed-in session cookies.
+    crate::applock::ensure_unlocked()?;
+    let mut targets: Vec<(String, String)> = SCRAPER_SAMPLE_URLS
+        .iter()
+        .map(|(source, url)| (source.to_string(), url.to_string()))
+        .collect();
+    for (source, url) in urls.unwrap_or_default() {
+        targets.retain(|(s, _)| *s != source);
+        targets.push((source, url));
+    }
+    targets.sort();
+
+    let mut results = Vec::new();
+    for (source, url) in targets {
+        let fetched = match source.as_str() {
+            "f95" => scrape_f95_metadata(url.clone()).await,
+            "dlsite" => scrape_dlsite_metadata(url.clone()).await,
+            "vndb" => scrape_vndb_metadata(url.clone()).await,
+            "steam" => scrape_steam_metadata(url.clone()).await,
+            "mangagamer" | "johren" | "fakku" | "itch" => fetch_store_metadata(url.clone()).await,
+            other => Err(format!("Unknown source '{other}'")),
+        };
+        let (error, fields) = match fetched {
+            Ok(meta) => (None, metadata_field_presence(&meta)),
+            Err(e) => (Some(e), Default::default()),
+        };
+        results.push(ScraperTestResult {
+            source,
+            url,
+            error,
+            fields,
+        });
+    }
+    Ok(results)
+}
+
 #[derive(Serialize, Deserialize, Clone, Default, Debug)]
 pub struct SearchResultItem {
     pub title: String,
     pub url: String,
     pub cover_url: Option<String>,
     pub source: String,
+    /// Fuzzy similarity (0.0–1.0) between the search query and `title`.
+    #[serde(default)]
+    pub score: f32,
 }
 
 fn normalize_search_query(raw: &str) -> String {
@@ -1577,6 +3119,59 @@ fn normalize_search_query(raw: &str) -> String {
     }
 }
 
+fn normalize_for_match(raw: &str) -> String {
+    normalize_search_query(raw)
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Fuzzy similarity in `[0, 1]` between two game titles.
+/// Both sides go through `normalize_search_query` (bracketed tags and
+/// separators removed), then are compared with a Dice coefficient over
+/// character bigrams. Containment of one title in the other counts as a
+/// strong match since store titles often append subtitles or editions.
+pub(crate) fn title_similarity(a: &str, b: &str) -> f32 {
+    let na = normalize_for_match(a);
+    let nb = normalize_for_match(b);
+    if na.is_empty() || nb.is_empty() {
+        return 0.0;
+    }
+    if na == nb {
+        return 1.0;
+    }
+
+    let bigrams = |s: &str| -> Vec<(char, char)> {
+        let chars: Vec<char> = s.chars().filter(|c| !c.is_whitespace()).collect();
+        chars.windows(2).map(|w| (w[0], w[1])).collect()
+    };
+    let ba = bigrams(&na);
+    let mut bb = bigrams(&nb);
+    let dice = if ba.is_empty() || bb.is_empty() {
+        0.0
+    } else {
+        let total = (ba.len() + bb.len()) as f32;
+        let mut shared = 0usize;
+        for g in &ba {
+            if let Some(pos) = bb.iter().position(|x| x == g) {
+                bb.swap_remove(pos);
+                shared += 1;
+            }
+        }
+        2.0 * shared as f32 / total
+    };
+
+    if na.contains(&nb) || nb.contains(&na) {
+        dice.max(0.85)
+    } else {
+        dice
+    }
+}
+
 fn build_query_variants(query: &str) -> Vec<String> {
     let mut v = Vec::<String>::new();
     let base = query.trim();
@@ -1642,11 +3237,11 @@ async fn fetch_vndb_alias_queries(query: &str) -> Vec<String> {
         "fields": "title,alttitle",
         "results": 5
     });
-    let resp = match reqwest::Client::new()
+    let resp = match metadata_client()
         .post("https://api.vndb.org/kana/vn")
         .header("User-Agent", "LIBMALY/1.3")
         .json(&body)
-        .send()
+        .send_retrying()
         .await
     {
         Ok(r) if r.status().is_success() => r,
@@ -1669,7 +3264,20 @@ async fn fetch_vndb_alias_queries(query: &str) -> Vec<String> {
     out
 }
 
+/// Index into the f95checker endpoint list that last returned results;
+/// `usize::MAX` until one has.
+static F95CHECKER_LAST_OK: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// While rate-limited, f95checker is not queried until this instant.
+static F95CHECKER_BACKOFF_UNTIL: Mutex<Option<std::time::Instant>> = Mutex::new(None);
+
 async fn fetch_f95checker_suggestions(query: &str) -> Vec<SearchResultItem> {
+    if let Some(until) = *F95CHECKER_BACKOFF_UNTIL.lock().unwrap() {
+        if std::time::Instant::now() < until {
+            return Vec::new();
+        }
+    }
+
     let encoded = urlencoding::encode(query);
     let candidates = [
         format!("https://api.f95checker.dev/search?query={encoded}"),
@@ -1678,13 +3286,35 @@ async fn fetch_f95checker_suggestions(query: &str) -> Vec<SearchResultItem> {
         format!("https://api.f95checker.dev/v1/search?q={encoded}"),
     ];
 
-    for url in candidates {
-        let resp = match reqwest::Client::new()
-            .get(&url)
+    // Try the shape that worked last time first, then the rest in order.
+    let last_ok = F95CHECKER_LAST_OK.load(Ordering::Relaxed);
+    let mut order: Vec<usize> = (0..candidates.len()).collect();
+    if last_ok < candidates.len() {
+        order.retain(|&i| i != last_ok);
+        order.insert(0, last_ok);
+    }
+
+    for idx in order {
+        let url = &candidates[idx];
+        let resp = match metadata_client()
+            .get(url)
             .header("User-Agent", "LIBMALY/1.3")
-            .send()
+            .send_retrying()
             .await
         {
+            Ok(r) if r.status().as_u16() == 429 => {
+                // Every endpoint shape hits the same API; stop instead of
+                // burning the remaining attempts.
+                let wait = r
+                    .headers()
+                    .get("Retry-After")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.trim().parse::<u64>().ok())
+                    .unwrap_or(60);
+                *F95CHECKER_BACKOFF_UNTIL.lock().unwrap() =
+                    Some(std::time::Instant::now() + std::time::Duration::from_secs(wait));
+                return Vec::new();
+            }
             Ok(r) if r.status().is_success() => r,
             _ => continue,
         };
@@ -1750,9 +3380,11 @@ async fn fetch_f95checker_suggestions(query: &str) -> Vec<SearchResultItem> {
                 url: normalize_f95_thread_url(&link),
                 cover_url,
                 source: "F95zone".into(),
+                score: 0.0,
             });
         }
         if !out.is_empty() {
+            F95CHECKER_LAST_OK.store(idx, Ordering::Relaxed);
             return out;
         }
     }
@@ -1786,12 +3418,12 @@ async fn fetch_ddg_site_suggestions(
     limit: usize,
 ) -> Vec<SearchResultItem> {
     let ddg_body = format!("q=site:{site}+{}", urlencoding::encode(query));
-    let resp = match reqwest::Client::new()
+    let resp = match metadata_client()
         .post("https://lite.duckduckgo.com/lite/")
         .header("User-Agent", "Mozilla/5.0")
         .header("Content-Type", "application/x-www-form-urlencoded")
         .body(ddg_body)
-        .send()
+        .send_retrying()
         .await
     {
         Ok(r) => r,
@@ -1822,101 +3454,203 @@ async fn fetch_ddg_site_suggestions(
             url: normalize_store_suggestion_url(&url, source),
             cover_url: None,
             source: source.to_string(),
+            score: 0.0,
         });
     }
     out
 }
 
-#[tauri::command]
-pub async fn search_suggest_links(query: String) -> Result<Vec<SearchResultItem>, String> {
-    let mut results = Vec::new();
-    let mut seen_urls = std::collections::HashSet::<String>::new();
-    let cache_key = normalize_search_query(&query).to_lowercase();
+/// Accept-Language sent with DLsite storefront requests; English first so
+/// titles come back translated where DLsite has them.
+const DLSITE_ACCEPT_LANGUAGE: &str = "en-US,en;q=0.9,ja;q=0.8";
 
-    let mut queries = build_query_variants(&query);
-    let alias_queries = fetch_vndb_alias_queries(&query).await;
-    for q in alias_queries {
-        if !queries.iter().any(|x| x.eq_ignore_ascii_case(&q)) {
-            queries.push(q);
+/// DLsite hides adult sections behind an age gate and picks the storefront
+/// language from a cookie, so seed both before searching.
+fn dlsite_prime_search_cookies() {
+    let store = DLSITE_SESSION.store();
+    let mut locked = store.lock().unwrap();
+    if let Ok(url) = reqwest::Url::parse("https://www.dlsite.com/") {
+        for cookie in [
+            "adultchecked=1; Domain=.dlsite.com; Path=/",
+            "locale=en-us; Domain=.dlsite.com; Path=/",
+        ] {
+            let _ = locked.parse(cookie, &url);
         }
     }
-    queries.truncate(8);
+}
 
-    let mut push_result = |item: SearchResultItem| -> bool {
-        let key = item.url.trim().to_lowercase();
-        if key.is_empty() || !seen_urls.insert(key) {
-            return false;
-        }
-        results.push(item);
-        true
-    };
+/// Extracts a DLsite product id (RJ123456, VJ012345, BJ…) from a work URL.
+fn dlsite_product_id(url: &str) -> Option<String> {
+    let idx = url.find("/product_id/")?;
+    let rest = &url[idx + "/product_id/".len()..];
+    let id: String = rest
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect();
+    if id.is_empty() {
+        None
+    } else {
+        Some(id.to_uppercase())
+    }
+}
 
-    // DLsite query (try multiple variants)
-    let mut dl_count = 0usize;
-    for q in &queries {
-        if dl_count >= 4 {
+async fn fetch_dlsite_suggestions(query: &str, section: &str, limit: usize) -> Vec<SearchResultItem> {
+    dlsite_prime_search_cookies();
+    let dlsite_url = format!(
+        "https://www.dlsite.com/{section}/fsr/=/keyword/{}",
+        urlencoding::encode(query)
+    );
+    let resp = match dlsite_http()
+        .get(&dlsite_url)
+        .header("Accept-Language", DLSITE_ACCEPT_LANGUAGE)
+        .send_retrying()
+        .await
+    {
+        Ok(r) => r,
+        Err(_) => return Vec::new(),
+    };
+    let body = match resp.text().await {
+        Ok(b) => b,
+        Err(_) => return Vec::new(),
+    };
+    let doc = Html::parse_document(&body);
+    let item_sel = sel(".search_result_img_box_inner");
+    let a_sel = sel("a");
+    let img_sel = sel("img");
+    let mut out = Vec::<SearchResultItem>::new();
+    for el in doc.select(&item_sel) {
+        if out.len() >= limit {
             break;
         }
-        let dlsite_url = format!(
-            "https://www.dlsite.com/home/fsr/=/keyword/{}",
-            urlencoding::encode(q)
-        );
-        if let Ok(resp) = dlsite_http()
-            .get(&dlsite_url)
-            .header("Accept-Language", "en-US,en;q=0.9,ja;q=0.8")
-            .send()
-            .await
-        {
-            if let Ok(body) = resp.text().await {
-                let doc = Html::parse_document(&body);
-                let item_sel = sel(".search_result_img_box_inner");
-                for el in doc.select(&item_sel) {
-                    if dl_count >= 4 {
-                        break;
-                    }
-                    let a_sel = sel("a");
-                    let img_sel = sel("img");
-                    if let Some(a) = el.select(&a_sel).next() {
-                        let title = a
-                            .attr("title")
-                            .or_else(|| {
-                                let img = el.select(&img_sel).next()?;
-                                img.attr("alt")
-                            })
-                            .unwrap_or("Unknown")
-                            .to_string();
-                        let url = a.attr("href").unwrap_or("").to_string();
-                        let cover_url = el
-                            .select(&img_sel)
-                            .next()
-                            .and_then(|i| i.attr("src"))
-                            .map(|s| {
-                                if s.starts_with("//") {
-                                    format!("https:{}", s)
-                                } else {
-                                    s.to_string()
-                                }
-                            });
-                        if !url.is_empty()
-                            && !url.contains("category")
-                            && push_result(SearchResultItem {
-                                title,
-                                url,
-                                cover_url,
-                                source: "DLsite".into(),
-                            })
-                        {
-                            dl_count += 1;
-                        }
+        let Some(a) = el.select(&a_sel).next() else {
+            continue;
+        };
+        let title = a
+            .attr("title")
+            .or_else(|| {
+                let img = el.select(&img_sel).next()?;
+                img.attr("alt")
+            })
+            .unwrap_or("Unknown")
+            .to_string();
+        let url = a.attr("href").unwrap_or("").to_string();
+        if url.is_empty() || url.contains("category") {
+            continue;
+        }
+        let cover_url = el
+            .select(&img_sel)
+            .next()
+            .and_then(|i| i.attr("src"))
+            .map(|s| {
+                if s.starts_with("//") {
+                    format!("https:{}", s)
+                } else {
+                    s.to_string()
+                }
+            });
+        out.push(SearchResultItem {
+            title,
+            url,
+            cover_url,
+            source: "DLsite".into(),
+            score: 0.0,
+        });
+    }
+    out
+}
+
+/// Scores every item against `query` and orders by score, best first.
+/// By default items stay grouped by source (in the order sources were
+/// queried); `global` mixes sources into a single ranking.
+fn rank_suggestions(query: &str, results: &mut [SearchResultItem], global: bool) {
+    for item in results.iter_mut() {
+        item.score = title_similarity(query, &item.title);
+    }
+    let mut group_order = Vec::<String>::new();
+    for item in results.iter() {
+        if !group_order.contains(&item.source) {
+            group_order.push(item.source.clone());
+        }
+    }
+    let group_of = |item: &SearchResultItem| {
+        group_order
+            .iter()
+            .position(|s| *s == item.source)
+            .unwrap_or(usize::MAX)
+    };
+    results.sort_by(|a, b| {
+        let by_score = b.score.total_cmp(&a.score);
+        if global {
+            by_score
+        } else {
+            group_of(a).cmp(&group_of(b)).then(by_score)
+        }
+    });
+}
+
+/// Appends `item` unless its URL was already taken. Returns whether it was added.
+fn push_unique_url(
+    seen: &mut HashSet<String>,
+    out: &mut Vec<SearchResultItem>,
+    item: SearchResultItem,
+) -> bool {
+    let key = item.url.trim().to_lowercase();
+    if key.is_empty() || !seen.insert(key) {
+        return false;
+    }
+    out.push(item);
+    true
+}
+
+/// Runs `fut` only for an enabled source.
+async fn gated(
+    enabled: bool,
+    fut: impl std::future::Future<Output = Vec<SearchResultItem>>,
+) -> Vec<SearchResultItem> {
+    if enabled {
+        fut.await
+    } else {
+        Vec::new()
+    }
+}
+
+/// DLsite suggestions (several query variants, across every configured section).
+async fn suggest_dlsite(queries: &[String]) -> Vec<SearchResultItem> {
+    let mut results = Vec::new();
+    let mut seen_urls = HashSet::<String>::new();
+    let dlsite_sections = crate::settings::current().dlsite_sections;
+    let mut seen_dlsite_ids = HashSet::<String>::new();
+    for section in &dlsite_sections {
+        let mut dl_count = 0usize;
+        for q in queries {
+            if dl_count >= 4 {
+                break;
+            }
+            for item in fetch_dlsite_suggestions(q, section, 4).await {
+                if dl_count >= 4 {
+                    break;
+                }
+                // The same work can surface under several sections; key on the product id.
+                if let Some(id) = dlsite_product_id(&item.url) {
+                    if !seen_dlsite_ids.insert(id) {
+                        continue;
                     }
                 }
+                if push_unique_url(&mut seen_urls, &mut results, item) {
+                    dl_count += 1;
+                }
             }
         }
     }
+    results
+}
 
-    // DuckDuckGo lite for F95zone (try multiple variants)
+/// F95zone suggestions: F95Checker first, DuckDuckGo lite for the misses.
+async fn suggest_f95(queries: &[String]) -> Vec<SearchResultItem> {
+    let mut results = Vec::new();
+    let mut seen_urls = HashSet::<String>::new();
     let mut f95_count = 0usize;
-    for q in &queries {
+    for q in queries {
         if f95_count >= 4 {
             break;
         }
@@ -1925,7 +3659,7 @@ pub async fn search_suggest_links(query: String) -> Result<Vec<SearchResultItem>
             if f95_count >= 4 {
                 break;
             }
-            if push_result(item) {
+            if push_unique_url(&mut seen_urls, &mut results, item) {
                 f95_count += 1;
             }
         }
@@ -1934,12 +3668,12 @@ pub async fn search_suggest_links(query: String) -> Result<Vec<SearchResultItem>
         }
 
         let ddg_body = format!("q=site:f95zone.to+{}", urlencoding::encode(q));
-        if let Ok(resp) = reqwest::Client::new()
+        if let Ok(resp) = metadata_client()
             .post("https://lite.duckduckgo.com/lite/")
             .header("User-Agent", "Mozilla/5.0")
             .header("Content-Type", "application/x-www-form-urlencoded")
             .body(ddg_body)
-            .send()
+            .send_retrying()
             .await
         {
             if let Ok(body) = resp.text().await {
@@ -1949,15 +3683,20 @@ pub async fn search_suggest_links(query: String) -> Result<Vec<SearchResultItem>
                     if f95_count >= 4 {
                         break;
                     }
-                let url = el.attr("href").unwrap_or("").to_string();
-                if url.contains("f95zone.to/threads") {
-                    let title = el.text().collect::<String>().trim().to_string();
-                    if push_result(SearchResultItem {
-                        title,
-                        url: normalize_f95_thread_url(&url),
-                        cover_url: None,
-                        source: "F95zone".into(),
-                    }) {
+                    let url = el.attr("href").unwrap_or("").to_string();
+                    if url.contains("f95zone.to/threads") {
+                        let title = el.text().collect::<String>().trim().to_string();
+                        if push_unique_url(
+                            &mut seen_urls,
+                            &mut results,
+                            SearchResultItem {
+                                title,
+                                url: normalize_f95_thread_url(&url),
+                                cover_url: None,
+                                source: "F95zone".into(),
+                                score: 0.0,
+                            },
+                        ) {
                             f95_count += 1;
                         }
                     }
@@ -1965,10 +3704,15 @@ pub async fn search_suggest_links(query: String) -> Result<Vec<SearchResultItem>
             }
         }
     }
+    results
+}
 
-    // VNDB direct API suggestions (stable, avoids DDG inconsistencies)
+/// VNDB direct API suggestions (stable, avoids DDG inconsistencies).
+async fn suggest_vndb(queries: &[String]) -> Vec<SearchResultItem> {
+    let mut results = Vec::new();
+    let mut seen_urls = HashSet::<String>::new();
     let mut vndb_count = 0usize;
-    for q in &queries {
+    for q in queries {
         if vndb_count >= 5 {
             break;
         }
@@ -1977,103 +3721,433 @@ pub async fn search_suggest_links(query: String) -> Result<Vec<SearchResultItem>
             "fields": "id,title,image.url",
             "results": 6
         });
-        if let Ok(resp) = reqwest::Client::new()
+        let Ok(resp) = metadata_client()
             .post("https://api.vndb.org/kana/vn")
             .header("User-Agent", "LIBMALY/1.3")
             .json(&body)
-            .send()
+            .send_retrying()
             .await
-        {
-            if resp.status().is_success() {
-                if let Ok(parsed) = resp.json::<VndbResponse>().await {
-                    for item in parsed.results.unwrap_or_default() {
-                        if vndb_count >= 5 {
-                            break;
-                        }
-                        let Some(id) = item.id.clone() else { continue; };
-                        let title = item
-                            .title
-                            .clone()
-                            .or(item.alttitle.clone())
-                            .unwrap_or_else(|| id.clone());
-                        let url = format!("https://vndb.org/{id}");
-                        let cover_url = item.image.and_then(|i| i.url);
-                        if push_result(SearchResultItem {
-                            title,
-                            url,
-                            cover_url,
-                            source: "VNDB".into(),
-                        }) {
-                            vndb_count += 1;
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    // MangaGamer suggestions via DDG site search.
-    let mut mg_count = 0usize;
-    for q in &queries {
-        if mg_count >= 3 {
-            break;
+        else {
+            continue;
+        };
+        if !resp.status().is_success() {
+            continue;
         }
-        for item in fetch_ddg_site_suggestions(q, "mangagamer.com", "MangaGamer", 3).await {
-            if mg_count >= 3 {
+        let Ok(parsed) = resp.json::<VndbResponse>().await else {
+            continue;
+        };
+        for item in parsed.results.unwrap_or_default() {
+            if vndb_count >= 5 {
                 break;
             }
-            if push_result(item) {
-                mg_count += 1;
+            let Some(id) = item.id.clone() else {
+                continue;
+            };
+            let title = item
+                .title
+                .clone()
+                .or(item.alttitle.clone())
+                .unwrap_or_else(|| id.clone());
+            let url = format!("https://vndb.org/{id}");
+            let cover_url = item.image.and_then(|i| i.url);
+            let item = SearchResultItem {
+                title,
+                url,
+                cover_url,
+                source: "VNDB".into(),
+                score: 0.0,
+            };
+            if push_unique_url(&mut seen_urls, &mut results, item) {
+                vndb_count += 1;
             }
         }
     }
+    results
+}
 
-    // Johren suggestions via DDG site search.
-    let mut johren_count = 0usize;
-    for q in &queries {
-        if johren_count >= 3 {
+/// Up to `cap` suggestions for `site` via DuckDuckGo site search
+/// (MangaGamer, Johren, FAKKU).
+async fn suggest_ddg_site(
+    queries: &[String],
+    site: &str,
+    source: &str,
+    cap: usize,
+) -> Vec<SearchResultItem> {
+    let mut results = Vec::new();
+    let mut seen_urls = HashSet::<String>::new();
+    for q in queries {
+        if results.len() >= cap {
             break;
         }
-        for item in fetch_ddg_site_suggestions(q, "johren.net", "Johren", 3).await {
-            if johren_count >= 3 {
+        for item in fetch_ddg_site_suggestions(q, site, source, cap).await {
+            if results.len() >= cap {
                 break;
             }
-            if push_result(item) {
-                johren_count += 1;
-            }
+            push_unique_url(&mut seen_urls, &mut results, item);
         }
     }
+    results
+}
 
-    // FAKKU suggestions via DDG site search.
-    let mut fakku_count = 0usize;
-    for q in &queries {
-        if fakku_count >= 3 {
-            break;
-        }
-        for item in fetch_ddg_site_suggestions(q, "fakku.net", "FAKKU", 3).await {
-            if fakku_count >= 3 {
-                break;
+/// Newest `request_id` passed to `search_suggest_links`.
+static LATEST_SEARCH: AtomicU64 = AtomicU64::new(0);
+const SEARCH_CANCEL_POLL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Resolves once a newer search than `request_id` has started; never for
+/// searches without an id.
+async fn search_superseded(request_id: Option<u64>) {
+    let Some(id) = request_id else {
+        return std::future::pending().await;
+    };
+    while id >= LATEST_SEARCH.load(Ordering::SeqCst) {
+        tokio::time::sleep(SEARCH_CANCEL_POLL).await;
+    }
+}
+
+/// `sources` picks the sites to query: `None` follows the `search_sources`
+/// setting, an empty list queries all of them. Searches typed in quick
+/// succession should pass increasing `request_id`s: once a newer one starts,
+/// an older one drops its pending requests and returns no results.
+#[tauri::command]
+pub async fn search_suggest_links(
+    query: String,
+    sort_globally: Option<bool>,
+    sources: Option<Vec<String>>,
+    request_id: Option<u64>,
+) -> Result<Vec<SearchResultItem>, String> {
+    if let Some(id) = request_id {
+        LATEST_SEARCH.fetch_max(id, Ordering::SeqCst);
+    }
+    let sources = match sources {
+        Some(list) => {
+            if let Some(bad) = list
+                .iter()
+                .find(|s| !crate::settings::SEARCH_SOURCES.contains(&s.as_str()))
+            {
+                return Err(format!("Unknown search source: {bad}"));
             }
-            if push_result(item) {
-                fakku_count += 1;
+            list
+        }
+        None => crate::settings::current().search_sources,
+    };
+    // An explicit empty list means every source.
+    let sources = if sources.is_empty() {
+        crate::settings::SEARCH_SOURCES
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    } else {
+        sources
+    };
+    let enabled = |id: &str| sources.iter().any(|s| s == id);
+
+    let mut cache_key = normalize_search_query(&query).to_lowercase();
+    // Results depend on the sources queried, so a subset gets its own entry.
+    if !cache_key.is_empty() && !crate::settings::SEARCH_SOURCES.iter().all(|s| enabled(s)) {
+        let mut ids: Vec<&str> = sources.iter().map(|s| s.as_str()).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        cache_key = format!("{cache_key}|{}", ids.join(","));
+    }
+
+    let lookup = async {
+        let mut queries = build_query_variants(&query);
+        // Alias lookup goes to VNDB too, so it is skipped with that source.
+        if enabled("vndb") {
+            let alias_queries = fetch_vndb_alias_queries(&query).await;
+            for q in alias_queries {
+                if !queries.iter().any(|x| x.eq_ignore_ascii_case(&q)) {
+                    queries.push(q);
+                }
             }
         }
+        queries.truncate(8);
+
+        // Sources are independent, so they run side by side; each keeps its own cap.
+        let (dlsite, f95, vndb, mangagamer, johren, fakku) = tokio::join!(
+            gated(enabled("dlsite"), suggest_dlsite(&queries)),
+            gated(enabled("f95"), suggest_f95(&queries)),
+            gated(enabled("vndb"), suggest_vndb(&queries)),
+            gated(
+                enabled("mangagamer"),
+                suggest_ddg_site(&queries, "mangagamer.com", "MangaGamer", 3)
+            ),
+            gated(
+                enabled("johren"),
+                suggest_ddg_site(&queries, "johren.net", "Johren", 3)
+            ),
+            gated(
+                enabled("fakku"),
+                suggest_ddg_site(&queries, "fakku.net", "FAKKU", 3)
+            ),
+        );
+        [dlsite, f95, vndb, mangagamer, johren, fakku]
+    };
+    let per_source = tokio::select! {
+        per_source = lookup => per_source,
+        _ = search_superseded(request_id) => return Ok(Vec::new()),
+    };
+
+    let mut results = Vec::new();
+    let mut seen_urls = HashSet::<String>::new();
+    for item in per_source.into_iter().flatten() {
+        push_unique_url(&mut seen_urls, &mut results, item);
     }
 
+    rank_suggestions(&query, &mut results, sort_globally.unwrap_or(false));
+
     // Cache successful lookups to shield against transient DDG failures on repeated queries.
     if !results.is_empty() && !cache_key.is_empty() {
-        suggest_cache()
-            .lock()
-            .unwrap()
-            .insert(cache_key.clone(), results.clone());
+        suggest_cache_put(cache_key.clone(), results.clone());
     }
 
     // If all live sources failed, fall back to last successful cached result for this query.
     if results.is_empty() && !cache_key.is_empty() {
-        if let Some(cached) = suggest_cache().lock().unwrap().get(&cache_key).cloned() {
+        if let Some(mut cached) = suggest_cache_get(&cache_key) {
+            rank_suggestions(&query, &mut cached, sort_globally.unwrap_or(false));
             return Ok(cached);
         }
     }
 
     Ok(results)
 }
+
+// ── Metadata prefetch ──────────────────────────────────────────────────────
+// Opt-in (`prefetch_metadata` setting): after a scan the frontend hands over
+// the games it has no metadata for. Each is searched, and a confident match
+// is fetched through the metadata cache and announced with a
+// `metadata-prefetched` event. Work is spread over a couple of workers with
+// a pause between games so the sources are not hammered.
+
+/// Games searched at the same time.
+const PREFETCH_CONCURRENCY: usize = 2;
+/// Pause a worker takes after each game.
+const PREFETCH_SPACING: std::time::Duration = std::time::Duration::from_millis(1500);
+/// Lowest search score accepted as the same game.
+const PREFETCH_MIN_SCORE: f32 = 0.9;
+
+/// Game paths (as `path_dedup_key`) already tried this session, so repeated
+/// scans do not search the same games again.
+static PREFETCH_TRIED: std::sync::OnceLock<Mutex<HashSet<String>>> = std::sync::OnceLock::new();
+
+#[derive(Deserialize)]
+pub struct PrefetchGame {
+    pub path: String,
+    pub name: String,
+}
+
+#[derive(Serialize, Clone)]
+pub struct PrefetchedMetadata {
+    pub path: String,
+    pub matched: SearchResultItem,
+    pub meta: GameMetadata,
+}
+
+/// Best search hit for `name` when it is close enough to trust without
+/// asking the user.
+async fn auto_match_game(name: &str) -> Option<SearchResultItem> {
+    let results = search_suggest_links(name.to_string(), Some(true), None, None)
+        .await
+        .ok()?;
+    results
+        .into_iter()
+        .filter(|r| r.score >= PREFETCH_MIN_SCORE)
+        .max_by(|a, b| a.score.total_cmp(&b.score))
+}
+
+/// Fetches `item` with the fetcher for its search source.
+async fn fetch_matched(item: &SearchResultItem) -> Result<GameMetadata, String> {
+    let url = item.url.clone();
+    match item.source.as_str() {
+        "F95zone" => fetch_f95_metadata(url, None, None).await,
+        "DLsite" => fetch_dlsite_metadata(url, None, None).await,
+        "VNDB" => fetch_vndb_metadata(url, None, None).await,
+        "MangaGamer" => fetch_mangagamer_metadata(url, None, None).await,
+        "Johren" => fetch_johren_metadata(url, None, None).await,
+        "FAKKU" => fetch_fakku_metadata(url, None, None).await,
+        other => Err(format!("No fetcher for source '{other}'")),
+    }
+}
+
+/// The `cached_scrape` source id `fetch_matched` files `item` under.
+fn matched_cache_source(item: &SearchResultItem) -> Option<&'static str> {
+    match item.source.as_str() {
+        "F95zone" => Some("f95"),
+        "DLsite" => Some("dlsite"),
+        "VNDB" => Some("vndb"),
+        "MangaGamer" => Some("mangagamer"),
+        "Johren" => Some("johren"),
+        "FAKKU" => Some("fakku"),
+        _ => None,
+    }
+}
+
+async fn prefetch_one(app: &tauri::AppHandle, game: PrefetchGame) {
+    use tauri::Emitter;
+    let Some(matched) = auto_match_game(&game.name).await else {
+        return;
+    };
+    // Anything already cached was fetched (and applied) before; refetching
+    // would only spend a request and re-emit stale news to the frontend.
+    if matched_cache_source(&matched)
+        .is_some_and(|source| cached_metadata(source, &matched.url).is_some())
+    {
+        return;
+    }
+    match fetch_matched(&matched).await {
+        Ok(meta) => {
+            let _ = app.emit(
+                "metadata-prefetched",
+                &PrefetchedMetadata {
+                    path: game.path,
+                    matched,
+                    meta,
+                },
+            );
+        }
+        Err(e) => crate::push_rust_log(
+            Some(app),
+            "warn",
+            format!("Metadata prefetch for '{}' failed: {e}", game.name),
+        ),
+    }
+}
+
+/// Queues `games` for background metadata matching and returns how many were
+/// queued. Does nothing when the `prefetch_metadata` setting is off or
+/// `offline_mode` is on. Games tried earlier in the session are skipped, as
+/// are matches whose page is already in the metadata cache.
+#[tauri::command]
+pub fn prefetch_metadata(app: tauri::AppHandle, games: Vec<PrefetchGame>) -> usize {
+    let settings = crate::settings::current();
+    if !settings.prefetch_metadata || settings.offline_mode {
+        return 0;
+    }
+    let queue: Vec<PrefetchGame> = {
+        let mut tried = PREFETCH_TRIED
+            .get_or_init(|| Mutex::new(HashSet::new()))
+            .lock()
+            .unwrap();
+        games
+            .into_iter()
+            .filter(|g| !g.name.trim().is_empty())
+            .filter(|g| tried.insert(crate::path_dedup_key(&g.path)))
+            .collect()
+    };
+    let queued = queue.len();
+    if queued == 0 {
+        return 0;
+    }
+
+    let queue = Arc::new(Mutex::new(queue.into_iter()));
+    for _ in 0..PREFETCH_CONCURRENCY.min(queued) {
+        let app = app.clone();
+        let queue = Arc::clone(&queue);
+        tauri::async_runtime::spawn(async move {
+            loop {
+                // Offline mode can be switched on while the queue drains.
+                if crate::settings::current().offline_mode {
+                    return;
+                }
+                let Some(game) = queue.lock().unwrap().next() else {
+                    return;
+                };
+                prefetch_one(&app, game).await;
+                tokio::time::sleep(PREFETCH_SPACING).await;
+            }
+        });
+    }
+    crate::push_rust_log(
+        Some(&app),
+        "info",
+        format!("Prefetching metadata for {queued} game(s)"),
+    );
+    queued
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_session(id: &'static str, cookie_file: &'static str) -> SiteSession {
+        SiteSession::new(
+            id,
+            cookie_file,
+            "https://example.com/",
+            "en-US,en;q=0.9",
+            SessionCheck::FinalUrl(&[]),
+            &["sid"],
+        )
+    }
+
+    fn temp_cookie_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("libmaly-test-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn site_sessions_keep_separate_cookie_stores() {
+        let files: HashSet<&str> = SITE_SESSIONS.iter().map(|s| s.cookie_file).collect();
+        assert_eq!(files.len(), SITE_SESSIONS.len());
+
+        let a = test_session("a", "a_cookies.json");
+        let b = test_session("b", "b_cookies.json");
+        for session in [&a, &b] {
+            let path = temp_cookie_path(session.cookie_file);
+            *session.store.lock().unwrap() = Some(session.load_or_new_store(&path));
+        }
+        assert!(!Arc::ptr_eq(&a.store(), &b.store()));
+
+        let url = reqwest::Url::parse("https://example.com/").unwrap();
+        a.store()
+            .lock()
+            .unwrap()
+            .parse("sid=abc; Max-Age=3600", &url)
+            .unwrap();
+        assert!(a.expiry().has_session);
+        assert_eq!(b.store().lock().unwrap().iter_any().count(), 0);
+        assert!(!b.expiry().has_session);
+    }
+
+    #[test]
+    fn expired_cookies_are_dropped_from_a_loaded_store() {
+        let url = reqwest::Url::parse("https://example.com/").unwrap();
+        let expired =
+            cookie_store::Cookie::parse("sid=old; Expires=Wed, 01 Jan 2020 00:00:00 GMT", &url)
+                .unwrap()
+                .into_owned();
+        let live = cookie_store::Cookie::parse("theme=dark; Max-Age=3600", &url)
+            .unwrap()
+            .into_owned();
+        assert!(expired.is_expired());
+        let saved = CookieStore::from_cookies(
+            [expired, live]
+                .into_iter()
+                .map(Ok::<_, std::convert::Infallible>),
+            true,
+        )
+        .unwrap();
+        let path = temp_cookie_path("expired_cookies.json");
+        let mut file = std::fs::File::create(&path).unwrap();
+        #[allow(deprecated)]
+        saved
+            .save_incl_expired_and_nonpersistent_json(&mut file)
+            .unwrap();
+        drop(file);
+
+        let session = test_session("expired", "expired_cookies.json");
+        *session.store.lock().unwrap() = Some(session.load_or_new_store(&path));
+        let store = session.store();
+        let names: Vec<String> = store
+            .lock()
+            .unwrap()
+            .iter_any()
+            .map(|c| c.name().to_string())
+            .collect();
+        assert_eq!(names, ["theme"]);
+
+        let expiry = session.expiry();
+        assert!(!expiry.has_session);
+        assert!(expiry.expired);
+        let _ = std::fs::remove_file(&path);
+    }
+}
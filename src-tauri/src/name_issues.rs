@@ -0,0 +1,152 @@
+use crate::Game;
+use serde::Serialize;
+
+// ── Garbled names ──────────────────────────────────────────────────────────
+// Japanese folder names copied between systems with different code pages
+// often arrive as replacement characters or mojibake (Shift-JIS or UTF-8
+// bytes shown as Windows-1252). These checks flag such names so the user can
+// rename the folder or take the title from fetched metadata instead.
+
+/// Windows-1252 characters for bytes 0x80..=0x9F, indexed by `byte - 0x80`.
+/// Unassigned bytes are `None`.
+const CP1252_HIGH: [Option<char>; 32] = [
+    Some('€'),
+    None,
+    Some('‚'),
+    Some('ƒ'),
+    Some('„'),
+    Some('…'),
+    Some('†'),
+    Some('‡'),
+    Some('ˆ'),
+    Some('‰'),
+    Some('Š'),
+    Some('‹'),
+    Some('Œ'),
+    None,
+    Some('Ž'),
+    None,
+    None,
+    Some('‘'),
+    Some('’'),
+    Some('“'),
+    Some('”'),
+    Some('•'),
+    Some('–'),
+    Some('—'),
+    Some('˜'),
+    Some('™'),
+    Some('š'),
+    Some('›'),
+    Some('œ'),
+    None,
+    Some('ž'),
+    Some('Ÿ'),
+];
+
+#[derive(Serialize)]
+pub struct NameIssue {
+    pub path: String,
+    pub name: String,
+    /// "replacement-char" or "mojibake".
+    pub kind: String,
+    pub suggestion: String,
+    /// The name as it was most likely meant, when it could be recovered.
+    pub corrected_name: Option<String>,
+}
+
+/// The bytes `name` came from if it is text decoded as Windows-1252 (or
+/// Latin-1 for the C1 range). `None` when a character has no such byte.
+fn cp1252_bytes(name: &str) -> Option<Vec<u8>> {
+    name.chars()
+        .map(|c| match c as u32 {
+            0..=0xFF => Some(c as u8),
+            _ => CP1252_HIGH
+                .iter()
+                .position(|&m| m == Some(c))
+                .map(|i| 0x80 + i as u8),
+        })
+        .collect()
+}
+
+/// True when every non-ASCII byte pairs up as a Shift-JIS double-byte
+/// character and at least two pairs look Japanese: kana, or a non-ASCII
+/// trail byte. An accented letter followed by an ASCII one also forms a
+/// valid pair ("é" + "l" in "Amélie"), so such pairs are not counted.
+fn looks_like_shift_jis(bytes: &[u8]) -> bool {
+    let mut japanese_pairs = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if b < 0x80 || (0xA1..=0xDF).contains(&b) {
+            // ASCII or half-width katakana.
+            i += 1;
+            continue;
+        }
+        let lead = (0x81..=0x9F).contains(&b) || (0xE0..=0xFC).contains(&b);
+        let trail = bytes
+            .get(i + 1)
+            .is_some_and(|&t| (0x40..=0x7E).contains(&t) || (0x80..=0xFC).contains(&t));
+        if !(lead && trail) {
+            return false;
+        }
+        let t = bytes[i + 1];
+        let hiragana = b == 0x82 && (0x9F..=0xF1).contains(&t);
+        let katakana = b == 0x83 && (0x40..=0x96).contains(&t);
+        if hiragana || katakana || t >= 0x80 {
+            japanese_pairs += 1;
+        }
+        i += 2;
+    }
+    japanese_pairs >= 2
+}
+
+/// Classifies `name`, returning the issue kind and the recovered name.
+fn classify(name: &str) -> Option<(&'static str, Option<String>)> {
+    if name.contains('\u{FFFD}') {
+        return Some(("replacement-char", None));
+    }
+    if name.is_ascii() {
+        return None;
+    }
+    let bytes = cp1252_bytes(name)?;
+    // UTF-8 shown as Windows-1252, e.g. "æ—¥æœ¬èªž" for "日本語".
+    if let Ok(decoded) = std::str::from_utf8(&bytes) {
+        if decoded != name {
+            return Some(("mojibake", Some(decoded.to_string())));
+        }
+    }
+    let has_c1 = name.chars().any(|c| ('\u{80}'..='\u{9F}').contains(&c));
+    if has_c1 || looks_like_shift_jis(&bytes) {
+        return Some(("mojibake", None));
+    }
+    None
+}
+
+/// Flags games whose names contain U+FFFD or look mis-decoded. Where the
+/// original text can be recovered it is returned as `corrected_name`.
+#[tauri::command]
+pub fn detect_name_issues(games: Vec<Game>) -> Vec<NameIssue> {
+    games
+        .into_iter()
+        .filter_map(|game| {
+            let (kind, corrected) = classify(&game.name)?;
+            let suggestion = match &corrected {
+                Some(fixed) => format!("Rename the folder to \"{fixed}\""),
+                None if kind == "replacement-char" => "Part of the name could not be decoded. \
+                     Rename the folder or fetch metadata for the title"
+                    .to_string(),
+                None => "The name looks garbled by a wrong code page. \
+                         Rename the folder or fetch metadata for the title"
+                    .to_string(),
+            };
+            Some(NameIssue {
+                path: game.path,
+                name: game.name,
+                kind: kind.to_string(),
+                suggestion,
+                corrected_name: corrected,
+            })
+        })
+        .collect()
+}
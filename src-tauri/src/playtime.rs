@@ -0,0 +1,81 @@
+use crate::data_paths::{app_data_root, read_store, write_store_atomic};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+// ── Playtime ───────────────────────────────────────────────────────────────
+// Recorded here when a launched game exits, so a session is kept even if the
+// frontend never sees the `game-finished` event.
+
+const PLAYTIME_FILE: &str = "playtime.json";
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct PlaytimeEntry {
+    pub total_secs: u64,
+    pub sessions: u64,
+    /// Unix seconds when the last session ended.
+    pub last_played: u64,
+}
+
+#[derive(Serialize)]
+pub struct PlaytimeStats {
+    pub total_secs: u64,
+    pub sessions: u64,
+    /// Game path -> stats.
+    pub games: HashMap<String, PlaytimeEntry>,
+}
+
+static PLAYTIME: OnceLock<Mutex<HashMap<String, PlaytimeEntry>>> = OnceLock::new();
+
+fn playtime_path() -> PathBuf {
+    app_data_root().join(PLAYTIME_FILE)
+}
+
+fn playtime_state() -> &'static Mutex<HashMap<String, PlaytimeEntry>> {
+    PLAYTIME.get_or_init(|| {
+        let loaded = std::fs::read_to_string(playtime_path())
+            .ok()
+            .and_then(|raw| read_store(&raw))
+            .unwrap_or_default();
+        Mutex::new(loaded)
+    })
+}
+
+/// Adds one finished session for `path`.
+pub fn record_session(path: &str, duration_secs: u64) -> Result<(), String> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut map = playtime_state().lock().unwrap();
+    let entry = map.entry(path.to_string()).or_default();
+    entry.total_secs += duration_secs;
+    entry.sessions += 1;
+    entry.last_played = now;
+    write_store_atomic(&playtime_path(), &*map)
+}
+
+/// Stats for one game, or for every game when `path` is `None`.
+#[tauri::command]
+pub fn get_playtime(path: Option<String>) -> PlaytimeStats {
+    let map = playtime_state().lock().unwrap();
+    let games: HashMap<String, PlaytimeEntry> = match path {
+        Some(p) => map.get(&p).map(|e| (p, e.clone())).into_iter().collect(),
+        None => map.clone(),
+    };
+    PlaytimeStats {
+        total_secs: games.values().map(|e| e.total_secs).sum(),
+        sessions: games.values().map(|e| e.sessions).sum(),
+        games,
+    }
+}
+
+#[tauri::command]
+pub fn reset_playtime(path: String) -> Result<(), String> {
+    let mut map = playtime_state().lock().unwrap();
+    if map.remove(&path).is_some() {
+        write_store_atomic(&playtime_path(), &*map)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use crate::data_paths::{
+    active_profile, is_valid_profile_name, profiles_root, set_active_profile, write_store,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+// ── Library profiles ───────────────────────────────────────────────────────
+// Each profile is a separate data folder (see `data_paths`). Non-default
+// profiles always keep frontend storage in their folder, like portable mode,
+// because the webview's localStorage is shared by every profile.
+
+const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Serialize)]
+pub struct ProfileInfo {
+    pub name: String,
+    pub active: bool,
+}
+
+fn profile_arg(name: &str) -> Result<Option<String>, String> {
+    let name = name.trim();
+    if name.eq_ignore_ascii_case(DEFAULT_PROFILE) {
+        return Ok(None);
+    }
+    if !is_valid_profile_name(name) {
+        return Err(format!("Invalid profile name: {}", name));
+    }
+    Ok(Some(name.to_string()))
+}
+
+#[tauri::command]
+pub fn list_profiles() -> Vec<ProfileInfo> {
+    let active = active_profile();
+    let mut names: Vec<String> = std::fs::read_dir(profiles_root())
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_dir())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .filter(|n| is_valid_profile_name(n))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort_by_key(|n| n.to_lowercase());
+
+    let mut out = vec![ProfileInfo {
+        name: DEFAULT_PROFILE.to_string(),
+        active: active.is_none(),
+    }];
+    out.extend(names.into_iter().map(|name| ProfileInfo {
+        active: active.as_deref() == Some(name.as_str()),
+        name,
+    }));
+    out
+}
+
+#[tauri::command]
+pub fn create_profile(name: String) -> Result<(), String> {
+    let name =
+        profile_arg(&name)?.ok_or_else(|| "The default profile already exists".to_string())?;
+    let dir = profiles_root().join(&name);
+    if dir.exists() {
+        return Err(format!("Profile '{}' already exists", name));
+    }
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    // A non-empty storage file stops the frontend from seeding the new
+    // profile with the current localStorage.
+    let seed: HashMap<String, String> =
+        HashMap::from([("libmaly_profile".to_string(), name.clone())]);
+    std::fs::write(
+        dir.join(crate::PORTABLE_STORAGE_FILE),
+        write_store(&seed, false)?,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Makes `name` ("default" for the default profile) active and restarts the
+/// app, since settings, sessions and caches are loaded once per run.
+#[tauri::command]
+pub fn switch_profile(app: AppHandle, name: String) -> Result<(), String> {
+    let profile = profile_arg(&name)?;
+    if let Some(ref n) = profile {
+        if !profiles_root().join(n).is_dir() {
+            return Err(format!("Profile '{}' does not exist", n));
+        }
+    }
+    if profile == active_profile() {
+        return Ok(());
+    }
+    set_active_profile(profile)?;
+    crate::push_rust_log(
+        Some(&app),
+        "info",
+        format!("Switching to profile '{}'", name),
+    );
+    app.restart()
+}
+
+#[tauri::command]
+pub fn delete_profile(name: String) -> Result<(), String> {
+    let name =
+        profile_arg(&name)?.ok_or_else(|| "The default profile cannot be deleted".to_string())?;
+    if active_profile().as_deref() == Some(name.as_str()) {
+        return Err("Switch to another profile before deleting this one".to_string());
+    }
+    let dir = profiles_root().join(&name);
+    if !dir.is_dir() {
+        return Err(format!("Profile '{}' does not exist", name));
+    }
+    std::fs::remove_dir_all(dir).map_err(|e| e.to_string())
+}
@@ -0,0 +1,353 @@
+use crate::engine_overrides::effective_engine;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+#[cfg(not(windows))]
+use std::process::Command;
+
+// ── RPG Maker runtime packages ─────────────────────────────────────────────
+// RPG Maker 2000/2003/XP/VX/VX Ace games load shared graphics and audio from
+// an RTP (run time package) installed separately. Without it they fail on
+// launch with a bare "file not found", so the check below runs beforehand.
+// MV and MZ bundle everything and never need one.
+
+/// The RPG Maker generation of the game at `game_path` (its exe), e.g.
+/// "2003" or "VX Ace". An engine override decides whether it is RPG Maker.
+pub fn rpgmaker_version(game_path: &Path) -> Option<&'static str> {
+    if effective_engine(&game_path.to_string_lossy()).as_deref() != Some("RPG Maker") {
+        return None;
+    }
+    let dir = game_path.parent()?;
+    let names: HashSet<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.file_name().to_string_lossy().to_lowercase())
+                .collect()
+        })
+        .unwrap_or_default();
+    let has = |name: &str| names.contains(name);
+
+    if has("rpg_rt.exe") || has("rpg_rt.ldb") {
+        // 2003 added side-view battles and the folders for their graphics.
+        return Some(if has("battlecharset") || has("battleweapon") {
+            "2003"
+        } else {
+            "2000"
+        });
+    }
+    // Game.ini names the RGSS library: RGSS1xx is XP, RGSS2xx VX, RGSS3xx VX Ace.
+    let library = ini_value(&dir.join("Game.ini"), "Library")
+        .unwrap_or_default()
+        .to_lowercase();
+    let data = dir.join("Data");
+    if library.contains("rgss3") || has("game.rgss3a") || data.join("System.rvdata2").is_file() {
+        Some("VX Ace")
+    } else if library.contains("rgss2")
+        || has("game.rgss2a")
+        || data.join("System.rvdata").is_file()
+    {
+        Some("VX")
+    } else if library.contains("rgss1")
+        || has("game.rgssad")
+        || data.join("System.rxdata").is_file()
+    {
+        Some("XP")
+    } else if dir.join("js").join("rmmz_core.js").is_file() {
+        Some("MZ")
+    } else {
+        Some("MV")
+    }
+}
+
+/// First `key=value` in an ini file, any section; keys match case-insensitively.
+fn ini_value(path: &Path, key: &str) -> Option<String> {
+    let raw = std::fs::read(path).ok()?;
+    String::from_utf8_lossy(&raw).lines().find_map(|line| {
+        let (k, v) = line.split_once('=')?;
+        k.trim()
+            .eq_ignore_ascii_case(key)
+            .then(|| v.trim().to_string())
+    })
+}
+
+/// RTPs the game asks for: none when it is a "full package" (2000/2003) or
+/// its Game.ini leaves the `RTP` entries empty (XP/VX/VX Ace).
+fn required_rtps(dir: &Path, version: &str) -> Vec<String> {
+    match version {
+        "2000" | "2003" => {
+            let full = ini_value(&dir.join("RPG_RT.ini"), "FullPackageFlag")
+                .map(|v| v == "1")
+                .unwrap_or(false);
+            if full {
+                Vec::new()
+            } else {
+                vec![version.to_string()]
+            }
+        }
+        "XP" => ["RTP1", "RTP2", "RTP3"]
+            .iter()
+            .filter_map(|key| ini_value(&dir.join("Game.ini"), key))
+            .filter(|v| !v.is_empty())
+            .collect(),
+        "VX" | "VX Ace" => ini_value(&dir.join("Game.ini"), "RTP")
+            .filter(|v| !v.is_empty())
+            .into_iter()
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Registry key (under `SOFTWARE`) and value holding the install folder of
+/// an RTP, plus its folder under `Common Files\Enterbrain` where one exists.
+fn rtp_location(version: &str, rtp: &str) -> (String, String, Option<String>) {
+    let rgss = match version {
+        "2000" => return ("ASCII\\RPG2000".into(), "RuntimePackagePath".into(), None),
+        "2003" => {
+            return (
+                "Enterbrain\\RPG2003".into(),
+                "RuntimePackagePath".into(),
+                None,
+            )
+        }
+        "XP" => "RGSS",
+        "VX" => "RGSS2",
+        _ => "RGSS3",
+    };
+    (
+        format!("Enterbrain\\{rgss}\\RTP"),
+        rtp.to_string(),
+        Some(format!("{rgss}\\{rtp}")),
+    )
+}
+
+/// String value of `HKLM`/`HKCU\SOFTWARE\<key>`, via `reg query` so no
+/// registry bindings are needed. 32-bit installers write below WOW6432Node.
+#[cfg(windows)]
+fn reg_query(key: &str, value: &str) -> Option<String> {
+    use std::os::windows::process::CommandExt;
+    for root in [
+        "HKLM\\SOFTWARE\\WOW6432Node",
+        "HKLM\\SOFTWARE",
+        "HKCU\\SOFTWARE",
+    ] {
+        let Ok(out) = std::process::Command::new("reg")
+            .args(["query", &format!("{root}\\{key}"), "/v", value])
+            .creation_flags(0x08000000) // CREATE_NO_WINDOW
+            .output()
+        else {
+            continue;
+        };
+        if !out.status.success() {
+            continue;
+        }
+        // "    <name>    REG_SZ    <data>"
+        let text = String::from_utf8_lossy(&out.stdout);
+        for line in text.lines() {
+            let parts: Vec<&str> = line.trim().splitn(3, "    ").collect();
+            if parts.len() == 3 && parts[1].ends_with("_SZ") {
+                return Some(parts[2].trim().to_string());
+            }
+        }
+    }
+    None
+}
+
+#[cfg(windows)]
+fn rtp_installed(version: &str, rtp: &str, _prefix: Option<&Path>) -> bool {
+    let (key, value, common_dir) = rtp_location(version, rtp);
+    if reg_query(&key, &value).is_some_and(|p| Path::new(&p).is_dir()) {
+        return true;
+    }
+    let Some(common_dir) = common_dir else {
+        return false;
+    };
+    ["CommonProgramFiles(x86)", "CommonProgramFiles"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .any(|base| {
+            PathBuf::from(base)
+                .join("Enterbrain")
+                .join(&common_dir)
+                .is_dir()
+        })
+}
+
+/// Host path of a Windows path inside a Wine prefix, via `dosdevices`.
+#[cfg(not(windows))]
+fn wine_host_path(prefix: &Path, win_path: &str) -> Option<PathBuf> {
+    let (drive, rest) = win_path.split_once(':')?;
+    let mut path = prefix
+        .join("dosdevices")
+        .join(format!("{}:", drive.to_lowercase()));
+    for part in rest.split('\\').filter(|p| !p.is_empty()) {
+        path.push(part);
+    }
+    Some(path)
+}
+
+/// Folders EasyRPG Player searches for the 2000/2003 RTP.
+#[cfg(not(windows))]
+fn easyrpg_rtp_dirs(version: &str) -> Vec<PathBuf> {
+    let env_var = if version == "2003" {
+        "RPG2K3_RTP_PATH"
+    } else {
+        "RPG2K_RTP_PATH"
+    };
+    let mut dirs: Vec<PathBuf> = [env_var, "RPG_RTP_PATH"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .map(PathBuf::from)
+        .collect();
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local/share")));
+    if let Some(data_home) = data_home {
+        dirs.push(data_home.join("rtp").join(version));
+    }
+    dirs.push(PathBuf::from("/usr/share/rtp").join(version));
+    dirs
+}
+
+#[cfg(not(windows))]
+fn rtp_installed(version: &str, rtp: &str, prefix: Option<&Path>) -> bool {
+    if matches!(version, "2000" | "2003") && easyrpg_rtp_dirs(version).iter().any(|d| d.is_dir()) {
+        return true;
+    }
+    let Some(prefix) = prefix else {
+        return false;
+    };
+    let (key, value, common_dir) = rtp_location(version, rtp);
+    let system_reg = prefix.join("system.reg");
+    for root in ["Software\\Wow6432Node", "Software"] {
+        let Ok(values) = crate::read_reg_key_strings(&system_reg, &format!("{root}\\{key}")) else {
+            continue;
+        };
+        let found = values
+            .iter()
+            .filter(|(name, _)| name.eq_ignore_ascii_case(&value))
+            .filter_map(|(_, path)| wine_host_path(prefix, path))
+            .any(|p| p.is_dir());
+        if found {
+            return true;
+        }
+    }
+    let Some(common_dir) = common_dir else {
+        return false;
+    };
+    ["Program Files (x86)", "Program Files"].iter().any(|pf| {
+        let mut dir = prefix
+            .join("drive_c")
+            .join(pf)
+            .join("Common Files")
+            .join("Enterbrain");
+        for part in common_dir.split('\\') {
+            dir.push(part);
+        }
+        dir.is_dir()
+    })
+}
+
+#[cfg(not(windows))]
+const EASYRPG_FLATPAK_ID: &str = "org.easyrpg.player";
+
+/// Whether the EasyRPG Player Flatpak is installed, per-user or system-wide.
+#[cfg(not(windows))]
+fn easyrpg_flatpak_installed() -> bool {
+    let user_apps =
+        std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local/share/flatpak/app"));
+    crate::command_in_path("flatpak")
+        && user_apps
+            .into_iter()
+            .chain([PathBuf::from("/var/lib/flatpak/app")])
+            .any(|apps| apps.join(EASYRPG_FLATPAK_ID).is_dir())
+}
+
+/// Whether EasyRPG Player, which runs 2000/2003 games natively, is installed
+/// (the `easyrpg-player` binary or its Flatpak).
+#[cfg(not(windows))]
+pub fn easyrpg_available() -> bool {
+    crate::command_in_path("easyrpg-player") || easyrpg_flatpak_installed()
+}
+
+/// EasyRPG Player command for the RPG Maker 2000/2003 game at `game_path`
+/// (its `RPG_RT.exe`), run from the game folder. The native binary wins
+/// over the Flatpak.
+#[cfg(not(windows))]
+pub fn easyrpg_launch_command(game_path: &str) -> Result<Command, String> {
+    let game = Path::new(game_path);
+    let dir = game
+        .parent()
+        .ok_or_else(|| "Cannot determine game directory".to_string())?;
+    if !matches!(rpgmaker_version(game), Some("2000" | "2003")) {
+        return Err("EasyRPG Player only runs RPG Maker 2000/2003 games".to_string());
+    }
+    let mut cmd = if crate::command_in_path("easyrpg-player") {
+        Command::new("easyrpg-player")
+    } else if easyrpg_flatpak_installed() {
+        let mut cmd = Command::new("flatpak");
+        // The sandbox only sees folders it is given.
+        cmd.arg("run")
+            .arg(format!("--filesystem={}", dir.display()))
+            .arg(EASYRPG_FLATPAK_ID);
+        cmd
+    } else {
+        return Err(format!(
+            "EasyRPG Player is not installed. Install the easyrpg-player package \
+             or the {EASYRPG_FLATPAK_ID} Flatpak."
+        ));
+    };
+    cmd.arg("--project-path").arg(dir).current_dir(dir);
+    Ok(cmd)
+}
+
+#[cfg(windows)]
+pub fn easyrpg_available() -> bool {
+    false
+}
+
+#[derive(Serialize)]
+pub struct RtpCheck {
+    /// "2000", "2003", "XP", "VX", "VX Ace", "MV" or "MZ"; `None` when the
+    /// game is not an RPG Maker game.
+    pub version: Option<String>,
+    /// RTP names the game depends on; empty when it bundles its assets.
+    pub required: Vec<String>,
+    /// Entries of `required` that were not found.
+    pub missing: Vec<String>,
+    /// EasyRPG Player can stand in for Wine (2000/2003 on Linux only).
+    pub easyrpg_available: bool,
+}
+
+/// Detects the RPG Maker version of the game at `game_path` (its exe) and
+/// which of the RTPs it needs are missing. Looks in the registry on Windows;
+/// elsewhere in EasyRPG's RTP folders and, when `prefix` is given, that Wine
+/// prefix. A bundled `RTP` folder next to the game always counts.
+#[tauri::command]
+pub fn check_rpgmaker_rtp(game_path: String, prefix: Option<String>) -> Result<RtpCheck, String> {
+    let dir = Path::new(&game_path)
+        .parent()
+        .ok_or_else(|| "Cannot determine game directory".to_string())?;
+    let Some(version) = rpgmaker_version(Path::new(&game_path)) else {
+        return Ok(RtpCheck {
+            version: None,
+            required: Vec::new(),
+            missing: Vec::new(),
+            easyrpg_available: false,
+        });
+    };
+    let required = required_rtps(dir, version);
+    let bundled = dir.join("RTP").is_dir();
+    let prefix = prefix.as_deref().map(Path::new);
+    let missing = required
+        .iter()
+        .filter(|rtp| !bundled && !rtp_installed(version, rtp, prefix))
+        .cloned()
+        .collect();
+    Ok(RtpCheck {
+        version: Some(version.to_string()),
+        required,
+        missing,
+        easyrpg_available: matches!(version, "2000" | "2003") && easyrpg_available(),
+    })
+}
@@ -4,7 +4,7 @@ use std::path::{Path, PathBuf};
 use std::sync::{mpsc, Mutex};
 use tauri::AppHandle;
 use base64::Engine;
-use crate::data_paths::app_data_root;
+use crate::data_paths::{app_data_root, read_store, write_store};
 #[cfg(windows)]
 use tauri::Emitter;
 
@@ -26,12 +26,63 @@ pub struct BossKeyConfig {
     pub mute: bool,
 }
 
+/// Image format written by the Windows GDI capture.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScreenshotFormat {
+    #[default]
+    Png,
+    Jpeg,
+    /// Lossless; the `image` crate has no lossy WebP encoder.
+    Webp,
+}
+
+#[cfg(windows)]
+impl ScreenshotFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ScreenshotFormat::Png => "png",
+            ScreenshotFormat::Jpeg => "jpg",
+            ScreenshotFormat::Webp => "webp",
+        }
+    }
+}
+
+/// Encoding options for captured screenshots. Every field is optional so an
+/// empty config keeps the plain `RgbaImage::save` behaviour.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ScreenshotConfig {
+    /// Output format; PNG unless set.
+    pub format: ScreenshotFormat,
+    /// JPEG quality, 1-100 (default 90). Ignored by PNG and WebP.
+    pub quality: Option<u8>,
+    /// PNG compression level: "fast" | "default" | "best" | "none".
+    /// Higher levels trade encode CPU time for smaller files.
+    pub png_compression: Option<String>,
+    /// Downscale captures whose longest side exceeds this many pixels before
+    /// encoding, to cap the memory/CPU spike of 4K+ captures.
+    pub max_dimension: Option<u32>,
+    /// Capture the whole virtual screen (every monitor) instead of the game
+    /// window. Windows only; helps with games spanning or moved to another
+    /// monitor, and with D3D windows that GDI reads back as white.
+    pub full_screen: bool,
+    /// Virtual-key code of the capture hotkey; `None` means F12. Only the
+    /// Windows hook uses it: Linux/macOS capture is manual for now.
+    pub capture_vk_code: Option<u32>,
+}
+
+/// Default capture hotkey (VK_F12).
+#[cfg(windows)]
+const DEFAULT_CAPTURE_VK: u32 = 0x7B;
+
 #[cfg(windows)]
 struct HookState {
     pid: u32,
     exe: String,
     app: AppHandle,
     boss_key: Option<BossKeyConfig>,
+    config: ScreenshotConfig,
 }
 
 #[cfg(windows)]
@@ -44,6 +95,32 @@ fn hook_state() -> &'static Mutex<Option<HookState>> {
 
 // ── Helpers ────────────────────────────────────────────────────────────────
 
+/// Extensions listed as screenshots: the capture formats above plus `.jpeg`.
+const SCREENSHOT_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp"];
+
+fn is_screenshot_file(path: &Path) -> bool {
+    path.extension()
+        .map(|x| {
+            let x = x.to_string_lossy();
+            SCREENSHOT_EXTENSIONS
+                .iter()
+                .any(|ext| x.eq_ignore_ascii_case(ext))
+        })
+        .unwrap_or(false)
+}
+
+fn image_mime(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .map(|x| x.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "webp" => "image/webp",
+        _ => "image/png",
+    }
+}
+
 /// Returns the base screenshots directory for the current platform.
 pub fn screenshots_dir(game_exe: &str) -> PathBuf {
     let base = app_data_root();
@@ -95,7 +172,7 @@ pub fn get_screenshots(game_exe: String) -> Result<Vec<Screenshot>, String> {
     let meta_path = dir.join("tags.json");
     let all_tags: std::collections::HashMap<String, Vec<String>> = if meta_path.exists() {
         let content = std::fs::read_to_string(&meta_path).map_err(|e| e.to_string())?;
-        serde_json::from_str(&content).unwrap_or_default()
+        read_store(&content).unwrap_or_default()
     } else {
         std::collections::HashMap::new()
     };
@@ -103,12 +180,7 @@ pub fn get_screenshots(game_exe: String) -> Result<Vec<Screenshot>, String> {
     let mut shots: Vec<Screenshot> = std::fs::read_dir(&dir)
         .map_err(|e| e.to_string())?
         .filter_map(|e| e.ok())
-        .filter(|e| {
-            e.path()
-                .extension()
-                .map(|x| x.to_string_lossy().eq_ignore_ascii_case("png"))
-                .unwrap_or(false)
-        })
+        .filter(|e| is_screenshot_file(&e.path()))
         .map(|e| {
             let path_str = e.path().to_string_lossy().to_string();
             let filename = e.file_name().to_string_lossy().to_string();
@@ -128,7 +200,7 @@ pub fn get_screenshots(game_exe: String) -> Result<Vec<Screenshot>, String> {
             }
         })
         .collect();
-    shots.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    shots.sort_by_key(|s| std::cmp::Reverse(s.timestamp));
     Ok(shots)
 }
 
@@ -146,14 +218,14 @@ pub fn save_screenshot_tags(
     let meta_path = dir.join("tags.json");
     let mut all_tags: std::collections::HashMap<String, Vec<String>> = if meta_path.exists() {
         let content = std::fs::read_to_string(&meta_path).map_err(|e| e.to_string())?;
-        serde_json::from_str(&content).unwrap_or_default()
+        read_store(&content).unwrap_or_default()
     } else {
         std::collections::HashMap::new()
     };
 
     all_tags.insert(screenshot_name, tags);
 
-    let content = serde_json::to_string_pretty(&all_tags).map_err(|e| e.to_string())?;
+    let content = write_store(&all_tags, true)?;
     std::fs::write(&meta_path, content).map_err(|e| e.to_string())?;
     Ok(())
 }
@@ -193,27 +265,23 @@ pub fn export_screenshots_zip(game_exe: String, output_path: String) -> Result<(
         return Err("No screenshots found for this game.".to_string());
     }
 
-    let mut png_files: Vec<PathBuf> = std::fs::read_dir(&dir)
+    let mut image_files: Vec<PathBuf> = std::fs::read_dir(&dir)
         .map_err(|e| e.to_string())?
         .filter_map(|e| e.ok())
         .map(|e| e.path())
-        .filter(|p| {
-            p.extension()
-                .map(|x| x.to_string_lossy().eq_ignore_ascii_case("png"))
-                .unwrap_or(false)
-        })
+        .filter(|p| is_screenshot_file(p))
         .collect();
-    if png_files.is_empty() {
+    if image_files.is_empty() {
         return Err("No screenshot files to export.".to_string());
     }
-    png_files.sort();
+    image_files.sort();
 
     let file = File::create(&output_path).map_err(|e| e.to_string())?;
     let mut zip = zip::ZipWriter::new(file);
     let options = zip::write::SimpleFileOptions::default()
         .compression_method(zip::CompressionMethod::Deflated);
 
-    for p in png_files {
+    for p in image_files {
         let name = p
             .file_name()
             .map(|n| n.to_string_lossy().into_owned())
@@ -236,11 +304,14 @@ pub fn export_screenshots_zip(game_exe: String, output_path: String) -> Result<(
 }
 
 #[tauri::command]
-pub fn take_screenshot_manual(state: tauri::State<ActiveGameState>) -> Result<Screenshot, String> {
+pub fn take_screenshot_manual(
+    state: tauri::State<ActiveGameState>,
+    config: Option<ScreenshotConfig>,
+) -> Result<Screenshot, String> {
     let guard = state.0.lock().unwrap();
     match &*guard {
         None => Err("No game is currently running.".to_string()),
-        Some(game) => capture_window_of(game.pid, &game.exe),
+        Some(game) => capture_window_of(game.pid, &game.exe, &config.unwrap_or_default()),
     }
 }
 
@@ -260,25 +331,106 @@ pub fn overwrite_screenshot_png(path: String, data_url: String) -> Result<(), St
 pub fn delete_screenshot_file(path: String) -> Result<(), String> {
     let p = PathBuf::from(path);
     if p.exists() {
-        std::fs::remove_file(p).map_err(|e| e.to_string())?;
+        std::fs::remove_file(&p).map_err(|e| e.to_string())?;
     }
+    remove_cached_thumbnails(&p);
     Ok(())
 }
 
+/// Deletes every cached `.thumbs/<name>.<max_dim>.jpg` of the screenshot at
+/// `src`, whatever sizes were generated.
+fn remove_cached_thumbnails(src: &Path) {
+    let (Some(dir), Some(name)) = (src.parent(), src.file_name()) else {
+        return;
+    };
+    let prefix = format!("{}.", name.to_string_lossy());
+    let Ok(entries) = std::fs::read_dir(dir.join(THUMBS_DIR)) else {
+        return;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        let is_thumb = file_name
+            .strip_prefix(&prefix)
+            .and_then(|rest| rest.strip_suffix(".jpg"))
+            .is_some_and(|size| !size.is_empty() && size.bytes().all(|b| b.is_ascii_digit()));
+        if is_thumb {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+}
+
 #[tauri::command]
 pub fn get_screenshot_data_url(path: String) -> Result<String, String> {
-    let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+    let mime = image_mime(Path::new(&path));
+    let bytes = std::fs::read(&path).map_err(|e| e.to_string())?;
     let b64 = base64::engine::general_purpose::STANDARD.encode(bytes);
-    Ok(format!("data:image/png;base64,{b64}"))
+    Ok(format!("data:{mime};base64,{b64}"))
+}
+
+/// Thumbnails are cached beside the screenshots, one file per size.
+const THUMBS_DIR: &str = ".thumbs";
+
+/// Returns a JPEG data URL of the screenshot scaled to fit `max_dim`, for
+/// gallery grids. The thumbnail is cached as `.thumbs/<name>.<max_dim>.jpg`
+/// and regenerated once the source is newer.
+#[tauri::command]
+pub fn get_screenshot_thumbnail(path: String, max_dim: u32) -> Result<String, String> {
+    let src = Path::new(&path);
+    let name = src
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .ok_or_else(|| "Invalid screenshot path".to_string())?;
+    let max_dim = max_dim.max(1);
+    let thumb_dir = src
+        .parent()
+        .map(|p| p.join(THUMBS_DIR))
+        .ok_or_else(|| "Invalid screenshot path".to_string())?;
+    let thumb_path = thumb_dir.join(format!("{name}.{max_dim}.jpg"));
+
+    let src_modified = std::fs::metadata(src)
+        .and_then(|m| m.modified())
+        .map_err(|e| e.to_string())?;
+    let fresh = std::fs::metadata(&thumb_path)
+        .and_then(|m| m.modified())
+        .map(|t| t >= src_modified)
+        .unwrap_or(false);
+
+    let bytes = if fresh {
+        std::fs::read(&thumb_path).map_err(|e| e.to_string())?
+    } else {
+        let img = image::open(src).map_err(|e| e.to_string())?;
+        let img = if img.width() > max_dim || img.height() > max_dim {
+            img.resize(max_dim, max_dim, image::imageops::FilterType::Lanczos3)
+        } else {
+            img
+        };
+        let mut out = Vec::new();
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut out, 85)
+            .encode_image(&img.to_rgb8())
+            .map_err(|e| e.to_string())?;
+        // The cache is best-effort; a read-only folder still gets a thumbnail.
+        if std::fs::create_dir_all(&thumb_dir).is_ok() {
+            let _ = std::fs::write(&thumb_path, &out);
+        }
+        out
+    };
+    let b64 = base64::engine::general_purpose::STANDARD.encode(bytes);
+    Ok(format!("data:image/jpeg;base64,{b64}"))
 }
 
 // ── Public capture entry-point (also used by hotkey thread) ───────────────
 
+/// `config` only affects the Windows GDI path; Linux/macOS capture through
+/// external tools that always write PNG.
 #[allow(unused_variables)]
-pub fn capture_window_of(pid: u32, game_exe: &str) -> Result<Screenshot, String> {
+pub fn capture_window_of(
+    pid: u32,
+    game_exe: &str,
+    config: &ScreenshotConfig,
+) -> Result<Screenshot, String> {
     #[cfg(windows)]
     {
-        win::capture_and_save(pid, game_exe)
+        win::capture_and_save(pid, game_exe, config)
     }
     #[cfg(target_os = "linux")]
     {
@@ -290,7 +442,7 @@ pub fn capture_window_of(pid: u32, game_exe: &str) -> Result<Screenshot, String>
     }
     #[cfg(not(any(windows, target_os = "linux", target_os = "macos")))]
     {
-        let _ = (pid, game_exe);
+        let _ = (pid, game_exe, config);
         Err("Screenshots are not supported on this platform.".to_string())
     }
 }
@@ -304,34 +456,33 @@ unsafe extern "system" fn ll_keyboard_proc(code: i32, wparam: usize, lparam: isi
     use winapi::um::winuser::{CallNextHookEx, KBDLLHOOKSTRUCT, WM_KEYDOWN};
     if code >= 0 && wparam == WM_KEYDOWN as usize {
         let kb = &*(lparam as *const KBDLLHOOKSTRUCT);
-        if kb.vkCode == 0x7B {
-            if let Ok(guard) = hook_state().lock() {
-                if let Some(ref state) = *guard {
-                    if kb.vkCode == 0x7B {
-                        match capture_window_of(state.pid, &state.exe) {
-                            Ok(shot) => {
-                                let _ = state.app.emit(
-                                    "screenshot-taken",
-                                    ScreenshotTakenPayload {
-                                        game_exe: state.exe.clone(),
-                                        screenshot: shot,
-                                    },
-                                );
-                            }
-                            Err(e) => eprintln!("[screenshot] F12: {}", e),
-                        }
-                    } else if let Some(ref boss) = state.boss_key {
-                        if kb.vkCode == boss.vk_code {
-                            let action = boss.action.clone();
-                            let mute = boss.mute;
-                            let pid = state.pid;
-                            // Hide the Libmaly window via frontend event
-                            let _ = state.app.emit("boss-key-pressed", ());
-                            // Execute panic action in background to avoid blocking the hook thread
-                            std::thread::spawn(move || {
-                                win::exec_panic_action(pid, &action, mute);
-                            });
+        if let Ok(guard) = hook_state().lock() {
+            if let Some(ref state) = *guard {
+                let capture_vk = state.config.capture_vk_code.unwrap_or(DEFAULT_CAPTURE_VK);
+                if kb.vkCode == capture_vk {
+                    match capture_window_of(state.pid, &state.exe, &state.config) {
+                        Ok(shot) => {
+                            let _ = state.app.emit(
+                                "screenshot-taken",
+                                ScreenshotTakenPayload {
+                                    game_exe: state.exe.clone(),
+                                    screenshot: shot,
+                                },
+                            );
                         }
+                        Err(e) => eprintln!("[screenshot] capture hotkey: {}", e),
+                    }
+                } else if let Some(ref boss) = state.boss_key {
+                    if kb.vkCode == boss.vk_code {
+                        let action = boss.action.clone();
+                        let mute = boss.mute;
+                        let pid = state.pid;
+                        // Hide the Libmaly window via frontend event
+                        let _ = state.app.emit("boss-key-pressed", ());
+                        // Execute panic action in background to avoid blocking the hook thread
+                        std::thread::spawn(move || {
+                            win::exec_panic_action(pid, &action, mute);
+                        });
                     }
                 }
             }
@@ -340,14 +491,16 @@ unsafe extern "system" fn ll_keyboard_proc(code: i32, wparam: usize, lparam: isi
     CallNextHookEx(std::ptr::null_mut(), code, wparam, lparam)
 }
 
-/// Registers a low-level keyboard hook that intercepts F12 globally.
-/// Uses `WH_KEYBOARD_LL` instead of `RegisterHotKey` so it works even when
-/// F12 is taken by another app (Steam overlay, browser devtools, etc.).
+/// Registers a low-level keyboard hook that intercepts the capture key
+/// (`ScreenshotConfig::capture_vk_code`, F12 by default) and the boss key
+/// globally. Uses `WH_KEYBOARD_LL` instead of `RegisterHotKey` so it works even
+/// when the key is taken by another app (Steam overlay, browser devtools, etc.).
 pub fn start_hotkey_listener(
     pid: u32,
     game_exe: String,
     app: AppHandle,
     boss_key: Option<BossKeyConfig>,
+    config: ScreenshotConfig,
     thread_id_tx: mpsc::Sender<u32>,
 ) {
     #[cfg(windows)]
@@ -363,6 +516,7 @@ pub fn start_hotkey_listener(
             exe: game_exe,
             app,
             boss_key,
+            config,
         });
 
         let thread_id = GetCurrentThreadId();
@@ -393,7 +547,7 @@ pub fn start_hotkey_listener(
 
     #[cfg(not(windows))]
     {
-        let _ = (pid, game_exe, app, boss_key);
+        let _ = (pid, game_exe, app, boss_key, config);
         let _ = thread_id_tx.send(0);
     }
 }
@@ -408,8 +562,63 @@ pub fn stop_hotkey_thread(thread_id: u32) {
     let _ = thread_id;
 }
 
+/// External tools the capture path can use on this platform, for diagnostics.
+pub fn screenshot_tools() -> Vec<String> {
+    #[cfg(windows)]
+    {
+        // Windows captures through GDI and needs nothing installed.
+        vec!["gdi".to_string()]
+    }
+    #[cfg(not(windows))]
+    {
+        #[cfg(target_os = "macos")]
+        let candidates = ["screencapture", "osascript"];
+        #[cfg(not(target_os = "macos"))]
+        let candidates = [
+            "grim",
+            "spectacle",
+            "xdotool",
+            "scrot",
+            "gnome-screenshot",
+            "import",
+        ];
+        candidates
+            .iter()
+            .filter(|tool| crate::command_in_path(tool))
+            .map(|tool| tool.to_string())
+            .collect()
+    }
+}
+
 // ── Linux screenshot capture ───────────────────────────────────────────────
 
+/// X11 tools cannot see Wayland windows, so Wayland sessions use their own.
+#[cfg(target_os = "linux")]
+fn is_wayland_session() -> bool {
+    std::env::var_os("WAYLAND_DISPLAY").is_some()
+        || std::env::var("XDG_SESSION_TYPE")
+            .map(|t| t.eq_ignore_ascii_case("wayland"))
+            .unwrap_or(false)
+}
+
+/// Full-screen capture with `grim` (wlroots compositors) or KDE's `spectacle`.
+/// Region selection via `slurp` is left out on purpose: it would block the
+/// hotkey on a mouse drag.
+#[cfg(target_os = "linux")]
+fn capture_wayland(out_str: &str) -> bool {
+    use std::process::Command;
+    Command::new("grim")
+        .arg(out_str)
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+        || Command::new("spectacle")
+            .args(["-b", "-n", "-o", out_str])
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+}
+
 #[cfg(target_os = "linux")]
 fn capture_linux(pid: u32, game_exe: &str) -> Result<Screenshot, String> {
     use std::process::Command;
@@ -423,6 +632,21 @@ fn capture_linux(pid: u32, game_exe: &str) -> Result<Screenshot, String> {
     let out_path = dir.join(&filename);
     let out_str = out_path.to_string_lossy().to_string();
 
+    if is_wayland_session() {
+        if !capture_wayland(&out_str) || !out_path.exists() {
+            return Err(
+                "Screenshot failed. On Wayland, install 'grim' (wlroots) or 'spectacle' (KDE)."
+                    .to_string(),
+            );
+        }
+        return Ok(Screenshot {
+            path: out_str,
+            filename,
+            timestamp: now,
+            tags: vec![],
+        });
+    }
+
     // Try to find the window ID for this PID via xdotool, then
     // capture only that window. Fall back to full-screen capture.
     let window_id: Option<String> = Command::new("xdotool")
@@ -554,7 +778,7 @@ fn capture_macos(pid: u32, game_exe: &str) -> Result<Screenshot, String> {
 
 #[cfg(windows)]
 mod win {
-    use super::{screenshots_dir, Screenshot};
+    use super::{screenshots_dir, Screenshot, ScreenshotConfig, ScreenshotFormat};
     use winapi::shared::minwindef::{BOOL, DWORD, FALSE, LPARAM, TRUE};
     use winapi::shared::windef::{HBITMAP, HWND, POINT, RECT};
     use winapi::um::wingdi::{
@@ -562,9 +786,10 @@ mod win {
         SelectObject, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, RGBQUAD, SRCCOPY,
     };
     use winapi::um::winuser::{
-        ClientToScreen, EnumWindows, GetClientRect, GetDC, GetForegroundWindow, GetWindowLongW,
-        GetWindowTextW, GetWindowThreadProcessId, IsWindowVisible, PrintWindow, ReleaseDC,
-        GWL_STYLE,
+        ClientToScreen, EnumWindows, GetClientRect, GetDC, GetForegroundWindow, GetSystemMetrics,
+        GetWindowLongW, GetWindowTextW, GetWindowThreadProcessId, IsWindowVisible, PrintWindow,
+        ReleaseDC, GWL_STYLE, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN,
+        SM_YVIRTUALSCREEN,
     };
 
     pub fn exec_panic_action(pid: u32, action: &str, mute: bool) {
@@ -656,10 +881,175 @@ mod win {
 
     // ── GDI capture ───────────────────────────────────────────────────────
 
-    pub fn capture_and_save(pid: u32, game_exe: &str) -> Result<Screenshot, String> {
-        let hwnd = find_game_window(pid).ok_or("Game window not found")?;
+    // ── Encoding ──────────────────────────────────────────────────────────
+
+    /// Shrinks `img` so its longest side fits `max_dim`, preserving aspect ratio.
+    fn downscale_to_fit(img: image::RgbaImage, max_dim: Option<u32>) -> image::RgbaImage {
+        let Some(max_dim) = max_dim.filter(|d| *d > 0) else {
+            return img;
+        };
+        let (w, h) = img.dimensions();
+        if w <= max_dim && h <= max_dim {
+            return img;
+        }
+        let scale = max_dim as f64 / w.max(h) as f64;
+        let nw = ((w as f64 * scale).round() as u32).max(1);
+        let nh = ((h as f64 * scale).round() as u32).max(1);
+        image::imageops::resize(&img, nw, nh, image::imageops::FilterType::Triangle)
+    }
+
+    /// Writes `img` in the configured format. JPEG and WebP drop the alpha
+    /// channel, which GDI captures never use (it is forced to 255).
+    fn write_image(
+        img: &image::RgbaImage,
+        out_path: &std::path::Path,
+        config: &ScreenshotConfig,
+    ) -> Result<(), String> {
+        use image::codecs::jpeg::JpegEncoder;
+        use image::codecs::webp::WebPEncoder;
+        use image::ImageEncoder;
+
+        if config.format == ScreenshotFormat::Png {
+            return write_png(img, out_path, config);
+        }
+        let rgb = image::DynamicImage::ImageRgba8(img.clone()).to_rgb8();
+        let file = std::fs::File::create(out_path).map_err(|e| e.to_string())?;
+        let writer = std::io::BufWriter::new(file);
+        let (w, h) = rgb.dimensions();
+        let color = image::ExtendedColorType::Rgb8;
+        match config.format {
+            ScreenshotFormat::Jpeg => {
+                let quality = config.quality.unwrap_or(90).clamp(1, 100);
+                JpegEncoder::new_with_quality(writer, quality).write_image(
+                    rgb.as_raw(),
+                    w,
+                    h,
+                    color,
+                )
+            }
+            _ => WebPEncoder::new_lossless(writer).write_image(rgb.as_raw(), w, h, color),
+        }
+        .map_err(|e| e.to_string())
+    }
+
+    fn write_png(
+        img: &image::RgbaImage,
+        out_path: &std::path::Path,
+        config: &ScreenshotConfig,
+    ) -> Result<(), String> {
+        use image::codecs::png::{CompressionType, FilterType, PngEncoder};
+        use image::ImageEncoder;
+
+        let compression = match config.png_compression.as_deref() {
+            None => return img.save(out_path).map_err(|e| e.to_string()),
+            Some("fast") => CompressionType::Fast,
+            Some("best") => CompressionType::Best,
+            Some("none") => CompressionType::Uncompressed,
+            Some(_) => CompressionType::Default,
+        };
+        let file = std::fs::File::create(out_path).map_err(|e| e.to_string())?;
+        let encoder = PngEncoder::new_with_quality(
+            std::io::BufWriter::new(file),
+            compression,
+            FilterType::Adaptive,
+        );
+        encoder
+            .write_image(
+                img.as_raw(),
+                img.width(),
+                img.height(),
+                image::ExtendedColorType::Rgba8,
+            )
+            .map_err(|e| e.to_string())
+    }
+
+    fn bitmap_info(w: i32, h: i32) -> BITMAPINFO {
+        BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: w,
+                biHeight: -h, // negative = top-down scan lines
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB,
+                biSizeImage: 0,
+                biXPelsPerMeter: 0,
+                biYPelsPerMeter: 0,
+                biClrUsed: 0,
+                biClrImportant: 0,
+            },
+            bmiColors: [RGBQUAD {
+                rgbBlue: 0,
+                rgbGreen: 0,
+                rgbRed: 0,
+                rgbReserved: 0,
+            }],
+        }
+    }
+
+    /// GDI gives BGRA — swap B ↔ R to get RGBA, set alpha = 255
+    fn bgra_to_rgba(buf: &mut [u8]) {
+        for px in buf.chunks_mut(4) {
+            px.swap(0, 2);
+            px[3] = 255;
+        }
+    }
 
-        let (pixels, width, height) = unsafe {
+    /// Copies the whole virtual screen, spanning every monitor, from the
+    /// screen DC. Its origin can be negative when a monitor sits left of or
+    /// above the primary one.
+    fn capture_virtual_screen() -> Result<(Vec<u8>, u32, u32), String> {
+        unsafe {
+            let x = GetSystemMetrics(SM_XVIRTUALSCREEN);
+            let y = GetSystemMetrics(SM_YVIRTUALSCREEN);
+            let w = GetSystemMetrics(SM_CXVIRTUALSCREEN);
+            let h = GetSystemMetrics(SM_CYVIRTUALSCREEN);
+            if w <= 0 || h <= 0 {
+                return Err(format!("Virtual screen reports size {}×{}", w, h));
+            }
+
+            let hdc_screen = GetDC(std::ptr::null_mut());
+            if hdc_screen.is_null() {
+                return Err("GetDC failed".into());
+            }
+            let hdc_mem = CreateCompatibleDC(hdc_screen);
+            let hbmp: HBITMAP = CreateCompatibleBitmap(hdc_screen, w, h);
+            let old = SelectObject(hdc_mem, hbmp as *mut _);
+
+            let copied = BitBlt(hdc_mem, 0, 0, w, h, hdc_screen, x, y, SRCCOPY) != 0;
+            let mut bmi = bitmap_info(w, h);
+            let mut buf: Vec<u8> = vec![0u8; (w * h) as usize * 4];
+            let ret = if copied {
+                GetDIBits(
+                    hdc_mem,
+                    hbmp,
+                    0,
+                    h as u32,
+                    buf.as_mut_ptr() as *mut _,
+                    &mut bmi,
+                    DIB_RGB_COLORS,
+                )
+            } else {
+                0
+            };
+
+            SelectObject(hdc_mem, old);
+            DeleteObject(hbmp as *mut _);
+            DeleteDC(hdc_mem);
+            ReleaseDC(std::ptr::null_mut(), hdc_screen);
+            if ret == 0 {
+                return Err("Could not copy the virtual screen".into());
+            }
+
+            bgra_to_rgba(&mut buf);
+            Ok((buf, w as u32, h as u32))
+        }
+    }
+
+    /// Captures the client area of `hwnd`, preferring the screen DC or
+    /// `PrintWindow` depending on whether the game is in the foreground.
+    fn capture_window(hwnd: HWND) -> Result<(Vec<u8>, u32, u32), String> {
+        unsafe {
             let mut rect: RECT = std::mem::zeroed();
             GetClientRect(hwnd, &mut rect);
             let w = rect.right - rect.left;
@@ -707,27 +1097,7 @@ mod win {
             }
 
             // Read pixels as 32 bpp BGRA top-down
-            let mut bmi = BITMAPINFO {
-                bmiHeader: BITMAPINFOHEADER {
-                    biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
-                    biWidth: w,
-                    biHeight: -h, // negative = top-down scan lines
-                    biPlanes: 1,
-                    biBitCount: 32,
-                    biCompression: BI_RGB,
-                    biSizeImage: 0,
-                    biXPelsPerMeter: 0,
-                    biYPelsPerMeter: 0,
-                    biClrUsed: 0,
-                    biClrImportant: 0,
-                },
-                bmiColors: [RGBQUAD {
-                    rgbBlue: 0,
-                    rgbGreen: 0,
-                    rgbRed: 0,
-                    rgbReserved: 0,
-                }],
-            };
+            let mut bmi = bitmap_info(w, h);
 
             let mut buf: Vec<u8> = vec![0u8; (w * h) as usize * 4];
             let mut ret = GetDIBits(
@@ -787,16 +1157,24 @@ mod win {
             DeleteDC(hdc_mem);
             ReleaseDC(hwnd, hdc_src);
 
-            // GDI gives BGRA — swap B ↔ R to get RGBA, set alpha = 255
-            for px in buf.chunks_mut(4) {
-                px.swap(0, 2);
-                px[3] = 255;
-            }
+            bgra_to_rgba(&mut buf);
+            Ok((buf, w as u32, h as u32))
+        }
+    }
 
-            (buf, w as u32, h as u32)
+    pub fn capture_and_save(
+        pid: u32,
+        game_exe: &str,
+        config: &ScreenshotConfig,
+    ) -> Result<Screenshot, String> {
+        let (pixels, width, height) = if config.full_screen {
+            capture_virtual_screen()?
+        } else {
+            let hwnd = find_game_window(pid).ok_or("Game window not found")?;
+            capture_window(hwnd)?
         };
 
-        // Encode to PNG via `image` crate
+        // Encode via `image` crate
         let dir = screenshots_dir(game_exe);
         std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
 
@@ -804,12 +1182,13 @@ mod win {
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs();
-        let filename = format!("screenshot_{}.png", now);
+        let filename = format!("screenshot_{}.{}", now, config.format.extension());
         let out_path = dir.join(&filename);
 
         let img = image::RgbaImage::from_raw(width, height, pixels)
             .ok_or("Failed to create image buffer from pixel data")?;
-        img.save(&out_path).map_err(|e| e.to_string())?;
+        let img = downscale_to_fit(img, config.max_dimension);
+        write_image(&img, &out_path, config)?;
 
         Ok(Screenshot {
             path: out_path.to_string_lossy().to_string(),
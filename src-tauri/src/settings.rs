@@ -0,0 +1,170 @@
+use crate::data_paths::{app_data_root, read_store, write_store};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+// ── Backend-side settings ──────────────────────────────────────────────────
+// Most preferences live in the frontend's storage. The few the backend needs
+// on its own (before the webview is up, or inside commands that should not
+// take every knob as a parameter) are mirrored here and persisted to disk.
+
+const SETTINGS_FILE: &str = "backend_settings.json";
+
+/// Accepted values for `cover_preference`.
+pub const COVER_PREFERENCES: &[&str] = &["cover", "first-screenshot", "largest"];
+
+/// Accepted values for `close_behavior`.
+pub const CLOSE_BEHAVIORS: &[&str] = &["tray", "quit", "ask"];
+
+/// Sources `search_suggest_links` can query.
+pub const SEARCH_SOURCES: &[&str] = &["dlsite", "f95", "vndb", "mangagamer", "johren", "fakku"];
+
+/// Shortest and longest gap between scheduled scans, in minutes.
+pub const MIN_SCAN_INTERVAL_MINS: u64 = 5;
+pub const MAX_SCAN_INTERVAL_MINS: u64 = 24 * 60;
+
+/// DLsite storefront sections that can be searched.
+pub const DLSITE_SECTIONS: &[&str] = &["home", "maniax", "soft", "pro", "girls", "bl"];
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct BackendSettings {
+    /// DLsite sections queried by `search_suggest_links`. "maniax" holds the
+    /// adult doujin works that the all-ages "home" section never returns.
+    pub dlsite_sections: Vec<String>,
+    /// Log every scraper request (URL, status, size, timing) to the app log.
+    pub debug_http_logging: bool,
+    /// Save the raw HTML of failed scraper fetches to the temp directory.
+    pub debug_dump_html: bool,
+    /// Allow the `test_scrapers` diagnostic, which fetches a sample page from
+    /// each source it has a URL for.
+    pub scraper_self_test: bool,
+    /// Which image metadata fetchers use as the cover; see `COVER_PREFERENCES`.
+    pub cover_preference: String,
+    /// What the window's close button does; see `CLOSE_BEHAVIORS`. "ask"
+    /// leaves the choice to the frontend via a `confirm-close` event.
+    pub close_behavior: String,
+    /// Whether the launch-on-login entry passes `--minimized`.
+    pub autostart_minimized: bool,
+    /// Encrypt saved site cookies; see `vault`.
+    pub encrypt_cookies: bool,
+    /// Check saved site logins at startup and sign in again where they lapsed.
+    pub refresh_sessions_on_startup: bool,
+    /// Sources `search_suggest_links` queries when the caller names none;
+    /// see `SEARCH_SOURCES`.
+    pub search_sources: Vec<String>,
+    /// Match newly scanned games in the background and cache their metadata;
+    /// see `prefetch_metadata`.
+    pub prefetch_metadata: bool,
+    /// Skip background network work such as the metadata prefetch.
+    pub offline_mode: bool,
+    /// Rescan the saved library roots in the background; see `library_cache`.
+    pub scheduled_scan: bool,
+    /// Minutes between scheduled scans, at least `MIN_SCAN_INTERVAL_MINS`.
+    pub scheduled_scan_interval_mins: u64,
+}
+
+impl Default for BackendSettings {
+    fn default() -> Self {
+        Self {
+            dlsite_sections: vec!["home".to_string(), "maniax".to_string()],
+            debug_http_logging: false,
+            debug_dump_html: false,
+            scraper_self_test: false,
+            cover_preference: "cover".to_string(),
+            close_behavior: "tray".to_string(),
+            autostart_minimized: true,
+            encrypt_cookies: false,
+            refresh_sessions_on_startup: true,
+            search_sources: SEARCH_SOURCES.iter().map(|s| s.to_string()).collect(),
+            prefetch_metadata: false,
+            offline_mode: false,
+            scheduled_scan: false,
+            scheduled_scan_interval_mins: 60,
+        }
+    }
+}
+
+static SETTINGS: OnceLock<Mutex<BackendSettings>> = OnceLock::new();
+
+pub(crate) fn settings_path() -> PathBuf {
+    app_data_root().join(SETTINGS_FILE)
+}
+
+fn settings_state() -> &'static Mutex<BackendSettings> {
+    SETTINGS.get_or_init(|| {
+        let loaded = std::fs::read_to_string(settings_path())
+            .ok()
+            .and_then(|raw| read_store(&raw))
+            .unwrap_or_default();
+        Mutex::new(loaded)
+    })
+}
+
+/// Drops unknown values so a stale or hand-edited file cannot break lookups.
+fn sanitize(mut settings: BackendSettings) -> BackendSettings {
+    // Duplicates go too, keeping the first so the user's order survives.
+    let mut seen = HashSet::new();
+    settings
+        .dlsite_sections
+        .retain(|s| DLSITE_SECTIONS.contains(&s.as_str()) && seen.insert(s.clone()));
+    if settings.dlsite_sections.is_empty() {
+        settings.dlsite_sections = BackendSettings::default().dlsite_sections;
+    }
+    let mut seen = HashSet::new();
+    settings
+        .search_sources
+        .retain(|s| SEARCH_SOURCES.contains(&s.as_str()) && seen.insert(s.clone()));
+    if settings.search_sources.is_empty() {
+        settings.search_sources = BackendSettings::default().search_sources;
+    }
+    if !COVER_PREFERENCES.contains(&settings.cover_preference.as_str()) {
+        settings.cover_preference = BackendSettings::default().cover_preference;
+    }
+    if !CLOSE_BEHAVIORS.contains(&settings.close_behavior.as_str()) {
+        settings.close_behavior = BackendSettings::default().close_behavior;
+    }
+    settings.scheduled_scan_interval_mins = settings
+        .scheduled_scan_interval_mins
+        .clamp(MIN_SCAN_INTERVAL_MINS, MAX_SCAN_INTERVAL_MINS);
+    settings
+}
+
+/// Snapshot of the current settings.
+pub fn current() -> BackendSettings {
+    sanitize(settings_state().lock().unwrap().clone())
+}
+
+#[tauri::command]
+pub fn get_backend_settings() -> BackendSettings {
+    current()
+}
+
+/// Applies `f` to the current settings and persists the result.
+pub fn update(f: impl FnOnce(&mut BackendSettings)) -> Result<(), String> {
+    let mut settings = current();
+    f(&mut settings);
+    set_backend_settings(settings)
+}
+
+#[tauri::command]
+pub fn set_backend_settings(settings: BackendSettings) -> Result<(), String> {
+    let settings = sanitize(settings);
+    let path = settings_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let raw = write_store(&settings, true)?;
+    std::fs::write(path, raw).map_err(|e| e.to_string())?;
+    let encryption_changed = {
+        let mut state = settings_state().lock().unwrap();
+        let changed = state.encrypt_cookies != settings.encrypt_cookies;
+        *state = settings;
+        changed
+    };
+    if encryption_changed {
+        crate::metadata::resave_sessions();
+    }
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use crate::data_paths::{app_data_root, ACTIVE_PROFILE_FILE, PROFILES_DIR};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+// ── Data snapshots ─────────────────────────────────────────────────────────
+// A snapshot zips the whole data root so a data-mutating operation can be
+// undone. Snapshots live in `<data>/snapshots` and only the newest few are kept.
+
+const SNAPSHOTS_DIR: &str = "snapshots";
+
+/// Snapshots kept after rotation.
+const MAX_SNAPSHOTS: usize = 10;
+
+/// Entries of the base folder that belong to profile switching, not to the
+/// profile being snapshotted.
+const PROFILE_ENTRIES: &[&str] = &[PROFILES_DIR, ACTIVE_PROFILE_FILE];
+
+/// Regenerable caches left out unless the caller asks for them.
+const CACHE_ENTRIES: &[&str] = &["covers", "suggest-cache.json", "metadata-cache"];
+
+fn snapshots_dir() -> PathBuf {
+    app_data_root().join(SNAPSHOTS_DIR)
+}
+
+#[derive(Serialize)]
+pub struct DataSnapshotResult {
+    pub zip_path: String,
+    pub files: usize,
+}
+
+fn is_snapshot_file(path: &Path) -> bool {
+    path.file_name()
+        .map(|n| {
+            let n = n.to_string_lossy();
+            n.starts_with("libmaly-snapshot-") && n.ends_with(".zip")
+        })
+        .unwrap_or(false)
+}
+
+/// Deletes the oldest snapshots beyond `MAX_SNAPSHOTS`.
+fn rotate_snapshots(dir: &Path) {
+    let mut snaps: Vec<PathBuf> = match std::fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_file() && is_snapshot_file(p))
+            .collect(),
+        Err(_) => return,
+    };
+    if snaps.len() <= MAX_SNAPSHOTS {
+        return;
+    }
+    // Names embed a millisecond timestamp, so they sort chronologically.
+    snaps.sort();
+    for old in &snaps[..snaps.len() - MAX_SNAPSHOTS] {
+        if let Err(e) = std::fs::remove_file(old) {
+            crate::push_rust_log(
+                None,
+                "warn",
+                format!("Could not remove old snapshot {}: {e}", old.display()),
+            );
+        }
+    }
+}
+
+/// Zips the data root into `<data>/snapshots`. Caches are skipped unless
+/// `include_caches` is set; other snapshots are never included.
+#[tauri::command]
+pub fn snapshot_data(include_caches: Option<bool>) -> Result<DataSnapshotResult, String> {
+    let include_caches = include_caches.unwrap_or(false);
+    let root = app_data_root();
+    let dir = snapshots_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let zip_path = dir.join(format!("libmaly-snapshot-{}.zip", crate::now_ms()));
+    let tmp_path = zip_path.with_extension("zip.tmp");
+
+    let file = std::fs::File::create(&tmp_path).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let mut files = 0usize;
+    let walker = WalkDir::new(&root).into_iter().filter_entry(|e| {
+        let rel = match e.path().strip_prefix(&root) {
+            Ok(r) => r,
+            Err(_) => return false,
+        };
+        let top = match rel.components().next() {
+            Some(c) => c.as_os_str().to_string_lossy().into_owned(),
+            None => return true,
+        };
+        top != SNAPSHOTS_DIR
+            && !PROFILE_ENTRIES.contains(&top.as_str())
+            && (include_caches || !CACHE_ENTRIES.contains(&top.as_str()))
+    });
+    for entry in walker.filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let rel = match entry.path().strip_prefix(&root) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+        let zip_name = rel.to_string_lossy().replace('\\', "/");
+        zip.start_file(zip_name, options)
+            .map_err(|e| e.to_string())?;
+        let mut src = std::fs::File::open(entry.path()).map_err(|e| e.to_string())?;
+        std::io::copy(&mut src, &mut zip).map_err(|e| e.to_string())?;
+        files += 1;
+    }
+    zip.finish().map_err(|e| e.to_string())?;
+    std::fs::rename(&tmp_path, &zip_path).map_err(|e| e.to_string())?;
+
+    rotate_snapshots(&dir);
+    crate::push_rust_log(
+        None,
+        "info",
+        format!(
+            "Data snapshot written: {} ({files} files)",
+            zip_path.display()
+        ),
+    );
+    Ok(DataSnapshotResult {
+        zip_path: zip_path.to_string_lossy().into_owned(),
+        files,
+    })
+}
+
+/// Extracts a snapshot over the data root. Files missing from the snapshot
+/// are left alone. Settings and sessions already loaded in memory only pick
+/// up the restored files after a restart.
+#[tauri::command]
+pub fn restore_data_snapshot(zip_path: String) -> Result<usize, String> {
+    let root = app_data_root();
+    let file = std::fs::File::open(&zip_path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+
+    let mut restored = 0usize;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        // Reject absolute paths and `..` so a crafted zip cannot escape the root.
+        let rel = match entry.enclosed_name() {
+            Some(r) => r,
+            None => continue,
+        };
+        if rel.starts_with(SNAPSHOTS_DIR) || PROFILE_ENTRIES.iter().any(|e| rel.starts_with(e)) {
+            continue;
+        }
+        let out_path = root.join(rel);
+        if entry.is_dir() {
+            std::fs::create_dir_all(&out_path).map_err(|e| e.to_string())?;
+            continue;
+        }
+        if let Some(parent) = out_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let mut out = std::fs::File::create(&out_path).map_err(|e| e.to_string())?;
+        std::io::copy(&mut entry, &mut out).map_err(|e| e.to_string())?;
+        restored += 1;
+    }
+
+    crate::push_rust_log(
+        None,
+        "info",
+        format!("Restored {restored} files from snapshot {zip_path}"),
+    );
+    Ok(restored)
+}
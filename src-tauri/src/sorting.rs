@@ -0,0 +1,117 @@
+use crate::metadata::cached_metadata;
+use crate::playtime::{get_playtime, PlaytimeEntry};
+use crate::Game;
+use serde::Deserialize;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+// ── Library sorting ────────────────────────────────────────────────────────
+// Sort keys that need data from other stores (playtime, cached metadata) are
+// resolved here so the frontend does not have to join them itself.
+
+/// Accepted values for `sort_games`' `key`.
+const SORT_KEYS: &[&str] = &[
+    "name",
+    "playtime",
+    "last_played",
+    "rating",
+    "size",
+    "linked",
+];
+
+/// Metadata page a game is linked to, as the frontend stores it.
+#[derive(Deserialize)]
+pub struct MetadataLink {
+    pub source: String,
+    pub url: String,
+    /// When the link was made, unix seconds.
+    #[serde(default)]
+    pub linked_at: Option<u64>,
+}
+
+/// Metadata rating as a fraction of the source's scale. VNDB rates out of
+/// 10, the stores out of 5; an explicit "x/y" wins over both.
+fn normalized_rating(source: &str, rating: &str) -> Option<f64> {
+    let rating = rating.trim();
+    let number = |s: &str| -> Option<f64> {
+        let end = s
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(s.len());
+        s[..end].parse().ok()
+    };
+    let value = number(rating)?;
+    let scale = rating
+        .split_once('/')
+        .and_then(|(_, rest)| number(rest.trim()))
+        .filter(|s| *s > 0.0)
+        .unwrap_or(if source == "vndb" { 10.0 } else { 5.0 });
+    Some(value / scale)
+}
+
+/// Returns `games` ordered by `key`: "name", "playtime", "last_played",
+/// "rating", "size" or "linked". `links` maps game paths to their metadata
+/// page, needed for "rating" and "linked". Games without a value for the key
+/// go last in either direction; ties fall back to the name.
+#[tauri::command]
+pub fn sort_games(
+    games: Vec<Game>,
+    key: String,
+    descending: Option<bool>,
+    links: Option<HashMap<String, MetadataLink>>,
+) -> Result<Vec<Game>, String> {
+    if !SORT_KEYS.contains(&key.as_str()) {
+        return Err(format!(
+            "Unknown sort key: {key}. Expected one of {}",
+            SORT_KEYS.join(", ")
+        ));
+    }
+    let descending = descending.unwrap_or(false);
+    let links = links.unwrap_or_default();
+    let playtime: HashMap<String, PlaytimeEntry> = match key.as_str() {
+        "playtime" | "last_played" => get_playtime(None).games,
+        _ => HashMap::new(),
+    };
+
+    let value = |game: &Game| -> Option<f64> {
+        match key.as_str() {
+            "playtime" => playtime.get(&game.path).map(|p| p.total_secs as f64),
+            "last_played" => playtime
+                .get(&game.path)
+                .map(|p| p.last_played)
+                .filter(|t| *t > 0)
+                .map(|t| t as f64),
+            "rating" => {
+                let link = links.get(&game.path)?;
+                let meta = cached_metadata(&link.source, &link.url)?;
+                normalized_rating(&link.source, meta.rating.as_deref()?)
+            }
+            "size" => Some(game.size_bytes as f64),
+            "linked" => links
+                .get(&game.path)
+                .and_then(|l| l.linked_at)
+                .map(|t| t as f64),
+            _ => None,
+        }
+    };
+
+    let mut keyed: Vec<(Option<f64>, String, Game)> = games
+        .into_iter()
+        .map(|game| (value(&game), game.name.to_lowercase(), game))
+        .collect();
+    keyed.sort_by(|(va, na, _), (vb, nb, _)| {
+        let by_value = match (va, vb) {
+            (Some(a), Some(b)) if descending => b.total_cmp(a),
+            (Some(a), Some(b)) => a.total_cmp(b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        let by_name = if key == "name" && descending {
+            nb.cmp(na)
+        } else {
+            na.cmp(nb)
+        };
+        by_value.then(by_name)
+    });
+    Ok(keyed.into_iter().map(|(_, _, game)| game).collect())
+}
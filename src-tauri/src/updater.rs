@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
@@ -18,6 +18,19 @@ pub struct UpdateResult {
     pub extracted_temp: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RollbackResult {
+    pub files_reverted: u32,
+    /// Absolute path of the originals that were restored
+    /// (`.libmaly_backup/_overwritten`).
+    pub backup_dir: String,
+    pub warnings: Vec<String>,
+}
+
+/// Subfolder of `.libmaly_backup` holding the originals of the files the last
+/// update overwrote, so `rollback_update` can put them back.
+const OVERWRITTEN_DIR: &str = "_overwritten";
+
 // ── Save / config detection ────────────────────────────────────────────────
 
 /// Patterns that almost certainly contain saves or user-specific data.
@@ -45,8 +58,21 @@ const PROTECTED_DIR_NAMES: &[&str] = &[
     "saves",
     // Unity
     "playerprefs",
+    // KiriKiri keeps saves in savedata/ and Wolf RPG in Data/save/, both
+    // matched above
+    "mysav",
 ];
 
+/// File names that are always save data, wherever they sit.
+const PROTECTED_FILE_NAMES: &[&str] = &[
+    // Ren'Py persistent data (seen endings, unlocked gallery)
+    "persistent",
+];
+
+/// Extensions of the files some engines keep next to their `.sav` files
+/// (slot index, thumbnails).  Only protected in a folder that holds a `.sav`.
+const SAVE_COMPANION_EXTENSIONS: &[&str] = &["ctl", "dat"];
+
 /// File extensions that are always save/config data regardless of location.
 const PROTECTED_EXTENSIONS: &[&str] = &[
     "sav", "save", "rpgsave", "rpgrmvp", "rvdata", "rvdata2",
@@ -69,6 +95,12 @@ fn is_protected(rel: &Path) -> bool {
             }
         }
     }
+    if let Some(name) = rel.file_name() {
+        let name_lower = name.to_string_lossy().to_lowercase();
+        if PROTECTED_FILE_NAMES.iter().any(|p| name_lower == *p) {
+            return true;
+        }
+    }
     // Check file extension
     if let Some(ext) = rel.extension() {
         let ext_lower = ext.to_string_lossy().to_lowercase();
@@ -83,6 +115,30 @@ fn is_protected(rel: &Path) -> bool {
     false
 }
 
+/// True for a `.ctl`/`.dat` file at `rel` whose folder in the game dir also
+/// holds a `.sav` file.  Folder lookups are memoised in `sav_dirs`.
+fn is_save_companion(game_dir: &Path, rel: &Path, sav_dirs: &mut HashMap<PathBuf, bool>) -> bool {
+    let is_companion_ext = rel.extension().is_some_and(|ext| {
+        let ext_lower = ext.to_string_lossy().to_lowercase();
+        SAVE_COMPANION_EXTENSIONS.iter().any(|e| ext_lower == *e)
+    });
+    if !is_companion_ext {
+        return false;
+    }
+    let dir = game_dir.join(rel.parent().unwrap_or(Path::new("")));
+    *sav_dirs.entry(dir).or_insert_with_key(|dir| {
+        fs::read_dir(dir)
+            .map(|it| {
+                it.filter_map(|e| e.ok()).any(|e| {
+                    e.path()
+                        .extension()
+                        .is_some_and(|ext| ext.eq_ignore_ascii_case("sav"))
+                })
+            })
+            .unwrap_or(false)
+    })
+}
+
 // ── ZIP extraction ─────────────────────────────────────────────────────────
 
 #[cfg(feature = "zip-support")]
@@ -109,11 +165,21 @@ fn extract_zip(zip_path: &Path, dest: &Path) -> Result<(), String> {
 }
 
 fn extract_zip_native(zip_path: &Path, dest: &Path) -> Result<(), String> {
+    extract_zip_with_progress(zip_path, dest, |_, _| {})
+}
+
+/// `extract_zip_native`, calling `on_entry(done, total)` after each entry.
+pub(crate) fn extract_zip_with_progress(
+    zip_path: &Path,
+    dest: &Path,
+    mut on_entry: impl FnMut(usize, usize),
+) -> Result<(), String> {
     fs::create_dir_all(dest).map_err(|e| e.to_string())?;
     // Use the `zip` crate (enabled via Cargo.toml feature flag)
     let f = fs::File::open(zip_path).map_err(|e| e.to_string())?;
     let mut archive = zip::ZipArchive::new(f).map_err(|e| e.to_string())?;
-    for i in 0..archive.len() {
+    let total = archive.len();
+    for i in 0..total {
         use std::io::Read;
         let mut entry = archive.by_index(i).map_err(|e| e.to_string())?;
         let out_path = match entry.enclosed_name() {
@@ -130,15 +196,65 @@ fn extract_zip_native(zip_path: &Path, dest: &Path) -> Result<(), String> {
             entry.read_to_end(&mut buf).map_err(|e| e.to_string())?;
             fs::write(&out_path, &buf).map_err(|e| e.to_string())?;
         }
+        on_entry(i + 1, total);
     }
     Ok(())
 }
 
+// ── tar.gz / 7z extraction ─────────────────────────────────────────────────
+
+fn is_tar_gz(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    name.ends_with(".tar.gz") || name.ends_with(".tgz")
+}
+
+/// Extracts a `.tar.gz`.  `tar::Archive::unpack` refuses entries that would
+/// land outside `dest` (absolute paths, `..`).
+fn extract_tar_gz(archive: &Path, dest: &Path) -> Result<(), String> {
+    fs::create_dir_all(dest).map_err(|e| e.to_string())?;
+    let f = fs::File::open(archive).map_err(|e| e.to_string())?;
+    tar::Archive::new(flate2::read::GzDecoder::new(std::io::BufReader::new(f)))
+        .unpack(dest)
+        .map_err(|e| format!("Could not extract {}: {}", archive.display(), e))
+}
+
+/// Whether `extract_archive` can unpack `path`.
+pub(crate) fn is_supported_archive(path: &Path) -> bool {
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    ext == "zip" || ext == "7z" || is_tar_gz(path)
+}
+
+/// Extracts a `.zip`, `.tar.gz`/`.tgz` or `.7z` into `dest`. 7z archives need
+/// the 7-Zip binary.
+pub(crate) fn extract_archive(archive: &Path, dest: &Path) -> Result<(), String> {
+    if is_tar_gz(archive) {
+        return extract_tar_gz(archive, dest);
+    }
+    let ext = archive
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "zip" => extract_zip_native(archive, dest),
+        "7z" => {
+            fs::create_dir_all(dest).map_err(|e| e.to_string())?;
+            crate::archives::extract_7z(archive, dest, |_| {})
+        }
+        _ => Err(format!("Unsupported archive: {}", archive.display())),
+    }
+}
+
 // ── Strip single top-level wrapper directory from extracted content ─────────
 
 /// If an archive was extracted and it contains only one top-level directory
 /// (common packaging pattern: `game-v2.0/game.exe`), return the path to that subdir.
-fn unwrap_single_dir(dir: &Path) -> PathBuf {
+pub(crate) fn unwrap_single_dir(dir: &Path) -> PathBuf {
     let entries: Vec<_> = match fs::read_dir(dir) {
         Ok(it) => it.filter_map(|e| e.ok()).collect(),
         Err(_) => return dir.to_path_buf(),
@@ -155,16 +271,19 @@ fn unwrap_single_dir(dir: &Path) -> PathBuf {
 // ── Core merge logic ───────────────────────────────────────────────────────
 
 /// Recursively copies all files from `src` into `dst`, skipping any relative
-/// paths that are protected.  Returns (updated, skipped).
+/// paths that are protected.  Files about to be replaced are first copied to
+/// the same relative path under `overwritten`.  Returns (updated, skipped).
 fn merge_dirs(
     src: &Path,
     dst: &Path,
     src_root: &Path,
     protected_rel: &HashSet<PathBuf>,
+    overwritten: &Path,
     warnings: &mut Vec<String>,
 ) -> (u32, u32) {
     let mut updated = 0u32;
     let mut skipped = 0u32;
+    let mut sav_dirs: HashMap<PathBuf, bool> = HashMap::new();
 
     for entry in WalkDir::new(src).min_depth(1).into_iter().filter_map(|e| e.ok()) {
         let abs_src = entry.path();
@@ -188,12 +307,21 @@ fn merge_dirs(
         }
 
         // It's a file
-        if prot {
+        if prot || is_save_companion(dst, &rel, &mut sav_dirs) {
             skipped += 1;
             continue;
         }
 
         let dst_file = dst.join(&rel);
+        if dst_file.is_file() {
+            let original = overwritten.join(&rel);
+            if let Some(p) = original.parent() {
+                let _ = fs::create_dir_all(p);
+            }
+            if let Err(e) = fs::copy(&dst_file, &original) {
+                warnings.push(format!("backup {}: {}", dst_file.display(), e));
+            }
+        }
         if let Some(p) = dst_file.parent() {
             let _ = fs::create_dir_all(p);
         }
@@ -229,20 +357,15 @@ pub async fn update_game(
 
     // ── Step 1: Resolve new-version folder ───────────────────────────
     let new_dir = {
-        let ext = source_path
-            .extension()
-            .map(|e| e.to_string_lossy().to_lowercase())
-            .unwrap_or_default();
-
-        if ext == "zip" {
+        if is_supported_archive(&source_path) {
             // Extract to a temp directory next to the game folder
             let temp = game_dir
                 .parent()
                 .unwrap_or(&game_dir)
                 .join(format!(".libmaly_update_extract_{}", std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs()));
-            extract_zip_native(&source_path, &temp)
-                .map_err(|e| format!("ZIP extraction failed: {}", e))?;
+            extract_archive(&source_path, &temp)
+                .map_err(|e| format!("Archive extraction failed: {}", e))?;
             extracted_temp = Some(temp.to_string_lossy().to_string());
             // Unwrap a single top-level directory if present
             unwrap_single_dir(&temp)
@@ -250,7 +373,7 @@ pub async fn update_game(
             source_path.clone()
         } else {
             return Err(format!(
-                "Unsupported source: '{}'. Please provide a folder or a .zip file.",
+                "Unsupported source: '{}'. Please provide a folder or a .zip, .tar.gz or .7z archive.",
                 new_source
             ));
         }
@@ -302,8 +425,21 @@ pub async fn update_game(
     }
 
     // ── Step 4: Copy new files over the game dir (skip protected) ────
-    let (files_updated, files_skipped) =
-        merge_dirs(&new_dir, &game_dir, &new_dir, &protected_rel, &mut warnings);
+    // Originals of overwritten files go to `_overwritten`, which only ever
+    // holds the state from before the latest update.
+    let overwritten_dir = backup_dir.join(OVERWRITTEN_DIR);
+    if overwritten_dir.exists() {
+        fs::remove_dir_all(&overwritten_dir)
+            .map_err(|e| format!("Could not clear the previous update backup: {}", e))?;
+    }
+    let (files_updated, files_skipped) = merge_dirs(
+        &new_dir,
+        &game_dir,
+        &new_dir,
+        &protected_rel,
+        &overwritten_dir,
+        &mut warnings,
+    );
 
     // ── Step 5: Restore protected dirs from backup (they may have
     //           been overwritten by the new version's empty placeholders) ──
@@ -342,6 +478,92 @@ pub async fn update_game(
     })
 }
 
+/// Undo the last `update_game` for the game at `game_exe` by copying the
+/// originals saved in `.libmaly_backup/_overwritten` back over the game dir.
+/// Files the update added are left in place.
+#[tauri::command]
+pub async fn rollback_update(game_exe: String) -> Result<RollbackResult, String> {
+    let game_dir = Path::new(&game_exe)
+        .parent()
+        .ok_or("Cannot determine game directory")?
+        .to_path_buf();
+    let overwritten_dir = game_dir.join(".libmaly_backup").join(OVERWRITTEN_DIR);
+
+    let mut files_reverted = 0u32;
+    let mut warnings: Vec<String> = Vec::new();
+    if !overwritten_dir.is_dir() {
+        warnings.push(format!(
+            "No update backup found at {}; nothing to roll back",
+            overwritten_dir.display()
+        ));
+    }
+    for entry in WalkDir::new(&overwritten_dir)
+        .min_depth(1)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let rel = match entry.path().strip_prefix(&overwritten_dir) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+        let dst_file = game_dir.join(rel);
+        if let Some(p) = dst_file.parent() {
+            let _ = fs::create_dir_all(p);
+        }
+        match fs::copy(entry.path(), &dst_file) {
+            Ok(_) => files_reverted += 1,
+            Err(e) => warnings.push(format!("restore {}: {}", rel.display(), e)),
+        }
+    }
+
+    Ok(RollbackResult {
+        files_reverted,
+        backup_dir: overwritten_dir.to_string_lossy().to_string(),
+        warnings,
+    })
+}
+
+/// Every entry of a zip with a safe (enclosed) name, as that name plus an
+/// is-directory flag.
+pub(crate) fn zip_entries<R: std::io::Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+) -> Result<Vec<(PathBuf, bool)>, String> {
+    let mut entries = Vec::new();
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        if let Some(name) = entry.enclosed_name() {
+            entries.push((name, entry.is_dir()));
+        }
+    }
+    Ok(entries)
+}
+
+/// The zip counterpart of `unwrap_single_dir`: the one top-level directory
+/// that holds every entry of the archive, if there is one.  `entries` come
+/// from `zip_entries`.
+pub(crate) fn zip_wrapper_dir(entries: &[(PathBuf, bool)]) -> Option<PathBuf> {
+    let mut top: Option<&std::ffi::OsStr> = None;
+    for (name, is_dir) in entries {
+        let mut comps = name.components();
+        let first = match comps.next() {
+            Some(std::path::Component::Normal(c)) => c,
+            _ => return None,
+        };
+        // A file at the archive root means there is no wrapper.
+        if !is_dir && comps.next().is_none() {
+            return None;
+        }
+        match top {
+            Some(t) if t != first => return None,
+            _ => top = Some(first),
+        }
+    }
+    top.map(PathBuf::from)
+}
+
 /// Scan a folder or zip and return a preview: which files would be updated
 /// and which protected directories were found — without making any changes.
 #[tauri::command]
@@ -360,11 +582,12 @@ pub async fn preview_update(
         return Err(format!("Path does not exist: {}", new_source));
     }
 
-    // Detect new-version root (no actual extraction for preview — just peek inside zip)
+    // Detect new-version root (no actual extraction for preview — zips are
+    // read in place below, other archives are not previewed)
     let new_dir_opt: Option<PathBuf> = if source_path.is_dir() {
         Some(source_path.clone())
     } else {
-        None // for zip we can't easily preview without extracting
+        None
     };
 
     // Collect protected dirs in old game dir
@@ -382,6 +605,7 @@ pub async fn preview_update(
     // Count changed files if new_dir is available
     let mut files_to_update: u32 = 0;
     let mut new_files: u32 = 0;
+    let mut sav_dirs: HashMap<PathBuf, bool> = HashMap::new();
     let source_is_zip = source_path.extension()
         .map(|e| e.to_string_lossy().to_lowercase() == "zip")
         .unwrap_or(false);
@@ -393,19 +617,45 @@ pub async fn preview_update(
                 Ok(r) => r,
                 Err(_) => continue,
             };
-            if is_protected(rel) { continue; }
+            if is_protected(rel) || is_save_companion(&game_dir, rel, &mut sav_dirs) {
+                continue;
+            }
             let dst = game_dir.join(rel);
             if dst.exists() { files_to_update += 1; } else { new_files += 1; }
         }
     }
 
-    // Estimate file count from zip (just count entries)
-    let zip_entry_count: Option<u32> = if source_is_zip {
-        match fs::File::open(&source_path).map(zip::ZipArchive::new) {
-            Ok(Ok(archive)) => Some(archive.len() as u32),
-            _ => None,
+    // Peek inside a zip without extracting: same unwrap and protection rules
+    // as `update_game` applies after extraction.
+    let mut zip_entry_count: Option<u32> = None;
+    if source_is_zip {
+        let f = fs::File::open(&source_path).map_err(|e| e.to_string())?;
+        let mut archive = zip::ZipArchive::new(f).map_err(|e| e.to_string())?;
+        zip_entry_count = Some(archive.len() as u32);
+
+        let entries = zip_entries(&mut archive)?;
+        let wrapper = zip_wrapper_dir(&entries);
+        for (name, is_dir) in &entries {
+            if *is_dir {
+                continue;
+            }
+            let rel = match &wrapper {
+                Some(w) => name.strip_prefix(w).unwrap_or(name),
+                None => name,
+            };
+            if rel.as_os_str().is_empty()
+                || is_protected(rel)
+                || is_save_companion(&game_dir, rel, &mut sav_dirs)
+            {
+                continue;
+            }
+            if game_dir.join(rel).exists() {
+                files_to_update += 1;
+            } else {
+                new_files += 1;
+            }
         }
-    } else { None };
+    }
 
     Ok(UpdatePreview {
         game_dir: game_dir.to_string_lossy().to_string(),
@@ -426,3 +676,72 @@ pub struct UpdatePreview {
     pub zip_entry_count: Option<u32>,
     pub protected_dirs: Vec<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, rel: &str, contents: &str) {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn merge_keeps_engine_saves() {
+        let root = std::env::temp_dir().join(format!("libmaly-merge-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (game, build) = (root.join("game"), root.join("build"));
+
+        let saves = [
+            "savedata/data0.kdt",       // KiriKiri
+            "Data/save/SaveData01.dat", // Wolf RPG
+            "mysav/slot01.bin",
+            "game/persistent", // Ren'Py
+            "slots/slot1.sav", // .ctl/.dat beside a .sav
+            "slots/slot1.ctl",
+            "slots/slot1.dat",
+        ];
+        for rel in saves {
+            write(&game, rel, "old");
+            write(&build, rel, "new");
+        }
+        // Game files, including a .dat with no .sav beside it, are updated.
+        let updates = ["Game.exe", "data/scenario.dat"];
+        for rel in updates {
+            write(&game, rel, "old");
+            write(&build, rel, "new");
+        }
+
+        let mut warnings = Vec::new();
+        let (updated, skipped) = merge_dirs(
+            &build,
+            &game,
+            &build,
+            &HashSet::new(),
+            &root.join("overwritten"),
+            &mut warnings,
+        );
+
+        for rel in saves {
+            assert_eq!(
+                fs::read_to_string(game.join(rel)).unwrap(),
+                "old",
+                "{rel} was overwritten"
+            );
+        }
+        for rel in updates {
+            assert_eq!(
+                fs::read_to_string(game.join(rel)).unwrap(),
+                "new",
+                "{rel} was not updated"
+            );
+        }
+        assert_eq!(
+            (updated, skipped),
+            (updates.len() as u32, saves.len() as u32)
+        );
+        assert!(warnings.is_empty(), "{warnings:?}");
+        let _ = fs::remove_dir_all(&root);
+    }
+}
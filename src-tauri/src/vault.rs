@@ -0,0 +1,128 @@
+use crate::settings;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use std::path::Path;
+use std::sync::Mutex;
+
+// ── Encryption at rest ─────────────────────────────────────────────────────
+// Session cookie files can be stored encrypted (ChaCha20-Poly1305). The key
+// comes from the app-lock password while one is set, otherwise from a random
+// key kept in the OS keyring. Plaintext files from older versions are still
+// read and get encrypted on their next save.
+
+/// Prefix marking an encrypted file; followed by the nonce and ciphertext.
+const MAGIC: &[u8] = b"LMENC1";
+const NONCE_LEN: usize = 12;
+
+pub(crate) const KEYRING_SERVICE: &str = "libmaly";
+const KEYRING_KEY_USER: &str = "cookie-encryption-key";
+
+/// Key derived from the app-lock password, present once the session is unlocked.
+static PASSWORD_KEY: Mutex<Option<[u8; 32]>> = Mutex::new(None);
+
+/// Installs (or, with `None`, forgets) the key derived from the app-lock password.
+pub fn set_password_key(key: Option<[u8; 32]>) {
+    *PASSWORD_KEY.lock().unwrap() = key;
+}
+
+/// Random key stored in the OS keyring, created on first use.
+fn keyring_key() -> Result<[u8; 32], String> {
+    let entry =
+        keyring::Entry::new(KEYRING_SERVICE, KEYRING_KEY_USER).map_err(|e| e.to_string())?;
+    let b64 = base64::engine::general_purpose::STANDARD;
+    match entry.get_password() {
+        Ok(stored) => {
+            let bytes = b64.decode(stored.trim()).map_err(|e| e.to_string())?;
+            bytes
+                .try_into()
+                .map_err(|_| "Keyring holds a malformed encryption key".to_string())
+        }
+        Err(keyring::Error::NoEntry) => {
+            let key: [u8; 32] = ChaCha20Poly1305::generate_key(&mut OsRng).into();
+            entry
+                .set_password(&b64.encode(key))
+                .map_err(|e| e.to_string())?;
+            Ok(key)
+        }
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn current_key() -> Result<[u8; 32], String> {
+    if crate::applock::is_enabled() {
+        return PASSWORD_KEY
+            .lock()
+            .unwrap()
+            .ok_or_else(|| "LIBMALY is locked".to_string());
+    }
+    keyring_key()
+}
+
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+fn encrypt(plain: &[u8]) -> Result<Vec<u8>, String> {
+    let key = current_key()?;
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let sealed = cipher.encrypt(&nonce, plain).map_err(|e| e.to_string())?;
+    let mut out = Vec::with_capacity(MAGIC.len() + NONCE_LEN + sealed.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&sealed);
+    Ok(out)
+}
+
+fn decrypt(data: &[u8]) -> Result<Vec<u8>, String> {
+    let body = &data[MAGIC.len()..];
+    if body.len() < NONCE_LEN {
+        return Err("Encrypted file is truncated".to_string());
+    }
+    let (nonce, sealed) = body.split_at(NONCE_LEN);
+    let key = current_key()?;
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+    cipher
+        .decrypt(Nonce::from_slice(nonce), sealed)
+        .map_err(|_| "Could not decrypt file (wrong key?)".to_string())
+}
+
+/// Reads a secret file, decrypting it when needed. Legacy plaintext files are
+/// returned as-is.
+pub fn read_secret_file(path: &Path) -> Result<Vec<u8>, String> {
+    let data = std::fs::read(path).map_err(|e| e.to_string())?;
+    if is_encrypted(&data) {
+        decrypt(&data)
+    } else {
+        Ok(data)
+    }
+}
+
+/// Writes a secret file, encrypted when `encrypt_cookies` is on. If the
+/// keyring is unavailable the file is written in plaintext and a warning is
+/// logged, so a missing keyring never costs the user their session.
+pub fn write_secret_file(path: &Path, plain: &[u8]) -> Result<(), String> {
+    let data = if settings::current().encrypt_cookies {
+        match encrypt(plain) {
+            Ok(sealed) => sealed,
+            // Never fall back to plaintext because the password key is missing.
+            Err(e) if crate::applock::is_enabled() => return Err(e),
+            Err(e) => {
+                crate::push_rust_log(
+                    None,
+                    "warn",
+                    format!(
+                        "Could not encrypt {}; saving it unencrypted: {}",
+                        path.display(),
+                        e
+                    ),
+                );
+                plain.to_vec()
+            }
+        }
+    } else {
+        plain.to_vec()
+    };
+    std::fs::write(path, data).map_err(|e| e.to_string())
+}